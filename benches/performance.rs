@@ -3,7 +3,9 @@
 //! These benchmarks measure the performance of key operations:
 //! - File walking speed with various filters
 //! - Regex rule execution on different file sizes
+//! - Many regex rules prefiltered by one RegexSet scan per file
 //! - AST rule execution with parser caching
+//! - Many AST rules sharing one parse per file
 //! - Full check workflow end-to-end
 
 use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
@@ -147,7 +149,9 @@ fn bench_regex_execution(c: &mut Criterion) {
                     file_path: Path::new("test.rs"),
                     content,
                     ast: None,
+                    line_offsets: None,
                     region_resolver: None,
+                    walked_files: None,
                 };
                 let violations = rule.execute(&ctx);
                 black_box(violations)
@@ -158,6 +162,92 @@ fn bench_regex_execution(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark the engine with many regex rules
+///
+/// The engine scans each file once with a combined RegexSet and only runs the
+/// full search for rules whose pattern occurs, so most of these rules (which
+/// never match) should cost little beyond the single scan. `per_rule` runs
+/// every rule's full search on every file for comparison.
+fn bench_regex_prefilter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regex_prefilter");
+    group.sample_size(10);
+
+    let temp_dir = create_test_files(50, 5_000);
+    let walker = FileWalker::new(temp_dir.path(), &[], &[]).unwrap();
+    let files: Vec<FileEntry> = walker.walk().filter_map(Result::ok).collect();
+    let contents: Vec<String> = files
+        .iter()
+        .map(|f| fs::read_to_string(&f.path).unwrap())
+        .collect();
+
+    for rule_count in [1, 10, 40].iter() {
+        let rule_dir = temp_dir.path().join(format!("regex-rules-{}", rule_count));
+        fs::create_dir(&rule_dir).unwrap();
+        for i in 0..*rule_count {
+            // Rule 0 matches every file; the rest never match
+            let pattern = if i == 0 {
+                "implement".to_string()
+            } else {
+                format!("forbidden_call_{}", i)
+            };
+            fs::write(
+                rule_dir.join(format!("rule-{}.toml", i)),
+                format!(
+                    "[rule]\nid = \"rule-{i}\"\ndescription = \"Rule {i}\"\nseverity = \"warning\"\n\n[match]\npattern = \"{pattern}\"\n"
+                ),
+            )
+            .unwrap();
+        }
+
+        let mut registry = RuleRegistry::new();
+        registry.load_custom_regex_rules(&rule_dir, None).unwrap();
+        let rules: Vec<&dyn Rule> = registry.iter_rules().collect();
+
+        group.bench_with_input(
+            BenchmarkId::new("per_rule", rule_count),
+            &contents,
+            |b, contents| {
+                b.iter(|| {
+                    let mut total = 0;
+                    for (file, content) in files.iter().zip(contents) {
+                        for rule in &rules {
+                            let ctx = ExecutionContext {
+                                file_path: &file.path,
+                                content,
+                                ast: None,
+                                line_offsets: None,
+                                region_resolver: None,
+                                walked_files: None,
+                            };
+                            total += rule.execute(&ctx).len();
+                        }
+                    }
+                    black_box(total)
+                });
+            },
+        );
+
+        let mut engine_registry = RuleRegistry::new();
+        engine_registry
+            .load_custom_regex_rules(&rule_dir, None)
+            .unwrap();
+        let engine = ratchets::engine::executor::ExecutionEngine::new(engine_registry, None);
+
+        group.bench_with_input(
+            BenchmarkId::new("engine", rule_count),
+            &files,
+            |b, files| {
+                b.iter(|| {
+                    let result = engine.execute(files.clone());
+                    black_box(result)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 /// Benchmark AST rule execution with parser caching
 ///
 /// This demonstrates the effectiveness of parser caching.
@@ -187,7 +277,9 @@ fn bench_ast_execution(c: &mut Criterion) {
                         file_path: Path::new("test.rs"),
                         content,
                         ast: None,
+                        line_offsets: None,
                         region_resolver: None,
+                        walked_files: None,
                     };
                     let violations = rule.execute(&ctx);
                     black_box(violations)
@@ -199,6 +291,155 @@ fn bench_ast_execution(c: &mut Criterion) {
     group.finish();
 }
 
+/// Create a set of Rust AST rules that each match a different method name
+#[cfg(feature = "lang-rust")]
+fn create_method_ast_rules(count: usize) -> Vec<AstRule> {
+    (0..count)
+        .map(|i| {
+            let toml = format!(
+                r#"
+[rule]
+id = "no-method-{i}"
+description = "Find method_{i} calls"
+severity = "error"
+
+[match]
+query = """
+(call_expression
+  function: (field_expression
+    field: (field_identifier) @method)
+  (#eq? @method "method_{i}")) @violation
+"""
+language = "rust"
+"#
+            );
+            AstRule::from_toml(&toml).unwrap()
+        })
+        .collect()
+}
+
+/// Benchmark many AST rules against one file
+///
+/// Compares handing every rule the same parsed tree (what the engine does)
+/// against letting each rule parse the file itself. Queries are compiled once
+/// at load time in both cases, so the difference is the repeated parsing.
+#[cfg(feature = "lang-rust")]
+fn bench_ast_shared_tree(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ast_shared_tree");
+
+    let content = format!(
+        "fn main() {{\n{}\n}}\n",
+        "    value.method_0().method_1().unwrap();\n".repeat(200)
+    );
+    let cache = ParserCache::new();
+
+    for rule_count in [1, 10, 30].iter() {
+        let rules = create_method_ast_rules(*rule_count);
+
+        group.throughput(Throughput::Elements(*rule_count as u64));
+
+        // Benchmark: Parse once and share the tree across all rules
+        group.bench_with_input(
+            BenchmarkId::new("shared_tree", rule_count),
+            &rules,
+            |b, rules| {
+                b.iter(|| {
+                    let mut parser = cache.get_parser(Language::Rust).unwrap();
+                    let tree = parser.parse(&content, None).unwrap();
+                    let ctx = ExecutionContext {
+                        file_path: Path::new("test.rs"),
+                        content: &content,
+                        ast: Some(&tree),
+                        line_offsets: None,
+                        region_resolver: None,
+                        walked_files: None,
+                    };
+                    let violations: Vec<_> =
+                        rules.iter().flat_map(|rule| rule.execute(&ctx)).collect();
+                    black_box(violations)
+                });
+            },
+        );
+
+        // Benchmark: Each rule parses the file on its own
+        group.bench_with_input(
+            BenchmarkId::new("parse_per_rule", rule_count),
+            &rules,
+            |b, rules| {
+                b.iter(|| {
+                    let ctx = ExecutionContext {
+                        file_path: Path::new("test.rs"),
+                        content: &content,
+                        ast: None,
+                        line_offsets: None,
+                        region_resolver: None,
+                        walked_files: None,
+                    };
+                    let violations: Vec<_> =
+                        rules.iter().flat_map(|rule| rule.execute(&ctx)).collect();
+                    black_box(violations)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Benchmark the engine with many AST rules across many files
+///
+/// Each file is parsed once by the engine regardless of how many AST rules
+/// apply, so time should grow with query count rather than parse count.
+#[cfg(feature = "lang-rust")]
+fn bench_ast_engine_many_rules(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ast_engine_many_rules");
+    group.sample_size(10);
+
+    let temp_dir = create_test_files(50, 1000);
+    let walker = FileWalker::new(temp_dir.path(), &[], &[]).unwrap();
+    let files: Vec<FileEntry> = walker.walk().filter_map(Result::ok).collect();
+
+    for rule_count in [1, 30].iter() {
+        let rule_dir = temp_dir.path().join(format!("ast-rules-{}", rule_count));
+        fs::create_dir(&rule_dir).unwrap();
+        for i in 0..*rule_count {
+            fs::write(
+                rule_dir.join(format!("method-{}.toml", i)),
+                format!(
+                    r#"
+[rule]
+id = "no-method-{i}"
+description = "Find method_{i} calls"
+severity = "error"
+
+[match]
+query = "(field_identifier) @violation (#eq? @violation \"method_{i}\")"
+language = "rust"
+"#
+                ),
+            )
+            .unwrap();
+        }
+
+        let mut registry = RuleRegistry::new();
+        registry.load_custom_ast_rules(&rule_dir, None).unwrap();
+        let engine = ratchets::engine::executor::ExecutionEngine::new(registry, None);
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(rule_count),
+            &files,
+            |b, files| {
+                b.iter(|| {
+                    let result = engine.execute(files.clone());
+                    black_box(result)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 /// Benchmark parser cache effectiveness
 ///
 /// This specifically tests that parser caching is working correctly
@@ -352,10 +593,16 @@ pattern = "TODO"
 
 criterion_group!(file_benches, bench_file_walking,);
 
-criterion_group!(rule_benches, bench_regex_execution,);
+criterion_group!(rule_benches, bench_regex_execution, bench_regex_prefilter,);
 
 #[cfg(feature = "lang-rust")]
-criterion_group!(ast_benches, bench_ast_execution, bench_parser_cache,);
+criterion_group!(
+    ast_benches,
+    bench_ast_execution,
+    bench_parser_cache,
+    bench_ast_shared_tree,
+    bench_ast_engine_many_rules,
+);
 
 criterion_group!(
     workflow_benches,
@@ -0,0 +1,338 @@
+#![forbid(unsafe_code)]
+
+//! Adapters that import third-party linter reports as budgeted violations
+//!
+//! Each adapter parses one tool's report format into [`ReportedViolation`]s.
+//! Every upstream code becomes a rule ID prefixed with the tool's name, e.g.
+//! ruff's `E501` becomes `ruff-E501` and clippy's `clippy::unwrap_used`
+//! becomes `clippy-unwrap_used`, so legacy lint debt can be budgeted per
+//! region in ratchet-counts.toml like any other rule.
+
+mod clippy;
+mod eslint;
+mod mypy;
+mod ruff;
+
+use crate::config::counts::CountsManager;
+use crate::engine::file_walker::FileEntry;
+use crate::rules::Violation;
+use crate::types::{RegionPath, RuleId};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Error type for importing linter reports
+#[derive(Debug, thiserror::Error)]
+pub enum AdapterError {
+    /// The `--import` argument is not `TOOL=FILE` with a known tool
+    #[error(
+        "Invalid report import '{0}': expected TOOL=FILE with TOOL one of clippy, ruff, eslint, mypy"
+    )]
+    InvalidImport(String),
+
+    /// The report file could not be read
+    #[error("Failed to read {format} report {path}: {source}")]
+    Io {
+        format: ReportFormat,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// The report is not in the tool's format
+    #[error("Invalid {format} report {path}: {message}")]
+    Parse {
+        format: ReportFormat,
+        path: PathBuf,
+        message: String,
+    },
+}
+
+/// A violation read from a linter report, before it becomes a [`Violation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportedViolation {
+    /// The tool's code for the check, e.g. `E501`
+    pub code: String,
+    /// File the violation is in, as the tool reported it
+    pub file: PathBuf,
+    /// 1-based line of the violation's start
+    pub line: u32,
+    /// 1-based column of the violation's start
+    pub column: u32,
+    /// 1-based line of the violation's end
+    pub end_line: u32,
+    /// 1-based column of the violation's end
+    pub end_column: u32,
+    /// The tool's message
+    pub message: String,
+}
+
+/// Report formats that can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// `cargo clippy --message-format=json`
+    Clippy,
+    /// `ruff check --output-format=json`
+    Ruff,
+    /// `eslint --format json`
+    Eslint,
+    /// mypy's default line output
+    Mypy,
+}
+
+impl ReportFormat {
+    /// The tool's name, used as the prefix of its rule IDs
+    pub fn name(self) -> &'static str {
+        match self {
+            ReportFormat::Clippy => "clippy",
+            ReportFormat::Ruff => "ruff",
+            ReportFormat::Eslint => "eslint",
+            ReportFormat::Mypy => "mypy",
+        }
+    }
+
+    /// Parse a report in this format
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the report is not in this format.
+    pub fn parse(self, content: &str) -> Result<Vec<ReportedViolation>, String> {
+        match self {
+            ReportFormat::Clippy => clippy::parse(content),
+            ReportFormat::Ruff => ruff::parse(content),
+            ReportFormat::Eslint => eslint::parse(content),
+            ReportFormat::Mypy => mypy::parse(content),
+        }
+    }
+
+    /// The rule ID for one of the tool's codes
+    ///
+    /// The code is prefixed with the tool's name; characters rule IDs cannot
+    /// contain, such as the `::` of clippy lints or the `@` and `/` of ESLint
+    /// plugin rules, become single hyphens.
+    pub fn rule_id(self, code: &str) -> Option<RuleId> {
+        let code = code.strip_prefix("clippy::").unwrap_or(code);
+        let mut id = self.name().to_string();
+        for part in code
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .filter(|part| !part.is_empty())
+        {
+            id.push('-');
+            id.push_str(part);
+        }
+        RuleId::new(id)
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = AdapterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clippy" => Ok(ReportFormat::Clippy),
+            "ruff" => Ok(ReportFormat::Ruff),
+            "eslint" => Ok(ReportFormat::Eslint),
+            "mypy" => Ok(ReportFormat::Mypy),
+            _ => Err(AdapterError::InvalidImport(s.to_string())),
+        }
+    }
+}
+
+/// A report to import, given as `TOOL=FILE` (e.g. `ruff=report.json`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportImport {
+    /// Format of the report
+    pub format: ReportFormat,
+    /// Path of the report file
+    pub path: PathBuf,
+}
+
+impl FromStr for ReportImport {
+    type Err = AdapterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (format, path) = s
+            .split_once('=')
+            .filter(|(_, path)| !path.is_empty())
+            .ok_or_else(|| AdapterError::InvalidImport(s.to_string()))?;
+        Ok(ReportImport {
+            format: format
+                .parse()
+                .map_err(|_| AdapterError::InvalidImport(s.to_string()))?,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+impl ReportImport {
+    /// Read the report and convert its violations in the checked files
+    ///
+    /// Violations in files that are not among `files` (excluded by
+    /// ratchets.toml, or outside the checked paths) are dropped, so imported
+    /// violations are scoped like native ones. The rest are assigned their
+    /// configured regions from `counts`, and their snippet is the trimmed
+    /// text of the line they start on (empty if the file cannot be read).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AdapterError` if the report cannot be read or parsed.
+    pub fn violations(
+        &self,
+        files: &[FileEntry],
+        counts: &CountsManager,
+    ) -> Result<Vec<Violation>, AdapterError> {
+        let content = std::fs::read_to_string(&self.path).map_err(|source| AdapterError::Io {
+            format: self.format,
+            path: self.path.clone(),
+            source,
+        })?;
+        let reported = self
+            .format
+            .parse(&content)
+            .map_err(|message| AdapterError::Parse {
+                format: self.format,
+                path: self.path.clone(),
+                message,
+            })?;
+
+        let current_dir = std::env::current_dir().ok();
+        let by_path: HashMap<PathBuf, &FileEntry> = files
+            .iter()
+            .map(|file| (relative_path(&file.path, None), file))
+            .collect();
+        // Contents of the files with violations, read once each
+        let mut sources: HashMap<&Path, Option<String>> = HashMap::new();
+        let mut violations = Vec::new();
+        for violation in reported {
+            let reported_file = relative_path(&violation.file, current_dir.as_deref());
+            let Some(file) = by_path.get(&reported_file) else {
+                continue;
+            };
+            let Some(rule_id) = self.format.rule_id(&violation.code) else {
+                continue;
+            };
+
+            let snippet = sources
+                .entry(file.path.as_path())
+                .or_insert_with(|| std::fs::read_to_string(&file.path).ok())
+                .as_deref()
+                .and_then(|content| {
+                    content
+                        .lines()
+                        .nth(violation.line.saturating_sub(1) as usize)
+                })
+                .map_or_else(String::new, |line| line.trim().to_string());
+            let region: RegionPath = counts.find_configured_region(&rule_id, &file.path);
+            violations.push(Violation {
+                rule_id,
+                file: file.path.clone(),
+                line: violation.line,
+                column: violation.column,
+                end_line: violation.end_line,
+                end_column: violation.end_column,
+                snippet,
+                message: violation.message,
+                region,
+            });
+        }
+        Ok(violations)
+    }
+}
+
+/// Spell a path the way the file walker's paths compare: relative to the
+/// current directory, without a leading `./`
+fn relative_path(path: &Path, current_dir: Option<&Path>) -> PathBuf {
+    let path = match current_dir {
+        Some(dir) if path.is_absolute() => path.strip_prefix(dir).unwrap_or(path),
+        _ => path,
+    };
+    path.strip_prefix(".").unwrap_or(path).to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_ids() {
+        let id = |format: ReportFormat, code: &str| format.rule_id(code).map(|id| id.to_string());
+        assert_eq!(id(ReportFormat::Ruff, "E501").as_deref(), Some("ruff-E501"));
+        assert_eq!(
+            id(ReportFormat::Clippy, "clippy::unwrap_used").as_deref(),
+            Some("clippy-unwrap_used")
+        );
+        assert_eq!(
+            id(ReportFormat::Eslint, "@typescript-eslint/no-explicit-any").as_deref(),
+            Some("eslint-typescript-eslint-no-explicit-any")
+        );
+        assert_eq!(
+            id(ReportFormat::Mypy, "arg-type").as_deref(),
+            Some("mypy-arg-type")
+        );
+    }
+
+    #[test]
+    fn test_report_import_parsing() -> Result<(), Box<dyn std::error::Error>> {
+        let import: ReportImport = "ruff=reports/ruff.json".parse()?;
+        assert_eq!(import.format, ReportFormat::Ruff);
+        assert_eq!(import.path, PathBuf::from("reports/ruff.json"));
+
+        assert!("ruff".parse::<ReportImport>().is_err());
+        assert!("ruff=".parse::<ReportImport>().is_err());
+        assert!("pylint=report.txt".parse::<ReportImport>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_violations_are_matched_to_checked_files() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::engine::file_walker::LanguageDetector;
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let checked = temp_dir.path().join("app.py");
+        std::fs::write(&checked, "import os\n    x: int = 'a'  \n")?;
+        let report = temp_dir.path().join("mypy.txt");
+        std::fs::write(
+            &report,
+            format!(
+                "{0}:2:5: error: Incompatible types  [assignment]\n{1}:1: error: Bad  [misc]\n",
+                checked.display(),
+                temp_dir.path().join("skipped.py").display()
+            ),
+        )?;
+
+        let import = ReportImport {
+            format: ReportFormat::Mypy,
+            path: report,
+        };
+        let files = [FileEntry::new(checked.clone(), &LanguageDetector::new())];
+        let violations = import.violations(&files, &CountsManager::new())?;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].file, checked);
+        assert_eq!(violations[0].rule_id.as_str(), "mypy-assignment");
+        assert_eq!(violations[0].snippet, "x: int = 'a'");
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let cwd = Path::new("/work/project");
+        assert_eq!(
+            relative_path(Path::new("/work/project/src/a.py"), Some(cwd)),
+            PathBuf::from("src/a.py")
+        );
+        assert_eq!(
+            relative_path(Path::new("./src/a.py"), Some(cwd)),
+            PathBuf::from("src/a.py")
+        );
+        assert_eq!(
+            relative_path(Path::new("/elsewhere/a.py"), Some(cwd)),
+            PathBuf::from("/elsewhere/a.py")
+        );
+    }
+}
@@ -0,0 +1,81 @@
+#![forbid(unsafe_code)]
+
+//! Adapter for `cargo clippy --message-format=json` output
+//!
+//! The output has one JSON object per line. Only `compiler-message` records
+//! with a lint code and warning or error level are violations, reported at
+//! their primary span.
+
+use super::ReportedViolation;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    reason: String,
+    #[serde(default)]
+    message: Option<Diagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Diagnostic {
+    message: String,
+    level: String,
+    #[serde(default)]
+    code: Option<Code>,
+    #[serde(default)]
+    spans: Vec<Span>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Code {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Span {
+    file_name: PathBuf,
+    line_start: u32,
+    line_end: u32,
+    column_start: u32,
+    column_end: u32,
+    is_primary: bool,
+}
+
+/// Parse clippy's JSON messages
+pub(super) fn parse(content: &str) -> Result<Vec<ReportedViolation>, String> {
+    let mut violations = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record =
+            serde_json::from_str(line).map_err(|e| format!("line {}: {}", index + 1, e))?;
+        if record.reason != "compiler-message" {
+            continue;
+        }
+        let Some(diagnostic) = record.message else {
+            continue;
+        };
+        if diagnostic.level != "warning" && diagnostic.level != "error" {
+            continue;
+        }
+        let Some(code) = diagnostic.code else {
+            continue;
+        };
+        let Some(span) = diagnostic.spans.into_iter().find(|span| span.is_primary) else {
+            continue;
+        };
+
+        violations.push(ReportedViolation {
+            code: code.code,
+            file: span.file_name,
+            line: span.line_start,
+            column: span.column_start,
+            end_line: span.line_end,
+            end_column: span.column_end,
+            message: diagnostic.message,
+        });
+    }
+    Ok(violations)
+}
@@ -0,0 +1,56 @@
+#![forbid(unsafe_code)]
+
+//! Adapter for `eslint --format json` reports
+//!
+//! The report is a JSON array with one object per file, each listing its
+//! messages. Parse errors, which have no rule, are imported as `parse-error`.
+
+use super::ReportedViolation;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileResult {
+    file_path: PathBuf,
+    #[serde(default)]
+    messages: Vec<Message>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Message {
+    #[serde(default)]
+    rule_id: Option<String>,
+    message: String,
+    #[serde(default)]
+    line: Option<u32>,
+    #[serde(default)]
+    column: Option<u32>,
+    #[serde(default)]
+    end_line: Option<u32>,
+    #[serde(default)]
+    end_column: Option<u32>,
+}
+
+/// Parse an ESLint JSON report
+pub(super) fn parse(content: &str) -> Result<Vec<ReportedViolation>, String> {
+    let results: Vec<FileResult> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut violations = Vec::new();
+    for result in results {
+        for message in result.messages {
+            let line = message.line.unwrap_or(1);
+            let column = message.column.unwrap_or(1);
+            violations.push(ReportedViolation {
+                code: message.rule_id.unwrap_or_else(|| "parse-error".to_string()),
+                file: result.file_path.clone(),
+                line,
+                column,
+                end_line: message.end_line.unwrap_or(line),
+                end_column: message.end_column.unwrap_or(column),
+                message: message.message,
+            });
+        }
+    }
+    Ok(violations)
+}
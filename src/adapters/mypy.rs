@@ -0,0 +1,41 @@
+#![forbid(unsafe_code)]
+
+//! Adapter for mypy's line output
+//!
+//! Each error is one line, `file:line[:column]: error: message  [code]`;
+//! columns appear with `--show-column-numbers`. Notes, summaries and other
+//! lines are skipped. Errors without a code are imported as `misc`.
+
+use super::ReportedViolation;
+use regex::Regex;
+use std::path::PathBuf;
+
+/// An error line of mypy's output
+const ERROR_LINE: &str = r"^(?P<file>[^:]+):(?P<line>\d+):(?:(?P<column>\d+):)?\s*error:\s*(?P<message>.*?)(?:\s+\[(?P<code>[a-z0-9-]+)\])?\s*$";
+
+/// Parse mypy's output
+pub(super) fn parse(content: &str) -> Result<Vec<ReportedViolation>, String> {
+    let error_line = Regex::new(ERROR_LINE).map_err(|e| e.to_string())?;
+    let mut violations = Vec::new();
+    for line in content.lines() {
+        let Some(caps) = error_line.captures(line) else {
+            continue;
+        };
+        let number = |name: &str| caps.name(name).and_then(|m| m.as_str().parse::<u32>().ok());
+        let Some(line) = number("line") else {
+            continue;
+        };
+        let column = number("column").unwrap_or(1);
+
+        violations.push(ReportedViolation {
+            code: caps.name("code").map_or("misc", |m| m.as_str()).to_string(),
+            file: PathBuf::from(&caps["file"]),
+            line,
+            column,
+            end_line: line,
+            end_column: column,
+            message: caps["message"].to_string(),
+        });
+    }
+    Ok(violations)
+}
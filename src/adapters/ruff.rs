@@ -0,0 +1,47 @@
+#![forbid(unsafe_code)]
+
+//! Adapter for `ruff check --output-format=json` reports
+//!
+//! The report is a JSON array with one object per violation. Syntax errors,
+//! which have no code, are imported as `syntax-error`.
+
+use super::ReportedViolation;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    #[serde(default)]
+    code: Option<String>,
+    message: String,
+    filename: PathBuf,
+    location: Location,
+    #[serde(default)]
+    end_location: Option<Location>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct Location {
+    row: u32,
+    column: u32,
+}
+
+/// Parse a ruff JSON report
+pub(super) fn parse(content: &str) -> Result<Vec<ReportedViolation>, String> {
+    let records: Vec<Record> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            let end = record.end_location.unwrap_or(record.location);
+            ReportedViolation {
+                code: record.code.unwrap_or_else(|| "syntax-error".to_string()),
+                file: record.filename,
+                line: record.location.row,
+                column: record.location.column,
+                end_line: end.row,
+                end_column: end.column,
+                message: record.message,
+            }
+        })
+        .collect())
+}
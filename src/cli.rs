@@ -2,14 +2,20 @@
 
 pub mod args;
 pub mod bump;
+pub mod cache;
 pub mod check;
 pub mod common;
+pub mod explain;
+pub mod fix;
 pub mod git_diff;
 pub mod init;
 pub mod list;
+pub mod lsp;
 pub mod merge_driver;
+pub mod test_rules;
 pub mod tighten;
 pub mod upgrade_notice;
+pub mod watch;
 
 // Re-export types for convenient access
-pub use args::{Cli, ColorChoice, Command, OutputFormat};
+pub use args::{CacheCommand, Cli, ColorChoice, Command, OutputFormat};
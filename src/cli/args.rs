@@ -1,5 +1,6 @@
 //! CLI argument parsing using clap
 
+use crate::adapters::ReportImport;
 use clap::{Parser, Subcommand, ValueEnum};
 
 /// Output format for ratchet commands
@@ -61,6 +62,23 @@ pub enum Command {
         /// rules still apply. Files deleted relative to the ref are skipped.
         #[arg(long, value_name = "REF")]
         since: Option<String>,
+
+        /// Ignore and do not update the result cache in .ratchets-cache/
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Only count violations per rule and region, without collecting
+        /// their details. Keeps memory bounded on trees with many matches.
+        #[arg(long, conflicts_with = "verbose")]
+        count_only: bool,
+
+        /// Import a linter report as budgeted violations, e.g. `ruff=report.json`.
+        ///
+        /// TOOL is one of clippy, ruff, eslint or mypy. Each code the tool
+        /// reports becomes a rule `<tool>-<code>` budgeted per region in
+        /// ratchet-counts.toml. Can be given more than once.
+        #[arg(long = "import", value_name = "TOOL=FILE")]
+        imports: Vec<ReportImport>,
     },
 
     /// Initialize ratchet in this repository
@@ -87,6 +105,18 @@ pub enum Command {
         /// Bump all rules to their current violation counts
         #[arg(long, conflicts_with = "region")]
         all: bool,
+
+        /// Ignore and do not update the result cache in .ratchets-cache/
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Only count violations, without collecting their details
+        #[arg(long)]
+        count_only: bool,
+
+        /// Bump the budget of violations suppressed by inline comments
+        #[arg(long, conflicts_with = "all")]
+        suppressed: bool,
     },
 
     /// Reduce budgets to match current violations
@@ -97,6 +127,43 @@ pub enum Command {
         /// Specific region to tighten
         #[arg(long)]
         region: Option<String>,
+
+        /// Ignore and do not update the result cache in .ratchets-cache/
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Only count violations, without collecting their details
+        #[arg(long)]
+        count_only: bool,
+    },
+
+    /// Apply the fixes of rules with a [fix] section
+    Fix {
+        /// Rule whose violations to fix (fixes all fixable rules if omitted)
+        rule_id: Option<String>,
+
+        /// Paths to fix (defaults to current directory)
+        paths: Vec<String>,
+
+        /// Print the changes as a unified diff instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Check rules against the examples in their [[test]] tables
+    TestRules {
+        /// Rule to test (tests every builtin and custom rule if omitted)
+        rule_id: Option<String>,
+    },
+
+    /// Explain a rule: rationale, examples, definition, sets and per-region standing
+    Explain {
+        /// Rule to explain
+        rule_id: String,
+
+        /// Output format
+        #[arg(short, long, default_value = "human")]
+        format: OutputFormat,
     },
 
     /// List all enabled rules
@@ -106,6 +173,29 @@ pub enum Command {
         format: OutputFormat,
     },
 
+    /// Re-check files as they change and show live budget status
+    Watch {
+        /// Paths to watch (defaults to current directory)
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+
+        /// Polling interval in milliseconds
+        #[arg(long, default_value = "500")]
+        interval: u64,
+    },
+
+    /// Run a Language Server Protocol server over stdin/stdout
+    ///
+    /// Publishes violations in open documents as diagnostics and shows the
+    /// rule's budget standing on hover.
+    Lsp,
+
+    /// Manage the result cache in .ratchets-cache/
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+
     /// Git merge driver for ratchet-counts.toml
     MergeDriver {
         /// Base version (ancestor)
@@ -119,6 +209,13 @@ pub enum Command {
     },
 }
 
+/// Subcommands of `ratchets cache`
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Remove all cached results
+    Clean,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,11 +236,17 @@ mod tests {
                 format,
                 verbose,
                 since,
+                no_cache,
+                count_only,
+                imports,
             } => {
                 assert_eq!(paths, vec!["."]);
                 assert_eq!(format, OutputFormat::Human);
                 assert!(!verbose);
                 assert_eq!(since, None);
+                assert!(!no_cache);
+                assert!(!count_only);
+                assert!(imports.is_empty());
             }
             _ => panic!("Expected Check command"),
         }
@@ -245,6 +348,7 @@ mod tests {
                 region,
                 count,
                 all,
+                ..
             } => {
                 assert_eq!(rule_id, Some("no-unwrap".to_string()));
                 assert_eq!(region, ".");
@@ -264,6 +368,7 @@ mod tests {
                 region,
                 count,
                 all,
+                ..
             } => {
                 assert_eq!(rule_id, Some("no-unwrap".to_string()));
                 assert_eq!(region, "src/legacy");
@@ -283,6 +388,7 @@ mod tests {
                 region,
                 count,
                 all,
+                ..
             } => {
                 assert_eq!(rule_id, Some("no-unwrap".to_string()));
                 assert_eq!(region, ".");
@@ -310,6 +416,7 @@ mod tests {
                 region,
                 count,
                 all,
+                ..
             } => {
                 assert_eq!(rule_id, Some("no-unwrap".to_string()));
                 assert_eq!(region, "src/legacy");
@@ -324,7 +431,9 @@ mod tests {
     fn test_tighten_all() {
         let cli = Cli::parse_from(["ratchets", "tighten"]);
         match cli.command {
-            Command::Tighten { rule_id, region } => {
+            Command::Tighten {
+                rule_id, region, ..
+            } => {
                 assert_eq!(rule_id, None);
                 assert_eq!(region, None);
             }
@@ -336,7 +445,9 @@ mod tests {
     fn test_tighten_specific_rule() {
         let cli = Cli::parse_from(["ratchets", "tighten", "no-unwrap"]);
         match cli.command {
-            Command::Tighten { rule_id, region } => {
+            Command::Tighten {
+                rule_id, region, ..
+            } => {
                 assert_eq!(rule_id, Some("no-unwrap".to_string()));
                 assert_eq!(region, None);
             }
@@ -348,7 +459,9 @@ mod tests {
     fn test_tighten_with_region() {
         let cli = Cli::parse_from(["ratchets", "tighten", "--region", "src/"]);
         match cli.command {
-            Command::Tighten { rule_id, region } => {
+            Command::Tighten {
+                rule_id, region, ..
+            } => {
                 assert_eq!(rule_id, None);
                 assert_eq!(region, Some("src/".to_string()));
             }
@@ -360,7 +473,9 @@ mod tests {
     fn test_tighten_rule_and_region() {
         let cli = Cli::parse_from(["ratchets", "tighten", "no-unwrap", "--region", "src/"]);
         match cli.command {
-            Command::Tighten { rule_id, region } => {
+            Command::Tighten {
+                rule_id, region, ..
+            } => {
                 assert_eq!(rule_id, Some("no-unwrap".to_string()));
                 assert_eq!(region, Some("src/".to_string()));
             }
@@ -494,6 +609,7 @@ mod tests {
                 region,
                 count,
                 all,
+                ..
             } => {
                 assert_eq!(rule_id, None);
                 assert_eq!(region, ".");
@@ -514,6 +630,7 @@ mod tests {
                 region,
                 count,
                 all,
+                ..
             } => {
                 assert_eq!(rule_id, Some("no-unwrap".to_string()));
                 assert_eq!(region, ".");
@@ -541,4 +658,159 @@ mod tests {
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("--all") || err_msg.contains("--count"));
     }
+
+    #[test]
+    fn test_no_cache_flag() {
+        let cli = Cli::parse_from(["ratchets", "check", "--no-cache"]);
+        assert!(matches!(cli.command, Command::Check { no_cache: true, .. }));
+
+        let cli = Cli::parse_from(["ratchets", "tighten", "--no-cache"]);
+        assert!(matches!(
+            cli.command,
+            Command::Tighten { no_cache: true, .. }
+        ));
+
+        let cli = Cli::parse_from(["ratchets", "bump", "--all", "--no-cache"]);
+        assert!(matches!(cli.command, Command::Bump { no_cache: true, .. }));
+    }
+
+    #[test]
+    fn test_count_only_flag() {
+        let cli = Cli::parse_from(["ratchets", "check", "--count-only"]);
+        assert!(matches!(
+            cli.command,
+            Command::Check {
+                count_only: true,
+                ..
+            }
+        ));
+
+        let cli = Cli::parse_from(["ratchets", "tighten", "--count-only"]);
+        assert!(matches!(
+            cli.command,
+            Command::Tighten {
+                count_only: true,
+                ..
+            }
+        ));
+
+        let cli = Cli::parse_from(["ratchets", "bump", "--all", "--count-only"]);
+        assert!(matches!(
+            cli.command,
+            Command::Bump {
+                count_only: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_check_import_flag() {
+        let cli = Cli::parse_from([
+            "ratchets",
+            "check",
+            "--import",
+            "ruff=ruff.json",
+            "--import",
+            "mypy=mypy.txt",
+        ]);
+        match cli.command {
+            Command::Check { imports, .. } => {
+                let tools: Vec<String> = imports.iter().map(|i| i.format.to_string()).collect();
+                assert_eq!(tools, vec!["ruff", "mypy"]);
+            }
+            _ => unreachable!("expected Check command"),
+        }
+
+        let result = Cli::try_parse_from(["ratchets", "check", "--import", "pylint=out.txt"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_count_only_conflicts_with_verbose() {
+        let result = Cli::try_parse_from(["ratchets", "check", "--count-only", "--verbose"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_watch_default_args() {
+        let cli = Cli::parse_from(["ratchets", "watch"]);
+        assert!(matches!(
+            cli.command,
+            Command::Watch { ref paths, interval: 500 } if paths == &["."]
+        ));
+    }
+
+    #[test]
+    fn test_watch_with_paths_and_interval() {
+        let cli = Cli::parse_from(["ratchets", "watch", "src/", "--interval", "100"]);
+        assert!(matches!(
+            cli.command,
+            Command::Watch { ref paths, interval: 100 } if paths == &["src/"]
+        ));
+    }
+
+    #[test]
+    fn test_lsp() {
+        let cli = Cli::parse_from(["ratchets", "lsp"]);
+        assert!(matches!(cli.command, Command::Lsp));
+    }
+
+    #[test]
+    fn test_fix_default_args() {
+        let cli = Cli::parse_from(["ratchets", "fix"]);
+        assert!(matches!(
+            cli.command,
+            Command::Fix { rule_id: None, ref paths, dry_run: false } if paths.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_fix_rule_paths_and_dry_run() {
+        let cli = Cli::parse_from(["ratchets", "fix", "no-os-path-join", "src", "--dry-run"]);
+        assert!(matches!(
+            cli.command,
+            Command::Fix { rule_id: Some(ref id), ref paths, dry_run: true }
+                if id == "no-os-path-join" && paths == &["src"]
+        ));
+    }
+
+    #[test]
+    fn test_test_rules_optional_rule() {
+        let cli = Cli::parse_from(["ratchets", "test-rules"]);
+        assert!(matches!(cli.command, Command::TestRules { rule_id: None }));
+
+        let cli = Cli::parse_from(["ratchets", "test-rules", "no-unwrap"]);
+        assert!(matches!(
+            cli.command,
+            Command::TestRules { rule_id: Some(ref id) } if id == "no-unwrap"
+        ));
+    }
+
+    #[test]
+    fn test_explain_requires_rule() {
+        let cli = Cli::parse_from(["ratchets", "explain", "no-unwrap", "-f", "jsonl"]);
+        assert!(matches!(
+            cli.command,
+            Command::Explain { ref rule_id, format: OutputFormat::Jsonl } if rule_id == "no-unwrap"
+        ));
+        assert!(Cli::try_parse_from(["ratchets", "explain"]).is_err());
+    }
+
+    #[test]
+    fn test_cache_clean() {
+        let cli = Cli::parse_from(["ratchets", "cache", "clean"]);
+        assert!(matches!(
+            cli.command,
+            Command::Cache {
+                command: CacheCommand::Clean
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cache_requires_subcommand() {
+        let result = Cli::try_parse_from(["ratchets", "cache"]);
+        assert!(result.is_err());
+    }
 }
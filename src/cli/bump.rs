@@ -5,6 +5,8 @@
 //! - Auto-detects current violations if count is not provided
 //! - Updates ratchet-counts.toml with the new budget
 //! - Validates that bumps don't go below current violation counts
+//! - Bumps the budget of violations suppressed by inline comments instead
+//!   with `--suppressed`
 
 use crate::cli::common::{EXIT_ERROR, EXIT_SUCCESS};
 use crate::config::counts::CountsManager;
@@ -50,14 +52,27 @@ enum BumpError {
 /// * `region` - The region path to bump (defaults to ".")
 /// * `count` - Optional new count (auto-detects if None)
 /// * `all` - Whether to bump all rules
+/// * `no_cache` - If true, neither read nor update the result cache
+/// * `count_only` - If true, rules count matches without building violation details
+/// * `suppressed` - If true, bump the budget of suppressed violations instead
 ///
 /// # Returns
 ///
 /// Exit code:
 /// - 0: Success
 /// - 2: Error (config error, invalid rule ID, count below current violations)
-pub fn run_bump(rule_id: Option<&str>, region: &str, count: Option<u64>, all: bool) -> i32 {
-    match run_bump_inner(rule_id, region, count, all) {
+pub fn run_bump(
+    rule_id: Option<&str>,
+    region: &str,
+    count: Option<u64>,
+    all: bool,
+    no_cache: bool,
+    count_only: bool,
+    suppressed: bool,
+) -> i32 {
+    match run_bump_inner(
+        rule_id, region, count, all, no_cache, count_only, suppressed,
+    ) {
         Ok(()) => EXIT_SUCCESS,
         Err(e) => {
             // Print the embedded upgrade notice for schema version mismatches
@@ -81,6 +96,9 @@ fn run_bump_inner(
     region: &str,
     count: Option<u64>,
     all: bool,
+    no_cache: bool,
+    count_only: bool,
+    suppressed: bool,
 ) -> Result<(), BumpError> {
     // Load configuration
     let config = super::common::load_config().map_err(BumpError::Config)?;
@@ -88,7 +106,7 @@ fn run_bump_inner(
 
     // Handle --all flag
     if all {
-        return run_bump_all(&config, &registry);
+        return run_bump_all(&config, &registry, no_cache, count_only);
     }
 
     // When not using --all, rule_id is required
@@ -113,7 +131,12 @@ fn run_bump_inner(
     }
 
     // 3. Get current violation count for this rule/region
-    let current_count = get_current_violation_count(&rule_id, region, &config)?;
+    let current = get_current_counts(&rule_id, region, &config, no_cache, count_only)?;
+    let (current_count, what) = if suppressed {
+        (current.suppressed, "suppressed violations")
+    } else {
+        (current.violations, "violations")
+    };
 
     // 4. Determine the new count
     let new_count = match count {
@@ -121,11 +144,12 @@ fn run_bump_inner(
             // User specified a count - validate it's not below current violations
             if n < current_count {
                 return Err(BumpError::Other(format!(
-                    "Cannot bump '{}' in region '{}' to {} (below current {} violations). Use 'ratchets tighten' to reduce budgets.",
+                    "Cannot bump '{}' in region '{}' to {} (below current {} {}). Use 'ratchets tighten' to reduce budgets.",
                     rule_id.as_str(),
                     region,
                     n,
-                    current_count
+                    current_count,
+                    what
                 )));
             }
             n
@@ -146,7 +170,12 @@ fn run_bump_inner(
 
     // 6. Validate region is configured (unless it's the root region ".")
     let region_path = RegionPath::new(region);
-    if region != "." && !counts.is_configured_region(&rule_id, &region_path) {
+    let configured = if suppressed {
+        counts.is_configured_suppressed_region(&rule_id, &region_path)
+    } else {
+        counts.is_configured_region(&rule_id, &region_path)
+    };
+    if region != "." && !configured {
         return Err(BumpError::Other(format!(
             "Region '{}' is not configured for rule '{}'. Add it to ratchet-counts.toml first.",
             region,
@@ -156,29 +185,48 @@ fn run_bump_inner(
 
     // Make counts mutable for updates
     let mut counts = counts;
-    let old_count = counts.get_budget_by_region(&rule_id, &region_path);
+    let budget = if suppressed {
+        "suppressed budget"
+    } else {
+        "budget"
+    };
+    let old_count = if suppressed {
+        counts.get_suppressed_budget_by_region(&rule_id, &region_path)
+    } else {
+        Some(counts.get_budget_by_region(&rule_id, &region_path))
+    };
 
     // 7. Update the count
-    counts.set_count(&rule_id, &region_path, new_count);
+    if suppressed {
+        counts.set_suppressed_count(&rule_id, &region_path, new_count);
+    } else {
+        counts.set_count(&rule_id, &region_path, new_count);
+    }
 
     // 8. Write back to file
     let toml_content = counts.to_toml_string();
     std::fs::write(counts_path, toml_content)?;
 
     // 9. Print success message
-    if old_count == new_count {
+    if old_count == Some(new_count) {
         println!(
-            "Budget for '{}' in region '{}' is already {}",
+            "{} for '{}' in region '{}' is already {}",
+            if suppressed {
+                "Suppressed budget"
+            } else {
+                "Budget"
+            },
             rule_id.as_str(),
             region,
             new_count
         );
     } else {
         println!(
-            "Bumped '{}' budget for region '{}' from {} to {}",
+            "Bumped '{}' {} for region '{}' from {} to {}",
             rule_id.as_str(),
+            budget,
             region,
-            old_count,
+            old_count.map_or_else(|| "unlimited".to_string(), |count| count.to_string()),
             new_count
         );
     }
@@ -187,7 +235,12 @@ fn run_bump_inner(
 }
 
 /// Bump all enabled rules to their current violation counts
-fn run_bump_all(config: &Config, registry: &RuleRegistry) -> Result<(), BumpError> {
+fn run_bump_all(
+    config: &Config,
+    registry: &RuleRegistry,
+    no_cache: bool,
+    count_only: bool,
+) -> Result<(), BumpError> {
     // Load existing counts
     let counts_path = Path::new("ratchet-counts.toml");
     let mut counts = if counts_path.exists() {
@@ -216,26 +269,38 @@ fn run_bump_all(config: &Config, registry: &RuleRegistry) -> Result<(), BumpErro
 
     for rule_id in rule_ids {
         // Get current violation count for root region
-        let current_count = get_current_violation_count(&rule_id, ".", config)?;
+        let current = get_current_counts(&rule_id, ".", config, no_cache, count_only)?;
 
         // Get old budget
         let region_path = RegionPath::new(".");
         let old_count = counts.get_budget_by_region(&rule_id, &region_path);
 
         // Update the count
-        counts.set_count(&rule_id, &region_path, current_count);
+        counts.set_count(&rule_id, &region_path, current.violations);
 
-        if old_count == current_count {
+        if old_count == current.violations {
             unchanged += 1;
         } else {
             println!(
                 "  {} budget: {} -> {}",
                 rule_id.as_str(),
                 old_count,
-                current_count
+                current.violations
             );
             updated += 1;
         }
+
+        // Suppressed budgets are only written for rules with suppressions
+        let old_suppressed = counts.get_suppressed_budget_by_region(&rule_id, &region_path);
+        if old_suppressed.unwrap_or(0) != current.suppressed {
+            counts.set_suppressed_count(&rule_id, &region_path, current.suppressed);
+            println!(
+                "  {} suppressed budget: {} -> {}",
+                rule_id.as_str(),
+                old_suppressed.map_or_else(|| "unlimited".to_string(), |count| count.to_string()),
+                current.suppressed
+            );
+        }
     }
 
     // Write back to file
@@ -250,12 +315,22 @@ fn run_bump_all(config: &Config, registry: &RuleRegistry) -> Result<(), BumpErro
     Ok(())
 }
 
-/// Get current violation count by running check for a specific rule/region
-fn get_current_violation_count(
+/// Current violations of a rule in a region
+struct CurrentCounts {
+    /// Violations counted against the rule's budget
+    violations: u64,
+    /// Violations suppressed by inline comments
+    suppressed: u64,
+}
+
+/// Get current violation counts by running check for a specific rule/region
+fn get_current_counts(
     rule_id: &RuleId,
     region: &str,
     config: &Config,
-) -> Result<u64, BumpError> {
+    no_cache: bool,
+    count_only: bool,
+) -> Result<CurrentCounts, BumpError> {
     // Load existing counts (we'll use budget 0 for this rule to count all violations)
     let counts_path = Path::new("ratchet-counts.toml");
     let mut counts = if counts_path.exists() {
@@ -277,27 +352,38 @@ fn get_current_violation_count(
     let files = super::common::discover_files(&[region.to_string()], config)?;
 
     // Run execution engine with the single rule and CountsManager for region resolution
-    let engine = ExecutionEngine::new(
+    let mut engine = ExecutionEngine::new(
         single_rule_registry,
         Some(std::sync::Arc::new(counts.clone())),
     );
-    let execution_result = engine.execute(files);
+    super::common::set_walked_files(&mut engine, config)?;
+    let tally = super::common::execute_rules(
+        &engine,
+        &files,
+        no_cache,
+        super::common::counting_mode(count_only),
+    )?;
 
     // Aggregate violations
     let aggregator = ViolationAggregator::new(counts);
-    let aggregation_result = aggregator.aggregate(execution_result.violations);
+    let aggregation_result = aggregator.aggregate_tally(tally);
 
-    // Find the status for our target rule/region
-    let status = aggregation_result
+    // Find the status for our target rule/region; absence means no violations
+    let violations = aggregation_result
         .statuses
         .iter()
-        .find(|s| s.rule_id == *rule_id && s.region.as_str() == region);
+        .find(|s| s.rule_id == *rule_id && s.region.as_str() == region)
+        .map_or(0, |s| s.actual_count);
+    let suppressed = aggregation_result
+        .suppressed
+        .iter()
+        .find(|s| s.rule_id == *rule_id && s.region.as_str() == region)
+        .map_or(0, |s| s.count);
 
-    // Return the actual count
-    match status {
-        Some(s) => Ok(s.actual_count),
-        None => Ok(0), // No violations found
-    }
+    Ok(CurrentCounts {
+        violations,
+        suppressed,
+    })
 }
 
 #[cfg(test)]
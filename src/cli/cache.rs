@@ -0,0 +1,32 @@
+//! Cache command implementation
+//!
+//! This module implements the `ratchets cache clean` command, which removes
+//! the result cache that `check`, `tighten` and `bump` keep in .ratchets-cache/.
+
+use crate::cli::common::{EXIT_ERROR, EXIT_SUCCESS};
+use crate::engine::cache::{CACHE_DIR, ResultCache};
+use std::path::Path;
+
+/// Run the cache clean command
+///
+/// # Returns
+///
+/// Exit code:
+/// - 0: Success (including when there was no cache)
+/// - 2: Error (cache directory could not be removed)
+pub fn run_cache_clean() -> i32 {
+    match ResultCache::clean(Path::new(CACHE_DIR)) {
+        Ok(true) => {
+            println!("Removed {}", CACHE_DIR);
+            EXIT_SUCCESS
+        }
+        Ok(false) => {
+            println!("No cache to remove");
+            EXIT_SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: Failed to remove {}: {}", CACHE_DIR, e);
+            EXIT_ERROR
+        }
+    }
+}
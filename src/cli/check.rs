@@ -5,14 +5,16 @@
 //! - Loads violation budgets from ratchet-counts.toml
 //! - Discovers files to check
 //! - Executes all enabled rules in parallel
+//! - Imports violations from third-party linter reports
 //! - Aggregates violations and checks against budgets
 //! - Formats output (human or JSONL)
 //! - Returns appropriate exit code
 
+use crate::adapters::{AdapterError, ReportImport};
 use crate::cli::args::OutputFormat;
 use crate::cli::common::{EXIT_ERROR, EXIT_EXCEEDED, EXIT_PARSE_ERROR, EXIT_SUCCESS};
 use crate::cli::git_diff::GitDiffError;
-use crate::engine::aggregator::ViolationAggregator;
+use crate::engine::aggregator::{TallyMode, ViolationAggregator};
 use crate::engine::executor::ExecutionEngine;
 use crate::error::ConfigError;
 use crate::output::{HumanFormatter, JsonlFormatter};
@@ -34,6 +36,9 @@ pub(crate) enum CheckError {
     #[error("{0}")]
     GitDiff(#[from] GitDiffError),
 
+    #[error("{0}")]
+    Import(#[from] AdapterError),
+
     #[error("Parse error in {file}: {message}")]
     #[allow(dead_code)] // Reserved for future use when we detect parse errors
     Parse { file: PathBuf, message: String },
@@ -57,6 +62,9 @@ pub(crate) enum CheckError {
 /// * `format` - Output format (human or JSONL)
 /// * `verbose` - If true, show individual violation details. If false, show only summary.
 /// * `since` - If `Some(ref)`, only check files changed since the given git ref.
+/// * `no_cache` - If true, neither read nor update the result cache.
+/// * `count_only` - If true, only count violations per rule and region (no details).
+/// * `imports` - Linter reports whose violations are budgeted alongside the rules'.
 ///
 /// # Returns
 ///
@@ -70,8 +78,11 @@ pub fn run_check(
     format: OutputFormat,
     verbose: bool,
     since: Option<&str>,
+    no_cache: bool,
+    count_only: bool,
+    imports: &[ReportImport],
 ) -> i32 {
-    match run_check_inner(paths, format, verbose, since) {
+    match run_check_inner(paths, format, verbose, since, no_cache, count_only, imports) {
         Ok(passed) => {
             if passed {
                 EXIT_SUCCESS
@@ -110,6 +121,9 @@ fn run_check_inner(
     format: OutputFormat,
     verbose: bool,
     since: Option<&str>,
+    no_cache: bool,
+    count_only: bool,
+    imports: &[ReportImport],
 ) -> Result<bool, CheckError> {
     // 1. Load ratchets.toml config
     let config = super::common::load_config()?;
@@ -120,8 +134,8 @@ fn run_check_inner(
     // 3. Build rule registry (load builtin + custom rules, apply config filter)
     let registry = super::common::build_registry(&config)?;
 
-    // If no rules are enabled, warn and exit successfully
-    if registry.is_empty() {
+    // If no rules are enabled and no reports imported, warn and exit successfully
+    if registry.is_empty() && imports.is_empty() {
         eprintln!("Warning: No rules are enabled. Nothing to check.");
         return Ok(true);
     }
@@ -156,13 +170,29 @@ fn run_check_inner(
         );
     }
 
-    // 6. Run ExecutionEngine with CountsManager for region resolution
-    let engine = ExecutionEngine::new(registry, Some(std::sync::Arc::new(counts.clone())));
-    let execution_result = engine.execute(files);
+    // 6. Run ExecutionEngine with CountsManager for region resolution, keeping
+    // individual violations only when they will be printed
+    let mode = if count_only {
+        TallyMode::CountOnly
+    } else if verbose {
+        TallyMode::Detailed
+    } else {
+        TallyMode::Summary
+    };
+    let mut engine = ExecutionEngine::new(registry, Some(std::sync::Arc::new(counts.clone())));
+    super::common::set_walked_files(&mut engine, &config)?;
+    let mut tally = super::common::execute_rules(&engine, &files, no_cache, mode)?;
+
+    // 6a. Add the violations of imported linter reports in the checked files
+    for import in imports {
+        for violation in import.violations(&files, &counts)? {
+            tally.add(violation);
+        }
+    }
 
     // 7. Aggregate violations with ViolationAggregator
     let aggregator = ViolationAggregator::new(counts);
-    let aggregation_result = aggregator.aggregate(execution_result.violations);
+    let aggregation_result = aggregator.aggregate_tally(tally);
 
     // 8. Format and print output
     match format {
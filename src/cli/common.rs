@@ -7,9 +7,13 @@ use crate::cli::git_diff::{self, GitDiffError};
 use crate::config::counts::CountsManager;
 use crate::config::ratchet_toml::Config;
 use crate::config::sets::ResolveError;
-use crate::engine::file_walker::{FileEntry, FileWalker, FileWalkerError};
+use crate::engine::aggregator::{TallyMode, ViolationTally};
+use crate::engine::cache::{CACHE_DIR, ResultCache};
+use crate::engine::executor::ExecutionEngine;
+use crate::engine::file_walker::{FileEntry, FileWalker, FileWalkerError, LanguageDetector};
 use crate::error::{ConfigError, RuleError};
 use crate::rules::RuleRegistry;
+use crate::types::Language;
 use std::path::{Path, PathBuf};
 
 /// Exit codes from DESIGN.md
@@ -56,6 +60,48 @@ pub(crate) fn discover_files(
     discover_files_verbose(paths, config, false, &mut |_| {})
 }
 
+/// Let rules that read directories see every file of the tree
+///
+/// Runs over some files only, such as `check` with explicit paths or
+/// `--since`, or `bump` over one region, would otherwise look up sibling
+/// files among the checked files alone. The extra walk only happens when a
+/// rule reads directories.
+///
+/// # Errors
+///
+/// Returns `FileWalkerError` if there is an error walking the file system.
+pub(crate) fn set_walked_files(
+    engine: &mut ExecutionEngine,
+    config: &Config,
+) -> Result<(), FileWalkerError> {
+    if engine.reads_directories() {
+        let files = discover_files(&[".".to_string()], config)?;
+        engine.set_walked_files(files.into_iter().map(|file| file.path));
+    }
+    Ok(())
+}
+
+/// Build the language detector for `config`'s header language and `[languages.map]`
+///
+/// # Errors
+///
+/// Returns `FileWalkerError` if a `[languages.map]` pattern is invalid.
+pub(crate) fn language_detector(config: &Config) -> Result<LanguageDetector, FileWalkerError> {
+    LanguageDetector::with_header_language(config.ratchets.header_language.unwrap_or(Language::C))
+        .with_language_map(&config.languages.map)
+}
+
+/// Explain why no language was detected for `path`, naming the
+/// `[languages.map]` pattern that would check it
+fn no_language_reason(path: &Path) -> String {
+    let pattern = match (path.extension(), path.file_name()) {
+        (Some(extension), _) => format!("*.{}", extension.to_string_lossy()),
+        (None, Some(name)) => format!("**/{}", name.to_string_lossy()),
+        (None, None) => return "no matching language".to_string(),
+    };
+    format!("no matching language for {}, see [languages.map]", pattern)
+}
+
 /// Discover files to check using FileWalker with verbose output
 ///
 /// Walks the specified paths and collects all files that match the
@@ -94,7 +140,8 @@ where
             &config.ratchets.include,
             &config.ratchets.exclude,
             verbose,
-        )?;
+        )?
+        .with_language_detector(language_detector(config)?);
 
         // Collect files from this path
         if verbose {
@@ -106,9 +153,9 @@ where
                     }
                     WalkResult::Skipped { path, reason } => {
                         let reason_str = match reason {
-                            SkipReason::ExcludedByPattern => "excluded by pattern",
-                            SkipReason::NoMatchingLanguage => "no matching language",
-                            SkipReason::NotAFile => "not a file",
+                            SkipReason::ExcludedByPattern => "excluded by pattern".to_string(),
+                            SkipReason::NoMatchingLanguage => no_language_reason(&path),
+                            SkipReason::NotAFile => "not a file".to_string(),
                         };
                         callback(&format!("Skipping {} ({})", path.display(), reason_str));
                     }
@@ -194,6 +241,53 @@ pub(crate) fn build_registry(config: &Config) -> Result<RuleRegistry, RuleError>
     RuleRegistry::build_from_config(config)
 }
 
+/// Execute rules against files, reusing results cached in .ratchets-cache/
+///
+/// Violations are counted into a tally as files finish (see [`TallyMode`]),
+/// then batch rules such as external commands run over all files at once.
+/// Cache read and write failures are reported as warnings; the check itself
+/// still runs. With `no_cache`, the cache is neither read nor written.
+/// Outside detailed mode the cache records only violation counts.
+///
+/// # Errors
+///
+/// Returns `RuleError` if a batch rule fails.
+pub(crate) fn execute_rules(
+    engine: &ExecutionEngine,
+    files: &[FileEntry],
+    no_cache: bool,
+    mode: TallyMode,
+) -> Result<ViolationTally, RuleError> {
+    let mut tally = if no_cache {
+        engine.execute_tally(files, None, mode)
+    } else {
+        let cache_dir = Path::new(CACHE_DIR);
+        let mut cache = ResultCache::load(cache_dir).unwrap_or_else(|e| {
+            eprintln!("Warning: Ignoring unreadable cache in {}: {}", CACHE_DIR, e);
+            ResultCache::new()
+        });
+        let tally = engine.execute_tally(files, Some(&mut cache), mode);
+        if let Err(e) = cache.save(cache_dir) {
+            eprintln!("Warning: Failed to write cache to {}: {}", CACHE_DIR, e);
+        }
+        tally
+    };
+    engine.execute_batch_rules(files, &mut tally)?;
+    Ok(tally)
+}
+
+/// Tally mode for commands that only need counts (`tighten` and `bump`)
+///
+/// These commands never print individual violations, so violations are
+/// dropped once counted; `count_only` also skips building them.
+pub(crate) fn counting_mode(count_only: bool) -> TallyMode {
+    if count_only {
+        TallyMode::CountOnly
+    } else {
+        TallyMode::Summary
+    }
+}
+
 /// Render a [`ResolveError`] to stderr.
 ///
 /// `Cycle` becomes `Set composition cycle: $a -> $b -> $a` (note the
@@ -238,6 +332,18 @@ mod tests {
     use super::*;
     use crate::types::{GlobPattern, Language};
 
+    #[test]
+    fn test_no_language_reason_suggests_mapping() {
+        assert_eq!(
+            no_language_reason(Path::new("./tools/defs.bzl")),
+            "no matching language for *.bzl, see [languages.map]"
+        );
+        assert_eq!(
+            no_language_reason(Path::new("./BUILD")),
+            "no matching language for **/BUILD, see [languages.map]"
+        );
+    }
+
     #[test]
     fn test_load_config_missing_file() {
         // Cannot assert on the outcome: ratchets.toml may or may not exist in
@@ -254,12 +360,15 @@ mod tests {
                 languages: vec![Language::Rust],
                 include: vec![GlobPattern::new("**/*.rs")],
                 exclude: vec![],
+                header_language: None,
             },
             rules: crate::config::ratchet_toml::RulesConfig {
                 builtin: std::collections::HashMap::new(),
                 custom: std::collections::HashMap::new(),
+                instances: Vec::new(),
             },
             output: crate::config::ratchet_toml::OutputConfig::default(),
+            languages: crate::config::ratchet_toml::LanguagesConfig::default(),
             patterns: std::collections::HashMap::new(),
             enabled_ratchets: Vec::new(),
             disabled_ratchets: Vec::new(),
@@ -279,12 +388,15 @@ mod tests {
                 languages: vec![Language::Rust],
                 include: vec![GlobPattern::new("**/*.rs")],
                 exclude: vec![],
+                header_language: None,
             },
             rules: crate::config::ratchet_toml::RulesConfig {
                 builtin: std::collections::HashMap::new(),
                 custom: std::collections::HashMap::new(),
+                instances: Vec::new(),
             },
             output: crate::config::ratchet_toml::OutputConfig::default(),
+            languages: crate::config::ratchet_toml::LanguagesConfig::default(),
             patterns: std::collections::HashMap::new(),
             enabled_ratchets: Vec::new(),
             disabled_ratchets: Vec::new(),
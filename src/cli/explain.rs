@@ -0,0 +1,293 @@
+//! Explain command implementation
+//!
+//! This module implements the `ratchets explain` command, which:
+//! - Shows a rule's description, rationale, help link and examples
+//! - Shows the regex pattern or tree-sitter query the rule runs
+//! - Lists the ratchet-sets that include the rule and whether it is enabled
+//! - Shows the violations and budget of every configured region, worst first
+//! - Supports both human-readable and JSONL output formats
+
+use crate::cli::args::OutputFormat;
+use crate::cli::common::{EXIT_ERROR, EXIT_SUCCESS, load_counts};
+use crate::config::counts::CountsManager;
+use crate::config::sets::SetRegistry;
+use crate::engine::aggregator::{AggregationResult, TallyMode, ViolationAggregator};
+use crate::engine::executor::ExecutionEngine;
+use crate::error::{ConfigError, RuleError};
+use crate::output::{
+    CheckStatus, RegionStanding, RuleExplanation, RuleExplanationHumanFormatter,
+    RuleExplanationJsonlFormatter,
+};
+use crate::rules::{Rule, RuleContext, RuleRegistry};
+use crate::types::{RegionPath, RuleId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Error type specific to explain command
+#[derive(Debug, thiserror::Error)]
+enum ExplainError {
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("Rule error: {0}")]
+    Rule(#[from] RuleError),
+
+    #[error("File walker error: {0}")]
+    FileWalker(#[from] crate::engine::file_walker::FileWalkerError),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Run the explain command
+///
+/// This is the main entry point for the explain command. It:
+/// 1. Loads the configuration, budgets, every rule and every set
+/// 2. Collects the rule's documentation, definition and enabling sets
+/// 3. Checks the project with the rule to get its per-region standing
+/// 4. Prints the explanation
+///
+/// Disabled rules can be explained too; their standing is measured against
+/// whatever budgets ratchet-counts.toml still has for them.
+///
+/// # Arguments
+///
+/// * `rule_id` - Rule to explain
+/// * `format` - Output format (human or JSONL)
+///
+/// # Returns
+///
+/// Exit code:
+/// - 0: Success
+/// - 2: Error (unknown rule, invalid configuration, etc.)
+pub fn run_explain(rule_id: &str, format: OutputFormat) -> i32 {
+    match run_explain_inner(rule_id) {
+        Ok(explanation) => {
+            match format {
+                OutputFormat::Human => {
+                    RuleExplanationHumanFormatter::new().write_to_stdout(&explanation)
+                }
+                OutputFormat::Jsonl => {
+                    RuleExplanationJsonlFormatter::new().write_to_stdout(&explanation)
+                }
+            }
+            EXIT_SUCCESS
+        }
+        Err(e) => {
+            if let ExplainError::Config(ConfigError::UnsupportedVersion(_)) = &e {
+                super::upgrade_notice::print_to_stderr();
+            }
+            if let ExplainError::Rule(RuleError::SetResolve(ref resolve)) = e {
+                super::common::print_resolve_error(resolve);
+            }
+            eprintln!("Error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Internal implementation of explain command
+fn run_explain_inner(rule_id: &str) -> Result<RuleExplanation, ExplainError> {
+    // 1. Load ratchets.toml and ratchet-counts.toml
+    let config = super::common::load_config()?;
+    let counts = load_counts()?;
+
+    // 2. Load every rule, so that disabled rules can be explained too
+    let rule_context = RuleContext::new(config.patterns.clone());
+    let mut registry = RuleRegistry::load_all(&rule_context)?;
+    registry.load_rule_instances(&config.rules.instances, Some(&rule_context))?;
+    let rule_id = RuleId::new(rule_id)
+        .filter(|id| registry.get_rule(id).is_some())
+        .ok_or_else(|| ExplainError::Other(format!("Unknown rule '{}'", rule_id)))?;
+    registry.filter_to_single_rule(&rule_id);
+    let rule = registry
+        .get_rule(&rule_id)
+        .ok_or_else(|| ExplainError::Other(format!("Unknown rule '{}'", rule_id)))?;
+
+    // 3. Find the sets that include the rule, and whether the config enables it
+    let sets = SetRegistry::load_all()?;
+    let resolved = sets
+        .resolve(&config.enabled_ratchets, &config.disabled_ratchets)
+        .map_err(RuleError::from)?;
+    let languages = &config.ratchets.languages;
+    let enabled = resolved.contains(&rule_id)
+        && (languages.is_empty()
+            || rule.languages().is_empty()
+            || rule
+                .languages()
+                .iter()
+                .any(|l| languages.iter().any(|config| config.includes(*l))));
+
+    let mut explanation = describe_rule(rule);
+    explanation.sets = sets
+        .sets_including(&rule_id)
+        .iter()
+        .map(|id| id.as_str().to_string())
+        .collect();
+    explanation.enabled = enabled;
+
+    // 4. Check the project with just this rule
+    let files = super::common::discover_files(&[".".to_string()], &config)?;
+    let engine = ExecutionEngine::new(registry, Some(Arc::new(counts.clone())));
+    let tally = super::common::execute_rules(&engine, &files, true, TallyMode::Summary)?;
+    let aggregation_result = ViolationAggregator::new(counts.clone()).aggregate_tally(tally);
+
+    explanation.regions = build_region_standings(&rule_id, &counts, &aggregation_result);
+    Ok(explanation)
+}
+
+/// Collect a rule's documentation and definition
+///
+/// Sets, enablement and regions are left empty for the caller to fill in.
+fn describe_rule(rule: &dyn Rule) -> RuleExplanation {
+    let docs = rule.docs().cloned().unwrap_or_default();
+    RuleExplanation {
+        rule_id: rule.id().as_str().to_string(),
+        description: rule.description().to_string(),
+        severity: format!("{:?}", rule.severity()).to_lowercase(),
+        languages: rule
+            .languages()
+            .iter()
+            .map(|l| format!("{:?}", l).to_lowercase())
+            .collect(),
+        rationale: docs.rationale,
+        help_url: docs.help_url,
+        good: docs.good,
+        bad: docs.bad,
+        pattern: rule.regex().map(|regex| regex.as_str().to_string()),
+        query: rule.query().map(str::to_string),
+        sets: Vec::new(),
+        enabled: false,
+        regions: Vec::new(),
+    }
+}
+
+/// Build the standing of `rule_id` in every region that has a configured
+/// budget or current violations, sorted by violations (most first)
+///
+/// The aggregator only reports regions with violations, so configured
+/// regions without any are added with a count of zero.
+fn build_region_standings(
+    rule_id: &RuleId,
+    counts: &CountsManager,
+    aggregation_result: &AggregationResult,
+) -> Vec<RegionStanding> {
+    let mut standings: HashMap<RegionPath, (u64, u64)> = HashMap::new();
+    for (configured_rule, region) in counts.iter_configured() {
+        if configured_rule == rule_id {
+            let budget = counts.get_budget_by_region(rule_id, region);
+            standings.insert(region.clone(), (0, budget));
+        }
+    }
+    for status in &aggregation_result.statuses {
+        if status.rule_id == *rule_id {
+            standings.insert(status.region.clone(), (status.actual_count, status.budget));
+        }
+    }
+
+    let mut standings: Vec<RegionStanding> = standings
+        .into_iter()
+        .map(|(region, (violations, budget))| RegionStanding {
+            region: region.as_str().to_string(),
+            violations,
+            budget,
+            status: if violations <= budget {
+                CheckStatus::Pass
+            } else {
+                CheckStatus::OverBudget
+            },
+        })
+        .collect();
+    standings.sort_by(|a, b| {
+        b.violations
+            .cmp(&a.violations)
+            .then_with(|| a.region.cmp(&b.region))
+    });
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::aggregator::RuleRegionStatus;
+
+    #[test]
+    fn test_build_region_standings_includes_quiet_configured_regions()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let rule_id = RuleId::new("no-unwrap").ok_or("invalid rule id")?;
+        let other = RuleId::new("no-expect").ok_or("invalid rule id")?;
+
+        let mut counts = CountsManager::new();
+        counts.set_count(&rule_id, &RegionPath::new("."), 2);
+        counts.set_count(&rule_id, &RegionPath::new("src/legacy"), 10);
+        counts.set_count(&rule_id, &RegionPath::new("tests"), 4);
+        counts.set_count(&other, &RegionPath::new("src"), 7);
+
+        let status =
+            |rule_id: &RuleId, region: &str, actual_count: u64, budget: u64| RuleRegionStatus {
+                rule_id: rule_id.clone(),
+                region: RegionPath::new(region),
+                actual_count,
+                budget,
+                passed: actual_count <= budget,
+                violations: vec![],
+            };
+        let aggregation_result = AggregationResult {
+            statuses: vec![
+                status(&rule_id, ".", 3, 2),
+                status(&rule_id, "src/legacy", 9, 10),
+                status(&other, "src", 7, 7),
+            ],
+            passed: false,
+            total_violations: 19,
+            violations_over_budget: 1,
+            suppressed: Vec::new(),
+        };
+
+        let standings = build_region_standings(&rule_id, &counts, &aggregation_result);
+        let summary: Vec<(&str, u64, u64, CheckStatus)> = standings
+            .iter()
+            .map(|s| (s.region.as_str(), s.violations, s.budget, s.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/legacy", 9, 10, CheckStatus::Pass),
+                (".", 3, 2, CheckStatus::OverBudget),
+                ("tests", 0, 4, CheckStatus::Pass),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe_rule_reads_docs_and_definition() -> Result<(), Box<dyn std::error::Error>> {
+        let rule = crate::rules::RegexRule::from_toml(
+            r#"
+[rule]
+id = "no-eval"
+description = "Disallow eval()"
+severity = "error"
+rationale = "eval runs arbitrary code."
+bad = "eval(text)"
+
+[match]
+pattern = "\\beval\\("
+languages = ["python"]
+"#,
+        )?;
+
+        let explanation = describe_rule(&rule);
+        assert_eq!(explanation.rule_id, "no-eval");
+        assert_eq!(explanation.languages, vec!["python"]);
+        assert_eq!(
+            explanation.rationale.as_deref(),
+            Some("eval runs arbitrary code.")
+        );
+        assert_eq!(explanation.bad.as_deref(), Some("eval(text)"));
+        assert_eq!(explanation.good, None);
+        assert_eq!(explanation.pattern.as_deref(), Some(r"\beval\("));
+        assert_eq!(explanation.query, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,467 @@
+//! Fix command implementation
+//!
+//! This module implements the `ratchets fix` command, which:
+//! - Computes the edits of every enabled rule with a `[fix]` section
+//! - Applies non-overlapping edits to each file, or prints them as a unified
+//!   diff with `--dry-run`
+//! - Supports restricting fixes to a single rule and to given paths
+
+use crate::cli::common::{EXIT_ERROR, EXIT_SUCCESS};
+use crate::engine::executor::ExecutionEngine;
+use crate::error::ConfigError;
+use crate::rules::Fix;
+use crate::types::RuleId;
+use rayon::prelude::*;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Lines of unchanged context around each change in dry-run diffs
+const DIFF_CONTEXT: usize = 3;
+
+/// Error type specific to fix command
+#[derive(Debug, thiserror::Error)]
+enum FixError {
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("Rule error: {0}")]
+    Rule(#[from] crate::error::RuleError),
+
+    #[error("File walker error: {0}")]
+    FileWalker(#[from] crate::engine::file_walker::FileWalkerError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Run the fix command
+///
+/// This is the main entry point for the fix command. It:
+/// 1. Loads the enabled rules, keeping only `rule_id` if given
+/// 2. Computes each file's fixes, dropping fixes that overlap an earlier one
+/// 3. Writes the fixed files, or prints a unified diff if `dry_run` is set
+/// 4. Suggests `ratchets tighten` to lock in the lower counts
+///
+/// # Arguments
+///
+/// * `rule_id` - Optional rule whose violations to fix (fixes all if None).
+///   A value that names no enabled rule but exists on disk is taken as a path.
+/// * `paths` - Paths to fix (defaults to the current directory if empty)
+/// * `dry_run` - If true, print the changes instead of writing them
+///
+/// # Returns
+///
+/// Exit code:
+/// - 0: Success (including nothing to fix)
+/// - 2: Error (config error, unknown rule, etc.)
+pub fn run_fix(rule_id: Option<&str>, paths: &[String], dry_run: bool) -> i32 {
+    match run_fix_inner(rule_id, paths, dry_run) {
+        Ok(summary) => {
+            let verb = if dry_run { "Would fix" } else { "Fixed" };
+            if summary.fixes == 0 {
+                eprintln!("No fixable violations found");
+            } else {
+                eprintln!(
+                    "{} {} violations in {} files",
+                    verb, summary.fixes, summary.files
+                );
+            }
+            if summary.skipped > 0 {
+                eprintln!(
+                    "Skipped {} overlapping fixes; run 'ratchets fix' again to apply them",
+                    summary.skipped
+                );
+            }
+            if summary.fixes > 0 && !dry_run {
+                eprintln!("\nRun 'ratchets tighten' to lower budgets to the new counts");
+            }
+            EXIT_SUCCESS
+        }
+        Err(e) => {
+            // Print the embedded upgrade notice for schema version mismatches
+            // before the generic error printer.
+            if let FixError::Config(ConfigError::UnsupportedVersion(_)) = &e {
+                super::upgrade_notice::print_to_stderr();
+            }
+            // Render ratchet-set resolution errors before the generic printer.
+            if let FixError::Rule(crate::error::RuleError::SetResolve(ref resolve)) = e {
+                super::common::print_resolve_error(resolve);
+            }
+            eprintln!("Error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Totals of a fix run
+#[derive(Debug, Default)]
+struct FixSummary {
+    /// Number of fixes applied (or that would be applied)
+    fixes: usize,
+    /// Number of files changed
+    files: usize,
+    /// Number of fixes dropped because they overlap another fix
+    skipped: usize,
+}
+
+/// Internal implementation of fix command
+fn run_fix_inner(
+    rule_id: Option<&str>,
+    paths: &[String],
+    dry_run: bool,
+) -> Result<FixSummary, FixError> {
+    // 1. Load configuration and rules
+    let config = super::common::load_config()?;
+    let mut registry = super::common::build_registry(&config)?;
+
+    // 2. A first argument that names no enabled rule but exists on disk is a
+    //    path, so `ratchets fix src/` works without a rule ID
+    let mut paths = paths.to_vec();
+    let rule_id = match rule_id {
+        Some(id)
+            if !registry.iter_rules().any(|rule| rule.id().as_str() == id)
+                && Path::new(id).exists() =>
+        {
+            paths.insert(0, id.to_string());
+            None
+        }
+        other => other,
+    };
+    if paths.is_empty() {
+        paths.push(".".to_string());
+    }
+
+    // 3. Restrict to the requested rule, which must exist and have a fix
+    if let Some(id) = rule_id {
+        let validated = RuleId::new(id).ok_or_else(|| {
+            FixError::Other(format!(
+                "Invalid rule ID '{}'. Rule IDs must contain only alphanumeric characters, hyphens, and underscores.",
+                id
+            ))
+        })?;
+        let rule = registry
+            .get_rule(&validated)
+            .ok_or_else(|| FixError::Other(format!("Rule '{}' is not enabled", id)))?;
+        if !rule.has_fix() {
+            return Err(FixError::Other(format!(
+                "Rule '{}' has no [fix] section",
+                id
+            )));
+        }
+        registry.filter_to_single_rule(&validated);
+    }
+
+    if !registry.iter_rules().any(|rule| rule.has_fix()) {
+        return Err(FixError::Other(
+            "No enabled rules have a [fix] section. Nothing to fix.".to_string(),
+        ));
+    }
+
+    // 4. Compute every file's fixes in parallel
+    let files = super::common::discover_files(&paths, &config)?;
+    let engine = ExecutionEngine::new(registry, None);
+    let mut changes: Vec<FileChange> = files
+        .par_iter()
+        .filter_map(|file| {
+            let content = match fs::read_to_string(&file.path) {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Failed to read file {}: {}",
+                        file.path.display(),
+                        e
+                    );
+                    return None;
+                }
+            };
+            let (fixes, skipped) = select_fixes(&content, engine.fix_content(file, &content));
+            if fixes.is_empty() {
+                return None;
+            }
+            Some(FileChange {
+                path: file.path.display().to_string(),
+                content,
+                fixes,
+                skipped,
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    // 5. Write the fixed files, or print what would change
+    let mut summary = FixSummary::default();
+    for change in &changes {
+        if dry_run {
+            print!(
+                "{}",
+                unified_diff(&change.path, &change.content, &change.fixes)
+            );
+        } else {
+            fs::write(&change.path, apply_fixes(&change.content, &change.fixes))?;
+        }
+        summary.fixes += change.fixes.len();
+        summary.files += 1;
+        summary.skipped += change.skipped;
+    }
+
+    Ok(summary)
+}
+
+/// The fixes selected for one file
+struct FileChange {
+    path: String,
+    content: String,
+    fixes: Vec<Fix>,
+    skipped: usize,
+}
+
+/// Order fixes by position and drop those that cannot be applied together
+///
+/// Fixes that would not change the content are dropped silently. A fix that
+/// overlaps an earlier one, or inserts at the same offset, is dropped and
+/// counted; running the fix again applies it if it still matches.
+///
+/// Returns the fixes to apply and the number of overlapping fixes dropped.
+fn select_fixes(content: &str, mut fixes: Vec<Fix>) -> (Vec<Fix>, usize) {
+    fixes.retain(|fix| content.get(fix.range.clone()) != Some(fix.replacement.as_str()));
+    fixes.sort_by_key(|fix| (fix.range.start, fix.range.end));
+    fixes.dedup();
+
+    let mut selected: Vec<Fix> = Vec::with_capacity(fixes.len());
+    let mut skipped = 0;
+    for fix in fixes {
+        if let Some(last) = selected.last()
+            && (fix.range.start < last.range.end || fix.range.start == last.range.start)
+        {
+            skipped += 1;
+            continue;
+        }
+        selected.push(fix);
+    }
+    (selected, skipped)
+}
+
+/// Apply sorted, non-overlapping fixes to `content`
+fn apply_fixes(content: &str, fixes: &[Fix]) -> String {
+    let mut fixed = String::with_capacity(content.len());
+    let mut position = 0;
+    for fix in fixes {
+        fixed.push_str(&content[position..fix.range.start]);
+        fixed.push_str(&fix.replacement);
+        position = fix.range.end;
+    }
+    fixed.push_str(&content[position..]);
+    fixed
+}
+
+/// Lines of the original file replaced by the fixes on them
+struct Block {
+    /// Index of the first replaced line
+    start: usize,
+    /// Index one past the last replaced line
+    end: usize,
+    /// Text the lines are replaced with
+    replacement: String,
+}
+
+/// Render sorted, non-overlapping fixes to `content` as a unified diff
+fn unified_diff(path: &str, content: &str, fixes: &[Fix]) -> String {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut line_starts = Vec::with_capacity(lines.len());
+    let mut offset = 0;
+    for line in &lines {
+        line_starts.push(offset);
+        offset += line.len();
+    }
+    let line_of = |offset: usize| {
+        line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1)
+    };
+    let line_start = |index: usize| line_starts.get(index).copied().unwrap_or(content.len());
+
+    // Group fixes that share a line into blocks of whole replaced lines
+    let mut groups: Vec<(usize, usize, Vec<&Fix>)> = Vec::new();
+    for fix in fixes {
+        let start = line_of(fix.range.start);
+        let last = line_of(fix.range.end.saturating_sub(1).max(fix.range.start));
+        let end = (last + 1).min(lines.len());
+        match groups.last_mut() {
+            Some((_, group_end, group)) if start < *group_end => {
+                *group_end = (*group_end).max(end);
+                group.push(fix);
+            }
+            _ => groups.push((start, end, vec![fix])),
+        }
+    }
+    let blocks: Vec<Block> = groups
+        .into_iter()
+        .map(|(start, end, group)| {
+            let base = line_start(start);
+            let relative: Vec<Fix> = group
+                .into_iter()
+                .map(|fix| Fix {
+                    range: fix.range.start - base..fix.range.end - base,
+                    replacement: fix.replacement.clone(),
+                })
+                .collect();
+            Block {
+                start,
+                end,
+                replacement: apply_fixes(&content[base..line_start(end)], &relative),
+            }
+        })
+        .collect();
+
+    let path = path.strip_prefix("./").unwrap_or(path);
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+
+    // Blocks closer than twice the context share a hunk
+    let mut delta: isize = 0;
+    let mut index = 0;
+    while index < blocks.len() {
+        let mut last = index;
+        while last + 1 < blocks.len()
+            && blocks[last + 1].start - blocks[last].end <= 2 * DIFF_CONTEXT
+        {
+            last += 1;
+        }
+        let hunk = &blocks[index..=last];
+        let old_start = hunk[0].start.saturating_sub(DIFF_CONTEXT);
+        let old_end = (hunk[hunk.len() - 1].end + DIFF_CONTEXT).min(lines.len());
+
+        let mut body = String::new();
+        let mut new_len = 0;
+        let mut position = old_start;
+        for block in hunk {
+            for line in &lines[position..block.start] {
+                push_line(&mut body, ' ', line);
+                new_len += 1;
+            }
+            for line in &lines[block.start..block.end] {
+                push_line(&mut body, '-', line);
+            }
+            for line in block.replacement.split_inclusive('\n') {
+                push_line(&mut body, '+', line);
+                new_len += 1;
+            }
+            position = block.end;
+        }
+        for line in &lines[position..old_end] {
+            push_line(&mut body, ' ', line);
+            new_len += 1;
+        }
+
+        let old_len = old_end - old_start;
+        let new_start = (old_start as isize + delta) as usize;
+        let _ = writeln!(
+            diff,
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_len),
+            hunk_range(new_start, new_len)
+        );
+        diff.push_str(&body);
+
+        delta += new_len as isize - old_len as isize;
+        index = last + 1;
+    }
+
+    diff
+}
+
+/// Format a hunk's line range from its 0-indexed start and length
+///
+/// Empty ranges name the line before them, as `diff -u` does.
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, len)
+    }
+}
+
+/// Append a diff line, marking a missing final newline
+fn push_line(diff: &mut String, prefix: char, line: &str) {
+    diff.push(prefix);
+    diff.push_str(line);
+    if !line.ends_with('\n') {
+        diff.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(range: std::ops::Range<usize>, replacement: &str) -> Fix {
+        Fix {
+            range,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fix_error_display() {
+        let err = FixError::Other("test error".to_string());
+        assert_eq!(err.to_string(), "test error");
+    }
+
+    #[test]
+    fn test_select_fixes_drops_overlaps_and_no_ops() {
+        let content = "abcdef";
+        let (selected, skipped) = select_fixes(
+            content,
+            vec![
+                fix(3..5, "X"),
+                fix(0..2, "Y"),
+                fix(1..4, "Z"),
+                fix(5..6, "f"),
+                fix(0..2, "Y"),
+            ],
+        );
+        assert_eq!(selected, vec![fix(0..2, "Y"), fix(3..5, "X")]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let content = "os.path.join(a, b)\nx = 1\n";
+        let fixes = vec![fix(0..18, "Path(a) / b"), fix(23..24, "2")];
+        assert_eq!(apply_fixes(content, &fixes), "Path(a) / b\nx = 2\n");
+    }
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        let content: String = (1..=12).map(|i| format!("line {}\n", i)).collect();
+        let line = |n: usize| content.find(&format!("line {}\n", n)).unwrap_or_default();
+
+        // Lines 2 and 4 share a hunk; line 12 gets its own
+        let fixes = vec![
+            fix(line(2)..line(2) + 6, "LINE 2"),
+            fix(line(4)..line(5), "LINE 4\nextra\n"),
+            fix(line(12)..line(12) + 7, "LINE 12"),
+        ];
+        let diff = unified_diff("./src/a.py", &content, &fixes);
+        assert_eq!(
+            diff,
+            "--- a/src/a.py\n+++ b/src/a.py\n\
+             @@ -1,7 +1,8 @@\n line 1\n-line 2\n+LINE 2\n line 3\n-line 4\n+LINE 4\n+extra\n line 5\n line 6\n line 7\n\
+             @@ -9,4 +10,4 @@\n line 9\n line 10\n line 11\n-line 12\n+LINE 12\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_missing_final_newline() {
+        let diff = unified_diff("a.rs", "let x = 1;", &[fix(8..9, "2")]);
+        assert_eq!(
+            diff,
+            "--- a/a.rs\n+++ b/a.rs\n@@ -1,1 +1,1 @@\n\
+             -let x = 1;\n\\ No newline at end of file\n\
+             +let x = 2;\n\\ No newline at end of file\n"
+        );
+    }
+}
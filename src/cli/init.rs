@@ -29,7 +29,10 @@ disabled_ratchets = []
 version = "2"
 
 # Languages to enable (uncomment as needed)
-# languages = ["rust", "typescript", "javascript", "python", "go"]
+# languages = ["rust", "typescript", "tsx", "javascript", "python", "go", "java", "kotlin", "c", "cpp", "bash"]
+
+# Language of .h headers, "c" (default) or "cpp"
+# header_language = "cpp"
 
 # File patterns to include (defaults to all)
 # include = ["src/**", "tests/**"]
@@ -43,6 +46,11 @@ version = "2"
 
 [output]
 format = "human"
+
+# Languages of files matching glob patterns, overriding detection by
+# extension or shebang (uncomment as needed)
+# [languages.map]
+# "*.bzl" = "python"
 "#;
 
 /// Default content for ratchet-counts.toml
@@ -105,6 +113,8 @@ impl InitResult {
 /// - ratchet-counts.toml (violation budgets)
 /// - ratchets/regex/ (directory for custom regex rules)
 /// - ratchets/ast/ (directory for custom AST rules)
+/// - ratchets/imports/ (directory for custom import rules)
+/// - ratchets/files/ (directory for custom file rules)
 ///
 /// # Arguments
 /// * `force` - If true, overwrite existing files. If false, skip existing files.
@@ -141,6 +151,8 @@ pub fn run_init(force: bool) -> Result<InitResult, InitError> {
     // Create directories (always create if they don't exist)
     create_directory("ratchets/regex", &mut result)?;
     create_directory("ratchets/ast", &mut result)?;
+    create_directory("ratchets/imports", &mut result)?;
+    create_directory("ratchets/files", &mut result)?;
 
     Ok(result)
 }
@@ -288,6 +300,8 @@ mod tests {
             // Check that directories exist
             assert!(temp_dir.path().join("ratchets/regex").is_dir());
             assert!(temp_dir.path().join("ratchets/ast").is_dir());
+            assert!(temp_dir.path().join("ratchets/imports").is_dir());
+            assert!(temp_dir.path().join("ratchets/files").is_dir());
             Ok(())
         })
     }
@@ -349,7 +363,7 @@ mod tests {
         with_temp_dir(|_temp_dir| {
             // First run should create everything
             let result1 = run_init(false)?;
-            assert_eq!(result1.created.len(), 4); // 2 files + 2 directories
+            assert_eq!(result1.created.len(), 6); // 2 files + 4 directories
             assert!(result1.skipped.is_empty());
             assert!(result1.overwritten.is_empty());
 
@@ -8,7 +8,7 @@
 use crate::cli::args::OutputFormat;
 use crate::cli::common::{EXIT_ERROR, EXIT_SUCCESS, load_counts};
 use crate::config::counts::CountsManager;
-use crate::engine::aggregator::ViolationAggregator;
+use crate::engine::aggregator::{TallyMode, ViolationAggregator};
 use crate::engine::executor::ExecutionEngine;
 use crate::output::{
     CheckStatus, RuleSource, RuleStatus, RuleStatusHumanFormatter, RuleStatusJsonlFormatter,
@@ -106,11 +106,11 @@ fn run_list_inner(format: OutputFormat) -> Result<(), ListError> {
         .collect();
 
     let engine = ExecutionEngine::new(registry, Some(std::sync::Arc::new(counts.clone())));
-    let execution_result = engine.execute(files);
+    let tally = super::common::execute_rules(&engine, &files, true, TallyMode::Summary)?;
 
     // 7. Aggregate violations to get per-rule counts
     let aggregator = ViolationAggregator::new(counts.clone());
-    let aggregation_result = aggregator.aggregate(execution_result.violations);
+    let aggregation_result = aggregator.aggregate_tally(tally);
 
     // 8. Build rule status list
     let rule_statuses = build_rule_statuses(&rule_metadata, &counts, &aggregation_result);
@@ -227,6 +227,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let statuses = build_rule_statuses(&rule_metadata, &counts, &aggregation_result);
@@ -259,6 +260,7 @@ mod tests {
             passed: true,
             total_violations: 5,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let statuses = build_rule_statuses(&rule_metadata, &counts, &aggregation_result);
@@ -298,6 +300,7 @@ mod tests {
             passed: false,
             total_violations: 10,
             violations_over_budget: 5,
+            suppressed: Vec::new(),
         };
 
         let statuses = build_rule_statuses(&rule_metadata, &counts, &aggregation_result);
@@ -357,6 +360,7 @@ mod tests {
             passed: false,
             total_violations: 15,
             violations_over_budget: 2,
+            suppressed: Vec::new(),
         };
 
         let statuses = build_rule_statuses(&rule_metadata, &counts, &aggregation_result);
@@ -390,6 +394,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let statuses = build_rule_statuses(&rule_metadata, &counts, &aggregation_result);
@@ -0,0 +1,603 @@
+//! Language server command implementation
+//!
+//! This module implements the `ratchets lsp` command, a Language Server
+//! Protocol server over stdin/stdout which:
+//! - Checks every file on startup so region counts cover the whole tree
+//! - Re-checks open documents on every edit and publishes their violations
+//!   as diagnostics
+//! - Shows the rule description and the region's current/budget numbers on hover
+//! - Reloads configuration and rules when files are saved
+//!
+//! Documents are synced in full on every change. Positions are converted
+//! between the byte columns of [`Violation`] and the UTF-16 columns LSP uses.
+
+use crate::cli::common::{EXIT_ERROR, EXIT_SUCCESS};
+use crate::cli::watch::{WatchEvent, Watcher};
+use crate::rules::Violation;
+use crate::types::Severity;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// JSON-RPC error code for requests received before `initialize`
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+/// JSON-RPC error code for unsupported requests
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// JSON-RPC error code for requests received after `shutdown`
+const INVALID_REQUEST: i64 = -32600;
+
+/// Exit code when the client exits without requesting shutdown, per the LSP spec
+const EXIT_WITHOUT_SHUTDOWN: i32 = 1;
+
+/// Error type specific to the lsp command
+#[derive(Debug, thiserror::Error)]
+pub enum LspError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid message: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+}
+
+/// A zero-indexed LSP position; `character` counts UTF-16 code units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+impl Position {
+    fn to_json(self) -> Value {
+        json!({ "line": self.line, "character": self.character })
+    }
+}
+
+/// An open document and the violations last published for it
+struct Document {
+    path: PathBuf,
+    text: String,
+    /// Each violation with its start and end position
+    diagnostics: Vec<(Position, Position, Violation)>,
+}
+
+/// Read one `Content-Length` framed message
+///
+/// Returns None at end of input.
+///
+/// # Errors
+///
+/// Returns an error if reading fails, the header has no valid
+/// `Content-Length`, or the body is not JSON.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, LspError> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            let length = value.trim().parse::<usize>().map_err(|_| {
+                LspError::Protocol(format!("invalid Content-Length: {}", value.trim()))
+            })?;
+            content_length = Some(length);
+        }
+    }
+
+    let length = content_length
+        .ok_or_else(|| LspError::Protocol("missing Content-Length header".to_string()))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Write one `Content-Length` framed message
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), LspError> {
+    let body = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Convert a zero-indexed byte column within `line` to UTF-16 code units
+///
+/// Columns past the end of the line are clamped to its length, and columns
+/// inside a multi-byte character are rounded down to its start.
+pub fn utf16_column(line: &str, byte_column: usize) -> u32 {
+    let mut end = byte_column.min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line[..end].encode_utf16().count() as u32
+}
+
+/// Map a rule severity to an LSP `DiagnosticSeverity`
+fn diagnostic_severity(severity: Severity) -> u8 {
+    match severity {
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+    }
+}
+
+/// Convert a `file://` URI to a path, decoding percent escapes
+///
+/// The URI's authority must be empty or `localhost`; files on other hosts
+/// have no local path.
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let encoded = encoded.strip_prefix("localhost").unwrap_or(encoded);
+    if !encoded.starts_with('/') {
+        return None;
+    }
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = encoded
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+/// Express an absolute path the way the file walker spells it from `.`
+///
+/// The watcher walks the current directory, so its files look like
+/// `./src/main.rs`; documents outside the current directory are returned
+/// unchanged and are never watched.
+fn walker_path(path: &Path) -> PathBuf {
+    let Ok(cwd) = std::env::current_dir() else {
+        return path.to_path_buf();
+    };
+    let canonical = std::fs::canonicalize(path).ok();
+    let relative = path
+        .strip_prefix(&cwd)
+        .ok()
+        .or_else(|| canonical.as_deref()?.strip_prefix(&cwd).ok());
+    match relative {
+        Some(relative) => Path::new(".").join(relative),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Language server state
+///
+/// Wraps a [`Watcher`] so region counts cover every file in the tree, with
+/// the unsaved content of open documents standing in for their files on disk.
+pub struct Server<W: Write> {
+    writer: W,
+    watcher: Option<Watcher>,
+    documents: HashMap<String, Document>,
+    initialized: bool,
+    shutdown: bool,
+}
+
+impl<W: Write> Server<W> {
+    /// Creates a server that writes responses and notifications to `writer`
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            watcher: None,
+            documents: HashMap::new(),
+            initialized: false,
+            shutdown: false,
+        }
+    }
+
+    /// Handle one incoming message
+    ///
+    /// Returns the process exit code once the client sends `exit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a response cannot be written.
+    pub fn handle(&mut self, message: &Value) -> Result<Option<i32>, LspError> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+
+        let Some(id) = message.get("id") else {
+            return self.handle_notification(method, params);
+        };
+
+        let result = match method {
+            _ if self.shutdown => Err((INVALID_REQUEST, "Server is shutting down")),
+            "initialize" => {
+                self.initialize(params)?;
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "change": 1, "save": true },
+                        "hoverProvider": true,
+                    },
+                    "serverInfo": { "name": "ratchets", "version": env!("CARGO_PKG_VERSION") },
+                }))
+            }
+            _ if !self.initialized => Err((SERVER_NOT_INITIALIZED, "Server not initialized")),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => Ok(self.hover(params)),
+            _ => Err((METHOD_NOT_FOUND, "Method not found")),
+        };
+
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        };
+        write_message(&mut self.writer, &response)?;
+        Ok(None)
+    }
+
+    /// Handle a notification, which gets no response
+    fn handle_notification(
+        &mut self,
+        method: &str,
+        params: &Value,
+    ) -> Result<Option<i32>, LspError> {
+        match method {
+            "exit" => {
+                let code = if self.shutdown {
+                    EXIT_SUCCESS
+                } else {
+                    EXIT_WITHOUT_SHUTDOWN
+                };
+                return Ok(Some(code));
+            }
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                if let (Some(uri), Some(text)) =
+                    (document["uri"].as_str(), document["text"].as_str())
+                    && let Some(path) = uri_to_path(uri)
+                {
+                    let document = Document {
+                        path: walker_path(&path),
+                        text: text.to_string(),
+                        diagnostics: Vec::new(),
+                    };
+                    self.documents.insert(uri.to_string(), document);
+                    self.check_document(uri)?;
+                }
+            }
+            "textDocument/didChange" => {
+                // Full sync: the last change holds the whole document
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                if let (Some(document), Some(text)) = (self.documents.get_mut(uri), text) {
+                    document.text = text.to_string();
+                    self.check_document(uri)?;
+                }
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                if let Some(document) = self.documents.remove(uri) {
+                    if let Some(watcher) = self.watcher.as_mut() {
+                        watcher.revert_document(&document.path);
+                    }
+                    self.publish(uri, Vec::new())?;
+                }
+            }
+            "textDocument/didSave" | "workspace/didChangeWatchedFiles" => self.refresh()?,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Switch to the workspace root and load configuration and rules
+    ///
+    /// A load failure is shown to the user; the server keeps running and
+    /// retries after the next save.
+    fn initialize(&mut self, params: &Value) -> Result<(), LspError> {
+        self.initialized = true;
+        if let Some(root) = params["rootUri"].as_str().and_then(uri_to_path)
+            && let Err(e) = std::env::set_current_dir(&root)
+        {
+            self.show_error(&format!("Cannot open workspace {}: {}", root.display(), e))?;
+        }
+        self.load()
+    }
+
+    /// Load the watcher, reporting failures to the user
+    fn load(&mut self) -> Result<(), LspError> {
+        match Watcher::new(&[".".to_string()]) {
+            Ok(watcher) => self.watcher = Some(watcher),
+            Err(e) => self.show_error(&e.to_string())?,
+        }
+        Ok(())
+    }
+
+    /// Pick up changes on disk, re-checking open documents if anything changed
+    fn refresh(&mut self) -> Result<(), LspError> {
+        let event = match self.watcher.as_mut() {
+            Some(watcher) => watcher.poll(),
+            None => {
+                self.load()?;
+                Ok(WatchEvent::Reloaded)
+            }
+        };
+
+        match event {
+            Ok(WatchEvent::Unchanged) => Ok(()),
+            Ok(_) => {
+                let uris: Vec<String> = self.documents.keys().cloned().collect();
+                for uri in uris {
+                    self.check_document(&uri)?;
+                }
+                Ok(())
+            }
+            Err(e) => self.show_error(&format!("{} (keeping previous results)", e)),
+        }
+    }
+
+    /// Re-check an open document and publish its diagnostics
+    fn check_document(&mut self, uri: &str) -> Result<(), LspError> {
+        let Some(document) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+        let violations = self
+            .watcher
+            .as_mut()
+            .and_then(|watcher| watcher.check_document(&document.path, &document.text))
+            .unwrap_or_default();
+
+        let lines: Vec<&str> = document.text.split('\n').collect();
+        let position = |line: u32, column: u32| {
+            let index = line.saturating_sub(1);
+            let text = lines.get(index as usize).copied().unwrap_or_default();
+            Position {
+                line: index,
+                character: utf16_column(text, column.saturating_sub(1) as usize),
+            }
+        };
+        document.diagnostics = violations
+            .into_iter()
+            .map(|v| {
+                let start = position(v.line, v.column);
+                let end = position(v.end_line, v.end_column);
+                (start, end, v)
+            })
+            .collect();
+
+        let diagnostics = document
+            .diagnostics
+            .iter()
+            .map(|(start, end, violation)| {
+                let severity = self
+                    .watcher
+                    .as_ref()
+                    .and_then(|watcher| watcher.registry().get_rule(&violation.rule_id))
+                    .map_or(Severity::Warning, |rule| rule.severity());
+                json!({
+                    "range": { "start": start.to_json(), "end": end.to_json() },
+                    "severity": diagnostic_severity(severity),
+                    "code": violation.rule_id.as_str(),
+                    "source": "ratchets",
+                    "message": violation.message,
+                })
+            })
+            .collect();
+        self.publish(uri, diagnostics)
+    }
+
+    /// Describe the rules violated at the hovered position
+    ///
+    /// Each rule shows its description and the current/budget numbers of the
+    /// region the document belongs to.
+    fn hover(&self, params: &Value) -> Value {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let (Some(document), Some(watcher)) = (self.documents.get(uri), self.watcher.as_ref())
+        else {
+            return Value::Null;
+        };
+        let position = Position {
+            line: params["position"]["line"].as_u64().unwrap_or_default() as u32,
+            character: params["position"]["character"].as_u64().unwrap_or_default() as u32,
+        };
+
+        let mut sections: Vec<String> = Vec::new();
+        let mut seen = Vec::new();
+        for (start, end, violation) in &document.diagnostics {
+            if position < *start || position > *end || seen.contains(&&violation.rule_id) {
+                continue;
+            }
+            seen.push(&violation.rule_id);
+
+            let rule_id = &violation.rule_id;
+            let description = watcher
+                .registry()
+                .get_rule(rule_id)
+                .map(|rule| rule.description())
+                .unwrap_or_default();
+            let counts = watcher.counts();
+            let region = counts.find_configured_region(rule_id, &document.path);
+            let current = watcher.region_count(rule_id, &region);
+            let budget = counts.get_budget_by_region(rule_id, &region);
+            let standing = if current > budget {
+                " (over budget)"
+            } else {
+                ""
+            };
+            sections.push(format!(
+                "**{}**: {}\n\nRegion `{}`: {} / {} allowed{}",
+                rule_id.as_str(),
+                description,
+                region.as_str(),
+                current,
+                budget,
+                standing
+            ));
+        }
+
+        if sections.is_empty() {
+            return Value::Null;
+        }
+        json!({
+            "contents": { "kind": "markdown", "value": sections.join("\n\n---\n\n") },
+        })
+    }
+
+    /// Send `textDocument/publishDiagnostics` for a document
+    fn publish(&mut self, uri: &str, diagnostics: Vec<Value>) -> Result<(), LspError> {
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        });
+        write_message(&mut self.writer, &notification)
+    }
+
+    /// Show an error message in the editor
+    fn show_error(&mut self, message: &str) -> Result<(), LspError> {
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "window/showMessage",
+            "params": { "type": 1, "message": format!("ratchets: {}", message) },
+        });
+        write_message(&mut self.writer, &notification)
+    }
+}
+
+/// Serve LSP messages from `reader` until the client exits
+///
+/// # Errors
+///
+/// Returns an error if a message cannot be read or a response cannot be
+/// written.
+pub fn serve(mut reader: impl BufRead, writer: impl Write) -> Result<i32, LspError> {
+    let mut server = Server::new(writer);
+    while let Some(message) = read_message(&mut reader)? {
+        if let Some(code) = server.handle(&message)? {
+            return Ok(code);
+        }
+    }
+    Ok(EXIT_WITHOUT_SHUTDOWN)
+}
+
+/// Run the lsp command
+///
+/// Serves the Language Server Protocol over stdin/stdout until the client
+/// sends `exit`.
+///
+/// # Returns
+///
+/// Exit code 0 after an orderly shutdown, 1 if the client exits without
+/// requesting shutdown, or 2 if the connection fails.
+pub fn run_lsp() -> i32 {
+    let stdin = std::io::stdin();
+    match serve(stdin.lock(), std::io::stdout()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf16_column() {
+        assert_eq!(utf16_column("let x = 1;", 4), 4);
+        // 'é' is 2 bytes and 1 UTF-16 unit; '😀' is 4 bytes and 2 units
+        assert_eq!(utf16_column("é = 1", 3), 2);
+        assert_eq!(utf16_column("😀x", 4), 2);
+        assert_eq!(utf16_column("😀x", 5), 3);
+        // Inside a character rounds down; past the end clamps
+        assert_eq!(utf16_column("😀x", 2), 0);
+        assert_eq!(utf16_column("ab", 10), 2);
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///tmp/my%20project/a.rs"),
+            Some(PathBuf::from("/tmp/my project/a.rs"))
+        );
+        assert_eq!(
+            uri_to_path("file://localhost/tmp/a.rs"),
+            Some(PathBuf::from("/tmp/a.rs"))
+        );
+        assert_eq!(uri_to_path("file://server/share/a.rs"), None);
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+
+    #[test]
+    fn test_message_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let message = json!({ "jsonrpc": "2.0", "method": "exit" });
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &message)?;
+        write_message(&mut buffer, &message)?;
+
+        let mut reader = std::io::Cursor::new(buffer);
+        assert_eq!(read_message(&mut reader)?, Some(message.clone()));
+        assert_eq!(read_message(&mut reader)?, Some(message));
+        assert_eq!(read_message(&mut reader)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_requests_before_initialize_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let mut server = Server::new(Vec::new());
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" });
+        assert_eq!(server.handle(&request)?, None);
+
+        let mut reader = std::io::Cursor::new(server.writer);
+        let response = read_message(&mut reader)?.ok_or("no response")?;
+        assert_eq!(response["error"]["code"], SERVER_NOT_INITIALIZED);
+        Ok(())
+    }
+
+    #[test]
+    fn test_requests_after_shutdown_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let mut server = Server::new(Vec::new());
+        server.initialized = true;
+        for (id, method) in [(1, "shutdown"), (2, "textDocument/hover"), (3, "shutdown")] {
+            let request = json!({ "jsonrpc": "2.0", "id": id, "method": method });
+            assert_eq!(server.handle(&request)?, None);
+        }
+        let exit = json!({ "jsonrpc": "2.0", "method": "exit" });
+        assert_eq!(server.handle(&exit)?, Some(EXIT_SUCCESS));
+
+        let mut reader = std::io::Cursor::new(server.writer);
+        let response = read_message(&mut reader)?.ok_or("no response")?;
+        assert_eq!(response["result"], Value::Null);
+        for id in [2, 3] {
+            let response = read_message(&mut reader)?.ok_or("no response")?;
+            assert_eq!(response["id"], id);
+            assert_eq!(response["error"]["code"], INVALID_REQUEST);
+        }
+        Ok(())
+    }
+}
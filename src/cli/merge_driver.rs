@@ -100,55 +100,42 @@ fn merge_counts(
 ) -> CountsManager {
     let mut merged = CountsManager::new();
 
-    let ours_counts = extract_all_counts(ours);
-    let theirs_counts = extract_all_counts(theirs);
-
-    let mut ours_map: HashMap<(String, String), u64> = HashMap::new();
-    for (rule_id, region, count) in &ours_counts {
-        ours_map.insert(
-            (rule_id.as_str().to_string(), region.as_str().to_string()),
-            *count,
-        );
-    }
-
-    let mut theirs_map: HashMap<(String, String), u64> = HashMap::new();
-    for (rule_id, region, count) in &theirs_counts {
-        theirs_map.insert(
-            (rule_id.as_str().to_string(), region.as_str().to_string()),
-            *count,
-        );
-    }
+    let ours_map: HashMap<CountKey, u64> = extract_all_counts(ours).into_iter().collect();
+    let theirs_map: HashMap<CountKey, u64> = extract_all_counts(theirs).into_iter().collect();
 
-    let mut all_keys: HashSet<(String, String)> = HashSet::new();
-    all_keys.extend(ours_map.keys().cloned());
-    all_keys.extend(theirs_map.keys().cloned());
+    let mut all_keys: HashSet<&CountKey> = HashSet::new();
+    all_keys.extend(ours_map.keys());
+    all_keys.extend(theirs_map.keys());
 
     // Take the minimum of the two values, or the only value if present in one side.
-    for (rule_id_str, region_str) in all_keys {
-        let ours_count = ours_map.get(&(rule_id_str.clone(), region_str.clone()));
-        let theirs_count = theirs_map.get(&(rule_id_str.clone(), region_str.clone()));
-
-        let final_count = match (ours_count, theirs_count) {
+    for key in all_keys {
+        let final_count = match (ours_map.get(key), theirs_map.get(key)) {
             (Some(&o), Some(&t)) => std::cmp::min(o, t),
             (Some(&o), None) => o,
             (None, Some(&t)) => t,
             (None, None) => continue, // Should never happen
         };
 
-        if let Some(rule_id) = RuleId::new(&rule_id_str) {
-            let region = RegionPath::new(region_str);
-            merged.set_count(&rule_id, &region, final_count);
+        let (rule_id, region, suppressed) = key;
+        if *suppressed {
+            merged.set_suppressed_count(rule_id, region, final_count);
+        } else {
+            merged.set_count(rule_id, region, final_count);
         }
     }
 
     merged
 }
 
-/// Extract all (rule_id, region, count) tuples from a CountsManager.
+/// A budget entry: rule, region, and whether it budgets suppressed violations
+type CountKey = (RuleId, RegionPath, bool);
+
+/// Extract every budget entry and its count from a CountsManager.
 ///
-/// CountsManager has no public iterator, so we round-trip through TOML to read
-/// back its rule/region/count entries.
-fn extract_all_counts(counts: &CountsManager) -> Vec<(RuleId, RegionPath, u64)> {
+/// CountsManager has no public iterator over explicit entries, so we
+/// round-trip through TOML to read back its rule/region/count entries,
+/// including those in `suppressed` sub-tables.
+fn extract_all_counts(counts: &CountsManager) -> Vec<(CountKey, u64)> {
     let mut result = Vec::new();
 
     let toml_str = counts.to_toml_string();
@@ -160,14 +147,7 @@ fn extract_all_counts(counts: &CountsManager) -> Vec<(RuleId, RegionPath, u64)>
             if let Some(rule_id) = RuleId::new(&rule_id_str)
                 && let toml::Value::Table(regions) = value
             {
-                for (region_str, count_value) in regions {
-                    let region = RegionPath::new(region_str);
-                    if let Some(count) = count_value.as_integer()
-                        && count >= 0
-                    {
-                        result.push((rule_id.clone(), region, count as u64));
-                    }
-                }
+                extract_regions(&rule_id, regions, false, &mut result);
             }
         }
     }
@@ -175,6 +155,27 @@ fn extract_all_counts(counts: &CountsManager) -> Vec<(RuleId, RegionPath, u64)>
     result
 }
 
+/// Extract the region counts of one rule table into `result`
+fn extract_regions(
+    rule_id: &RuleId,
+    regions: toml::Table,
+    suppressed: bool,
+    result: &mut Vec<(CountKey, u64)>,
+) {
+    for (region_str, value) in regions {
+        match value {
+            toml::Value::Integer(count) if count >= 0 => {
+                let key = (rule_id.clone(), RegionPath::new(region_str), suppressed);
+                result.push((key, count as u64));
+            }
+            toml::Value::Table(nested) if !suppressed && region_str == "suppressed" => {
+                extract_regions(rule_id, nested, true, result);
+            }
+            _ => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -390,6 +391,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_merge_driver_suppressed_budgets() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+
+        let base = create_test_file(&temp_dir, "base.toml", "")?;
+        let ours = create_test_file(
+            &temp_dir,
+            "ours.toml",
+            r#"
+[no-unwrap]
+"." = 4
+
+[no-unwrap.suppressed]
+"." = 3
+"#,
+        )?;
+        let theirs = create_test_file(
+            &temp_dir,
+            "theirs.toml",
+            r#"
+[no-unwrap]
+"." = 5
+
+[no-unwrap.suppressed]
+"." = 2
+"#,
+        )?;
+
+        let result = run_merge_driver(&base, &ours, &theirs);
+        assert_eq!(result, EXIT_SUCCESS);
+
+        let merged_counts = CountsManager::parse(&fs::read_to_string(&ours)?)?;
+        let rule_id = RuleId::new("no-unwrap").ok_or("invalid rule id")?;
+        let root = RegionPath::new(".");
+        assert_eq!(merged_counts.get_budget_by_region(&rule_id, &root), 4);
+        assert_eq!(
+            merged_counts.get_suppressed_budget_by_region(&rule_id, &root),
+            Some(2)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_merge_driver_missing_files() -> Result<(), Box<dyn std::error::Error>> {
         let temp_dir = TempDir::new()?;
@@ -447,13 +490,13 @@ mod tests {
 
         let has_rule1_root = extracted
             .iter()
-            .any(|(r, p, c)| r.as_str() == "rule1" && p.as_str() == "." && *c == 10);
+            .any(|((r, p, _), c)| r.as_str() == "rule1" && p.as_str() == "." && *c == 10);
         let has_rule1_src = extracted
             .iter()
-            .any(|(r, p, c)| r.as_str() == "rule1" && p.as_str() == "src" && *c == 5);
+            .any(|((r, p, _), c)| r.as_str() == "rule1" && p.as_str() == "src" && *c == 5);
         let has_rule2_root = extracted
             .iter()
-            .any(|(r, p, c)| r.as_str() == "rule2" && p.as_str() == "." && *c == 20);
+            .any(|((r, p, _), c)| r.as_str() == "rule2" && p.as_str() == "." && *c == 20);
 
         assert!(has_rule1_root);
         assert!(has_rule1_src);
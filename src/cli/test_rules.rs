@@ -0,0 +1,238 @@
+//! Test-rules command implementation
+//!
+//! This module implements the `ratchets test-rules` command, which:
+//! - Loads every builtin and custom rule, whether or not it is enabled
+//! - Checks each rule against the examples in its `[[test]]` tables, running
+//!   them through the execution engine like real files
+//! - Reports failing examples and exits non-zero if any fail
+
+use crate::cli::common::{EXIT_ERROR, EXIT_EXCEEDED, EXIT_SUCCESS};
+use crate::config::Config;
+use crate::engine::executor::ExecutionEngine;
+use crate::engine::file_walker::{FileEntry, LanguageDetector};
+use crate::error::ConfigError;
+use crate::rules::{Rule, RuleContext, RuleRegistry};
+use crate::types::RuleId;
+use std::path::{Path, PathBuf};
+
+/// Error type specific to test-rules command
+#[derive(Debug, thiserror::Error)]
+enum TestRulesError {
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("Rule error: {0}")]
+    Rule(#[from] crate::error::RuleError),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+/// A rule example whose violations differ from its expectation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailure {
+    /// Rule under test
+    pub rule_id: RuleId,
+    /// Position of the `[[test]]` table in the rule file (1-indexed)
+    pub index: usize,
+    /// Path the example was checked as
+    pub filename: PathBuf,
+    /// Description of the mismatch
+    pub reason: String,
+}
+
+/// Results of running rule examples
+#[derive(Debug, Default)]
+pub struct TestReport {
+    /// Number of rules with at least one example
+    pub rules_tested: usize,
+    /// Number of examples that passed
+    pub passed: usize,
+    /// Examples that failed, sorted by rule and position
+    pub failures: Vec<TestFailure>,
+}
+
+/// Run the test-rules command
+///
+/// This is the main entry point for the test-rules command. It:
+/// 1. Loads every builtin and custom rule, keeping only `rule_id` if given
+/// 2. Checks each rule's examples and prints the failures
+/// 3. Prints a summary of passed and failed examples
+///
+/// Pattern references in custom rules resolve against ratchets.toml if it
+/// exists; the command also works without one.
+///
+/// # Arguments
+///
+/// * `rule_id` - Optional rule to test (tests all rules if None)
+///
+/// # Returns
+///
+/// Exit code:
+/// - 0: Every example passed
+/// - 1: At least one example failed
+/// - 2: Error (invalid rule definition, unknown rule, etc.)
+pub fn run_test_rules(rule_id: Option<&str>) -> i32 {
+    match run_test_rules_inner(rule_id) {
+        Ok(report) => {
+            for failure in &report.failures {
+                println!(
+                    "FAIL {} [test {}, {}]: {}",
+                    failure.rule_id,
+                    failure.index,
+                    failure.filename.display(),
+                    failure.reason
+                );
+            }
+            if !report.failures.is_empty() {
+                println!();
+            }
+            println!(
+                "{} passed, {} failed across {} rules with tests",
+                report.passed,
+                report.failures.len(),
+                report.rules_tested
+            );
+
+            if report.failures.is_empty() {
+                EXIT_SUCCESS
+            } else {
+                EXIT_EXCEEDED
+            }
+        }
+        Err(e) => {
+            if let TestRulesError::Config(ConfigError::UnsupportedVersion(_)) = &e {
+                super::upgrade_notice::print_to_stderr();
+            }
+            eprintln!("Error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Internal implementation of test-rules command
+fn run_test_rules_inner(rule_id: Option<&str>) -> Result<TestReport, TestRulesError> {
+    // 1. Resolve pattern references and rule instances against ratchets.toml,
+    // if there is one
+    let config_path = Path::new("ratchets.toml");
+    let config = if config_path.exists() {
+        Some(Config::load(config_path)?)
+    } else {
+        None
+    };
+    let rule_context = match config {
+        Some(ref config) => RuleContext::new(config.patterns.clone()),
+        None => RuleContext::empty(),
+    };
+
+    // 2. Load every rule, regardless of the enabled set and languages
+    let mut registry = RuleRegistry::load_all(&rule_context)?;
+    if let Some(ref config) = config {
+        registry.load_rule_instances(&config.rules.instances, Some(&rule_context))?;
+    }
+    if let Some(id) = rule_id {
+        let validated = RuleId::new(id)
+            .filter(|id| registry.get_rule(id).is_some())
+            .ok_or_else(|| TestRulesError::Other(format!("Unknown rule '{}'", id)))?;
+        registry.filter_to_single_rule(&validated);
+    }
+
+    // 3. Check the examples
+    Ok(run_rule_tests(&ExecutionEngine::new(registry, None)))
+}
+
+/// Check every rule of `engine` against its examples
+///
+/// Each example is executed as the content of its file name with all of the
+/// engine's rules, and only the violations of the rule under test count.
+pub fn run_rule_tests(engine: &ExecutionEngine) -> TestReport {
+    let detector = LanguageDetector::new();
+    let mut rules: Vec<&dyn Rule> = engine
+        .registry()
+        .iter_rules()
+        .filter(|rule| !rule.tests().is_empty())
+        .collect();
+    rules.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+
+    let mut report = TestReport {
+        rules_tested: rules.len(),
+        ..TestReport::default()
+    };
+    for rule in rules {
+        for (index, test) in rule.tests().iter().enumerate() {
+            let file = FileEntry::new(test.filename.clone(), &detector);
+            let mut violations = engine.execute_content(&file, &test.code);
+            violations.retain(|v| v.rule_id == *rule.id());
+
+            match test.verify(&violations) {
+                Ok(()) => report.passed += 1,
+                Err(reason) => report.failures.push(TestFailure {
+                    rule_id: rule.id().clone(),
+                    index: index + 1,
+                    filename: test.filename.clone(),
+                    reason,
+                }),
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_rule_examples_pass() -> Result<(), Box<dyn std::error::Error>> {
+        let mut registry = RuleRegistry::new();
+        registry.load_embedded_builtin_regex_rules()?;
+        registry.load_embedded_builtin_ast_rules()?;
+        let untested: Vec<String> = registry
+            .iter_rules()
+            .filter(|rule| rule.tests().is_empty())
+            .map(|rule| rule.id().to_string())
+            .collect();
+        assert!(untested.is_empty(), "rules without tests: {untested:?}");
+
+        let report = run_rule_tests(&ExecutionEngine::new(registry, None));
+        assert!(report.failures.is_empty(), "{:#?}", report.failures);
+        assert!(report.passed >= report.rules_tested);
+        Ok(())
+    }
+
+    #[test]
+    fn test_failing_example_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        std::fs::write(
+            temp_dir.path().join("legacy.toml"),
+            r#"
+[rule]
+id = "no-legacy"
+description = "No legacy calls"
+severity = "warning"
+
+[match]
+pattern = "legacy\\("
+languages = ["python"]
+
+[[test]]
+code = "legacy()\nmodern()\nlegacy()\n"
+lines = [1, 3]
+
+[[test]]
+code = "modern()\n"
+filename = "src/app.py"
+expect = 1
+"#,
+        )?;
+        let mut registry = RuleRegistry::new();
+        registry.load_custom_regex_rules(temp_dir.path(), None)?;
+
+        let report = run_rule_tests(&ExecutionEngine::new(registry, None));
+        assert_eq!((report.rules_tested, report.passed), (1, 1));
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].index, 2);
+        assert_eq!(report.failures[0].filename, PathBuf::from("src/app.py"));
+        Ok(())
+    }
+}
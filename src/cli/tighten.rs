@@ -3,6 +3,7 @@
 //! This module implements the `ratchet tighten` command, which:
 //! - Runs check to get current violation counts
 //! - Reduces budgets to match current violations (if lower than budget)
+//! - Does the same for the budgets of violations suppressed by inline comments
 //! - Fails if any violations exceed current budgets
 //! - Supports filtering by rule_id and region
 
@@ -48,6 +49,8 @@ enum TightenError {
 ///
 /// * `rule_id` - Optional specific rule to tighten (tightens all if None)
 /// * `region` - Optional specific region to tighten
+/// * `no_cache` - If true, neither read nor update the result cache
+/// * `count_only` - If true, rules count matches without building violation details
 ///
 /// # Returns
 ///
@@ -55,8 +58,13 @@ enum TightenError {
 /// - 0: Success (including no changes needed)
 /// - 1: Violations exceed budget (can't tighten)
 /// - 2: Error (config error, etc.)
-pub fn run_tighten(rule_id: Option<&str>, region: Option<&str>) -> i32 {
-    match run_tighten_inner(rule_id, region) {
+pub fn run_tighten(
+    rule_id: Option<&str>,
+    region: Option<&str>,
+    no_cache: bool,
+    count_only: bool,
+) -> i32 {
+    match run_tighten_inner(rule_id, region, no_cache, count_only) {
         Ok(TightenResult::Success(count)) => {
             if count == 0 {
                 eprintln!("No budgets needed tightening");
@@ -67,10 +75,15 @@ pub fn run_tighten(rule_id: Option<&str>, region: Option<&str>) -> i32 {
             eprintln!("Error: Cannot tighten while violations exceed budget\n");
             for violation in violations {
                 eprintln!(
-                    "{} in \"{}\": {} violations exceed budget of {}",
+                    "{} in \"{}\": {} {} exceed budget of {}",
                     violation.rule_id.as_str(),
                     violation.region.as_str(),
                     violation.actual_count,
+                    if violation.suppressed {
+                        "suppressed violations"
+                    } else {
+                        "violations"
+                    },
                     violation.budget
                 );
             }
@@ -105,12 +118,19 @@ struct ExceededViolation {
     region: RegionPath,
     actual_count: u64,
     budget: u64,
+    /// Whether the count is of suppressed violations
+    suppressed: bool,
 }
 
+/// A budget entry: rule, region, and whether it budgets suppressed violations
+type BudgetKey = (RuleId, RegionPath, bool);
+
 /// Internal implementation of tighten command
 fn run_tighten_inner(
     rule_id: Option<&str>,
     region: Option<&str>,
+    no_cache: bool,
+    count_only: bool,
 ) -> Result<TightenResult, TightenError> {
     // 1. Validate rule_id if provided
     let rule_id_filter = if let Some(id) = rule_id {
@@ -135,7 +155,7 @@ fn run_tighten_inner(
     let CheckOutcome {
         aggregation_result,
         enabled_rules,
-    } = run_full_check(&config)?;
+    } = run_full_check(&config, no_cache, count_only)?;
 
     // 4. Load existing counts
     let counts_path = Path::new("ratchet-counts.toml");
@@ -149,45 +169,61 @@ fn run_tighten_inner(
     //    statuses. The aggregator only emits a status for a (rule, region) with
     //    at least one violation, so a configured budget with zero current
     //    violations would otherwise never be visited (and never tightened to 0).
+    //    Suppressed-violation budgets are keyed separately and handled alike.
     let mut exceeded_violations = Vec::new();
     let mut tightened_budgets = Vec::new();
 
     // Map each (rule, region) key to its actual violation count from the
     // aggregator. Absence means zero current violations.
-    let actual_counts: HashMap<(RuleId, RegionPath), u64> = aggregation_result
-        .statuses
-        .iter()
-        .map(|status| {
-            (
-                (status.rule_id.clone(), status.region.clone()),
-                status.actual_count,
-            )
-        })
-        .collect();
+    let statuses = aggregation_result.statuses.iter().map(|status| {
+        (
+            (status.rule_id.clone(), status.region.clone(), false),
+            status.actual_count,
+        )
+    });
+    let suppressed = aggregation_result.suppressed.iter().map(|suppressed| {
+        (
+            (suppressed.rule_id.clone(), suppressed.region.clone(), true),
+            suppressed.count,
+        )
+    });
+    let actual_counts: HashMap<BudgetKey, u64> = statuses.chain(suppressed).collect();
 
     // Build the deduplicated set of target keys from both sources. Configured
     // pairs for rules outside the resolved enabled set are orphans: they are
     // skipped here so their dormant budgets are never tightened.
-    let mut target_keys: HashSet<(RuleId, RegionPath)> = HashSet::new();
-    for (rule_id, region) in counts.iter_configured() {
+    let mut target_keys: HashSet<BudgetKey> = HashSet::new();
+    let configured = counts
+        .iter_configured()
+        .map(|(rule_id, region)| (rule_id, region, false))
+        .chain(
+            counts
+                .iter_configured_suppressed()
+                .map(|(rule_id, region)| (rule_id, region, true)),
+        );
+    for (rule_id, region, suppressed) in configured {
         if !enabled_rules.contains(rule_id) {
             continue;
         }
-        target_keys.insert((rule_id.clone(), region.clone()));
+        target_keys.insert((rule_id.clone(), region.clone(), suppressed));
     }
     for key in actual_counts.keys() {
         target_keys.insert(key.clone());
     }
 
-    // Iterate deterministically: sort by rule id, then region.
-    let mut target_keys: Vec<(RuleId, RegionPath)> = target_keys.into_iter().collect();
+    // Iterate deterministically: sort by rule id, then region, violations
+    // before suppressions.
+    let mut target_keys: Vec<BudgetKey> = target_keys.into_iter().collect();
     target_keys.sort_by(|a, b| {
         a.0.as_str()
             .cmp(b.0.as_str())
             .then_with(|| a.1.as_str().cmp(b.1.as_str()))
+            .then_with(|| a.2.cmp(&b.2))
     });
 
-    for (key_rule_id, key_region) in &target_keys {
+    for key in &target_keys {
+        let (key_rule_id, key_region, suppressed) = key;
+
         // Apply filters
         if let Some(ref filter_rule_id) = rule_id_filter
             && key_rule_id != filter_rule_id
@@ -201,11 +237,16 @@ fn run_tighten_inner(
             continue;
         }
 
-        let actual = actual_counts
-            .get(&(key_rule_id.clone(), key_region.clone()))
-            .copied()
-            .unwrap_or(0);
-        let budget = counts.get_budget_by_region(key_rule_id, key_region);
+        let actual = actual_counts.get(key).copied().unwrap_or(0);
+        let budget = if *suppressed {
+            // Unlimited suppressions stay unlimited until bump budgets them
+            match counts.get_suppressed_budget_by_region(key_rule_id, key_region) {
+                Some(budget) => budget,
+                None => continue,
+            }
+        } else {
+            counts.get_budget_by_region(key_rule_id, key_region)
+        };
 
         // Check if we can tighten
         if actual > budget {
@@ -215,11 +256,16 @@ fn run_tighten_inner(
                 region: key_region.clone(),
                 actual_count: actual,
                 budget,
+                suppressed: *suppressed,
             });
         } else if actual < budget {
             // Can tighten - reduce budget to current count
-            tightened_budgets.push((key_rule_id.clone(), key_region.clone(), budget, actual));
-            counts.set_count(key_rule_id, key_region, actual);
+            tightened_budgets.push((key.clone(), budget, actual));
+            if *suppressed {
+                counts.set_suppressed_count(key_rule_id, key_region, actual);
+            } else {
+                counts.set_count(key_rule_id, key_region, actual);
+            }
         }
         // If actual == budget, no change needed
     }
@@ -236,10 +282,11 @@ fn run_tighten_inner(
 
         // 8. Print summary of changes
         eprintln!("Tightening budgets...\n");
-        for (rule_id, region, old_budget, new_budget) in &tightened_budgets {
+        for ((rule_id, region, suppressed), old_budget, new_budget) in &tightened_budgets {
             eprintln!(
-                "Tightened {} in \"{}\": {} -> {}",
+                "Tightened {}{} in \"{}\": {} -> {}",
                 rule_id.as_str(),
+                if *suppressed { " (suppressed)" } else { "" },
                 region.as_str(),
                 old_budget,
                 new_budget
@@ -258,7 +305,11 @@ struct CheckOutcome {
 }
 
 /// Run a full check and return aggregation results
-fn run_full_check(config: &Config) -> Result<CheckOutcome, TightenError> {
+fn run_full_check(
+    config: &Config,
+    no_cache: bool,
+    count_only: bool,
+) -> Result<CheckOutcome, TightenError> {
     // Load counts
     let counts_path = Path::new("ratchet-counts.toml");
     let counts = if counts_path.exists() {
@@ -294,11 +345,16 @@ fn run_full_check(config: &Config) -> Result<CheckOutcome, TightenError> {
 
     // Run execution engine with CountsManager for region resolution
     let engine = ExecutionEngine::new(registry, Some(std::sync::Arc::new(counts.clone())));
-    let execution_result = engine.execute(files);
+    let tally = super::common::execute_rules(
+        &engine,
+        &files,
+        no_cache,
+        super::common::counting_mode(count_only),
+    )?;
 
     // Aggregate violations
     let aggregator = ViolationAggregator::new(counts);
-    let aggregation_result = aggregator.aggregate(execution_result.violations);
+    let aggregation_result = aggregator.aggregate_tally(tally);
 
     Ok(CheckOutcome {
         aggregation_result,
@@ -377,6 +433,7 @@ mod tests {
             region: RegionPath::new("src"),
             actual_count: 5,
             budget: 3,
+            suppressed: false,
         };
 
         assert_eq!(violation.rule_id.as_str(), "no-unwrap");
@@ -401,6 +458,7 @@ mod tests {
             region: RegionPath::new("src"),
             actual_count: 10,
             budget: 5,
+            suppressed: true,
         }]);
         match exceeded {
             TightenResult::ExceededBudget(violations) => {
@@ -0,0 +1,416 @@
+//! Watch command implementation
+//!
+//! This module implements the `ratchets watch` command, which:
+//! - Runs a full check and keeps each file's violations in memory
+//! - Polls the walked tree and re-runs only files that changed, along with
+//!   files whose rules read their directory when a file is added or removed
+//! - Re-runs batch rules, such as external commands, over every file after
+//!   any change
+//! - Reloads everything when configuration, custom rule or set files change
+//! - Redraws the per-(rule, region) budget status after every change
+
+use crate::cli::common::EXIT_ERROR;
+use crate::config::counts::CountsManager;
+use crate::config::ratchet_toml::Config;
+use crate::engine::aggregator::{
+    AggregationResult, TallyMode, ViolationAggregator, ViolationTally,
+};
+use crate::engine::executor::ExecutionEngine;
+use crate::engine::file_walker::FileEntry;
+use crate::error::ConfigError;
+use crate::output::HumanFormatter;
+use crate::rules::{RuleRegistry, Violation};
+use crate::types::{RegionPath, RuleId};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use termcolor::ColorChoice;
+
+/// Configuration files whose edits trigger a full reload
+const CONFIG_FILES: [&str; 2] = ["ratchets.toml", "ratchet-counts.toml"];
+
+/// Custom rule and set directories whose `*.toml` edits trigger a full reload
+const RULE_DIRS: [&str; 7] = [
+    "ratchets/regex",
+    "ratchets/ast",
+    "ratchets/imports",
+    "ratchets/files",
+    "ratchets/external",
+    "ratchets/templates",
+    "ratchets/sets",
+];
+
+/// Error type specific to watch command
+#[derive(Debug, thiserror::Error)]
+pub enum WatchError {
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("Rule error: {0}")]
+    Rule(#[from] crate::error::RuleError),
+
+    #[error("File walker error: {0}")]
+    FileWalker(#[from] crate::engine::file_walker::FileWalkerError),
+}
+
+/// Outcome of a single poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// Nothing changed since the last poll
+    Unchanged,
+    /// This many files were added, modified or removed and re-checked
+    FilesChanged(usize),
+    /// Configuration or rules changed and everything was reloaded
+    Reloaded,
+}
+
+/// Modification time and size of a file, used to detect edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl Stamp {
+    /// Reads the stamp of a file, or None if it does not exist
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// In-memory state of a watch session
+///
+/// Holds the loaded configuration, budgets and rules together with the
+/// violations of every checked file, so a change only re-runs the files
+/// that were edited.
+pub struct Watcher {
+    paths: Vec<String>,
+    config: Config,
+    counts: CountsManager,
+    engine: ExecutionEngine,
+    /// Stamps of the checked files as of their last run
+    files: HashMap<PathBuf, Stamp>,
+    /// Violations and suppressions of each checked file from its last run
+    tallies: HashMap<PathBuf, ViolationTally>,
+    /// Violations and suppressions of the batch rules from their last run
+    batch: ViolationTally,
+    /// Stamps of configuration and custom rule files
+    config_stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+impl Watcher {
+    /// Loads configuration and rules and checks every file under `paths`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration, budgets or rules cannot be
+    /// loaded, or the file walk fails.
+    pub fn new(paths: &[String]) -> Result<Self, WatchError> {
+        let config_stamps = config_stamps();
+        let config = super::common::load_config()?;
+        let counts = super::common::load_counts()?;
+        let registry = super::common::build_registry(&config)?;
+        let engine = ExecutionEngine::new(registry, Some(std::sync::Arc::new(counts.clone())));
+
+        let mut watcher = Self {
+            paths: paths.to_vec(),
+            config,
+            counts,
+            engine,
+            files: HashMap::new(),
+            tallies: HashMap::new(),
+            batch: ViolationTally::new(TallyMode::Detailed),
+            config_stamps,
+        };
+        watcher.refresh_files()?;
+        Ok(watcher)
+    }
+
+    /// Checks for changes and re-runs whatever they affect
+    ///
+    /// Edits to configuration or custom rule files reload everything;
+    /// otherwise only added, modified and removed files are re-checked, and
+    /// batch rules re-run over every file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a reload fails (e.g. ratchets.toml is mid-edit and
+    /// invalid). The previous state is kept, and the reload is retried after
+    /// the next configuration change.
+    pub fn poll(&mut self) -> Result<WatchEvent, WatchError> {
+        let config_stamps = config_stamps();
+        if config_stamps != self.config_stamps {
+            self.config_stamps = config_stamps;
+            *self = Self::new(&self.paths)?;
+            return Ok(WatchEvent::Reloaded);
+        }
+
+        let changed = self.refresh_files()?;
+        if changed == 0 {
+            Ok(WatchEvent::Unchanged)
+        } else {
+            Ok(WatchEvent::FilesChanged(changed))
+        }
+    }
+
+    /// Aggregates the current violations and suppressions against the budgets
+    ///
+    /// The per-file tallies are the ones `ratchets check` builds, so both
+    /// commands report the same result for the same tree.
+    pub fn aggregate(&self) -> AggregationResult {
+        let mut tally = ViolationTally::new(TallyMode::Summary);
+        for file_tally in self.tallies.values().chain([&self.batch]) {
+            tally.merge(file_tally.clone());
+        }
+        ViolationAggregator::new(self.counts.clone()).aggregate_tally(tally)
+    }
+
+    /// Returns the number of files being watched
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns the number of current violations of a rule within a region
+    pub fn region_count(&self, rule_id: &RuleId, region: &RegionPath) -> u64 {
+        self.all_violations()
+            .filter(|v| &v.rule_id == rule_id && &v.region == region)
+            .count() as u64
+    }
+
+    /// Iterates over the violations of every rule, batch rules included
+    fn all_violations(&self) -> impl Iterator<Item = &Violation> {
+        self.tallies
+            .values()
+            .chain([&self.batch])
+            .flat_map(ViolationTally::violations)
+    }
+
+    /// Returns the loaded budgets
+    pub fn counts(&self) -> &CountsManager {
+        &self.counts
+    }
+
+    /// Returns the loaded rules
+    pub fn registry(&self) -> &RuleRegistry {
+        self.engine.registry()
+    }
+
+    /// Re-checks a watched file against unsaved content instead of the disk
+    ///
+    /// The returned violations replace the file's previous ones, so
+    /// [`aggregate`](Self::aggregate) reflects the edit. Batch rules only
+    /// check files on disk, so the file's violations from their last run are
+    /// appended as they are. Returns None if `path` is not a watched file
+    /// (e.g. it is excluded by the configuration).
+    pub fn check_document(&mut self, path: &Path, content: &str) -> Option<Vec<Violation>> {
+        let entry = self.entry(path)?;
+        let tally = self
+            .engine
+            .execute_content_tally(&entry, content, TallyMode::Detailed);
+        let mut violations: Vec<Violation> = tally.violations().cloned().collect();
+        self.tallies.insert(entry.path, tally);
+        violations.extend(
+            self.batch
+                .violations()
+                .filter(|violation| violation.file == path)
+                .cloned(),
+        );
+        Some(violations)
+    }
+
+    /// Re-checks a watched file from disk, discarding unsaved content
+    pub fn revert_document(&mut self, path: &Path) {
+        let Some(entry) = self.entry(path) else {
+            return;
+        };
+        let tally = self.check_file(&entry);
+        self.tallies.insert(entry.path, tally);
+    }
+
+    /// Builds the file entry for a watched file
+    fn entry(&self, path: &Path) -> Option<FileEntry> {
+        if !self.files.contains_key(path) {
+            return None;
+        }
+        let detector = super::common::language_detector(&self.config).ok()?;
+        Some(FileEntry::new(path.to_path_buf(), &detector))
+    }
+
+    /// Walks the tree, re-runs files whose stamps changed and forgets removed files
+    ///
+    /// When a file is added or removed, the other files in its directory are
+    /// re-run too if any rule reads directories. After any change the batch
+    /// rules re-run over every file.
+    ///
+    /// Returns the number of files added, modified or removed.
+    fn refresh_files(&mut self) -> Result<usize, WatchError> {
+        let entries = super::common::discover_files(&self.paths, &self.config)?;
+
+        let mut files = HashMap::with_capacity(entries.len());
+        let mut changed: Vec<FileEntry> = Vec::new();
+        // Directories that gained or lost a file
+        let mut dirs: HashSet<PathBuf> = HashSet::new();
+        for entry in &entries {
+            let Some(stamp) = Stamp::of(&entry.path) else {
+                continue;
+            };
+            match self.files.get(&entry.path) {
+                Some(previous) if previous == &stamp => {}
+                Some(_) => changed.push(entry.clone()),
+                None => {
+                    dirs.extend(entry.path.parent().map(Path::to_path_buf));
+                    changed.push(entry.clone());
+                }
+            }
+            files.insert(entry.path.clone(), stamp);
+        }
+
+        let removed: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter(|path| !files.contains_key(*path))
+            .cloned()
+            .collect();
+        for path in &removed {
+            dirs.extend(path.parent().map(Path::to_path_buf));
+            self.tallies.remove(path);
+        }
+        self.files = files;
+        self.engine.set_walked_files(self.files.keys().cloned());
+
+        let modified = changed.len() + removed.len();
+        if modified == 0 {
+            return Ok(0);
+        }
+
+        if !dirs.is_empty() && self.engine.reads_directories() {
+            let rerun: HashSet<PathBuf> = changed.iter().map(|c| c.path.clone()).collect();
+            let dependents: Vec<FileEntry> = entries
+                .iter()
+                .filter(|entry| {
+                    self.files.contains_key(&entry.path)
+                        && !rerun.contains(&entry.path)
+                        && entry.path.parent().is_some_and(|dir| dirs.contains(dir))
+                })
+                .cloned()
+                .collect();
+            changed.extend(dependents);
+        }
+
+        let tallies: Vec<(PathBuf, ViolationTally)> = changed
+            .par_iter()
+            .map(|entry| (entry.path.clone(), self.check_file(entry)))
+            .collect();
+        self.tallies.extend(tallies);
+
+        let checked: Vec<FileEntry> = entries
+            .into_iter()
+            .filter(|entry| self.files.contains_key(&entry.path))
+            .collect();
+        let mut batch = ViolationTally::new(TallyMode::Detailed);
+        self.engine.execute_batch_rules(&checked, &mut batch)?;
+        self.batch = batch;
+
+        Ok(modified)
+    }
+
+    /// Checks a file from disk, keeping its violations and counting suppressions
+    fn check_file(&self, entry: &FileEntry) -> ViolationTally {
+        self.engine
+            .execute_tally(std::slice::from_ref(entry), None, TallyMode::Detailed)
+    }
+}
+
+/// Collects the stamps of configuration and custom rule files
+fn config_stamps() -> BTreeMap<PathBuf, Stamp> {
+    let mut stamps = BTreeMap::new();
+    for file in CONFIG_FILES {
+        let path = PathBuf::from(file);
+        if let Some(stamp) = Stamp::of(&path) {
+            stamps.insert(path, stamp);
+        }
+    }
+
+    for dir in RULE_DIRS {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "toml")
+                && let Some(stamp) = Stamp::of(&path)
+            {
+                stamps.insert(path, stamp);
+            }
+        }
+    }
+    stamps
+}
+
+/// Run the watch command
+///
+/// Checks every file once, then polls for changes every `interval_ms`
+/// milliseconds and redraws the budget status after each change. Runs until
+/// interrupted.
+///
+/// # Arguments
+///
+/// * `paths` - Paths to watch (defaults to current directory)
+/// * `interval_ms` - Polling interval in milliseconds
+///
+/// # Returns
+///
+/// Exit code 2 if the initial load fails; otherwise does not return.
+pub fn run_watch(paths: &[String], interval_ms: u64) -> i32 {
+    let mut watcher = match Watcher::new(paths) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            // Print the embedded upgrade notice for schema version mismatches
+            // before the generic error printer.
+            if let WatchError::Config(ConfigError::UnsupportedVersion(_)) = &e {
+                super::upgrade_notice::print_to_stderr();
+            }
+            // Render ratchet-set resolution errors before the generic printer.
+            if let WatchError::Rule(crate::error::RuleError::SetResolve(ref resolve)) = e {
+                super::common::print_resolve_error(resolve);
+            }
+            eprintln!("Error: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    redraw(&watcher);
+    loop {
+        std::thread::sleep(Duration::from_millis(interval_ms));
+        match watcher.poll() {
+            Ok(WatchEvent::Unchanged) => {}
+            Ok(_) => redraw(&watcher),
+            Err(e) => eprintln!("Error: {} (keeping previous results)", e),
+        }
+    }
+}
+
+/// Clear the terminal and print the current budget status
+fn redraw(watcher: &Watcher) {
+    let mut stdout = std::io::stdout();
+    if stdout.is_terminal() {
+        // Clear the screen and move the cursor to the top-left corner
+        let _ = write!(stdout, "\x1b[2J\x1b[H");
+        let _ = stdout.flush();
+    }
+
+    let formatter = HumanFormatter::new(ColorChoice::Auto);
+    if let Err(e) = formatter.write_to_stdout(&watcher.aggregate(), false) {
+        eprintln!("Error writing output: {}", e);
+    }
+    eprintln!(
+        "\nWatching {} files for changes. Press Ctrl-C to stop.",
+        watcher.file_count()
+    );
+}
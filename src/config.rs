@@ -6,6 +6,7 @@ pub mod sets;
 
 pub use counts::{CountsManager, RegionTree};
 pub use ratchet_toml::{
-    ColorOption, Config, OutputConfig, OutputFormat, RatchetRef, RuleSettings, RulesConfig,
+    ColorOption, Config, OutputConfig, OutputFormat, RatchetRef, RuleInstance, RuleSettings,
+    RulesConfig,
 };
 pub use sets::{RatchetSet, ResolveError, SetRegistry};
@@ -3,6 +3,18 @@
 //! This module handles the violation budget tracking system. It parses
 //! ratchet-counts.toml, resolves region inheritance, and provides methods
 //! for querying and mutating counts.
+//!
+//! Violations that inline `ratchets-ignore` comments suppress are budgeted
+//! separately, in a `suppressed` sub-table of the rule. A rule without the
+//! sub-table has no limit on suppressions:
+//!
+//! ```toml
+//! [no-unwrap]
+//! "." = 4
+//!
+//! [no-unwrap.suppressed]
+//! "." = 2
+//! ```
 
 use crate::error::ConfigError;
 use crate::types::{RegionPath, RuleId};
@@ -12,12 +24,17 @@ use std::path::Path;
 /// Manages violation budgets for all rules across all regions
 ///
 /// CountsManager stores a mapping from rule IDs to their region trees,
-/// which define the violation budgets for each region.
+/// which define the violation budgets for each region, and a second mapping
+/// for the budgets of suppressed violations.
 #[derive(Debug, Clone)]
 pub struct CountsManager {
     counts: HashMap<RuleId, RegionTree>,
+    suppressed: HashMap<RuleId, RegionTree>,
 }
 
+/// Key of the sub-table holding a rule's suppressed-violation budgets
+const SUPPRESSED_TABLE: &str = "suppressed";
+
 /// Hierarchical storage of violation counts per region for a single rule
 ///
 /// Each rule has a RegionTree that stores:
@@ -236,6 +253,7 @@ impl CountsManager {
     pub fn new() -> Self {
         CountsManager {
             counts: HashMap::new(),
+            suppressed: HashMap::new(),
         }
     }
 
@@ -246,12 +264,16 @@ impl CountsManager {
     /// [rule-id]
     /// "." = 0
     /// "src/legacy" = 15
+    ///
+    /// [rule-id.suppressed]
+    /// "." = 2
     /// ```
     pub fn parse(s: &str) -> Result<Self, ConfigError> {
         // Parse the TOML into a raw map
         let parsed: toml::Value = toml::from_str(s)?;
 
         let mut counts = HashMap::new();
+        let mut suppressed = HashMap::new();
 
         if let toml::Value::Table(table) = parsed {
             for (rule_id_str, value) in table {
@@ -262,38 +284,29 @@ impl CountsManager {
                         message: format!("Invalid rule ID: {}", rule_id_str),
                     })?;
 
-                // Parse the region counts for this rule
-                let mut tree = RegionTree::new();
-
-                if let toml::Value::Table(regions) = value {
-                    for (region_str, count_value) in regions {
-                        let region = RegionPath::new(region_str);
-
-                        let count =
-                            count_value
-                                .as_integer()
-                                .ok_or_else(|| ConfigError::InvalidValue {
-                                    field: format!("{}.{}", rule_id_str, region),
-                                    message: "Count must be a non-negative integer".to_string(),
-                                })?;
-
-                        if count < 0 {
-                            return Err(ConfigError::InvalidValue {
-                                field: format!("{}.{}", rule_id_str, region),
-                                message: "Count must be non-negative".to_string(),
-                            });
-                        }
-
-                        tree.set_count(&region, count as u64);
-                    }
-                } else {
+                let toml::Value::Table(mut regions) = value else {
                     return Err(ConfigError::InvalidValue {
                         field: rule_id_str,
                         message: "Rule section must be a table of region counts".to_string(),
                     });
+                };
+
+                // A `suppressed` sub-table holds the suppressed-violation
+                // budgets; a region of that name would have a count instead
+                if let Some(toml::Value::Table(_)) = regions.get(SUPPRESSED_TABLE)
+                    && let Some(toml::Value::Table(suppressed_regions)) =
+                        regions.remove(SUPPRESSED_TABLE)
+                {
+                    let field = format!("{}.{}", rule_id_str, SUPPRESSED_TABLE);
+                    let tree = parse_region_tree(&field, suppressed_regions)?;
+                    suppressed.insert(rule_id.clone(), tree);
+                    if regions.is_empty() {
+                        continue;
+                    }
                 }
 
-                counts.insert(rule_id, tree);
+                // Parse the region counts for this rule
+                counts.insert(rule_id, parse_region_tree(&rule_id_str, regions)?);
             }
         } else {
             return Err(ConfigError::InvalidSyntax(
@@ -301,7 +314,7 @@ impl CountsManager {
             ));
         }
 
-        Ok(CountsManager { counts })
+        Ok(CountsManager { counts, suppressed })
     }
 
     /// Loads a CountsManager from a file
@@ -377,7 +390,11 @@ impl CountsManager {
     /// Orphans stay dormant (no cleanup); tighten only warns about them so the
     /// count is retained if the rule is re-enabled.
     pub fn iter_rule_ids(&self) -> impl Iterator<Item = &RuleId> {
-        self.counts.keys()
+        self.counts.keys().chain(
+            self.suppressed
+                .keys()
+                .filter(|rule_id| !self.counts.contains_key(*rule_id)),
+        )
     }
 
     /// Iterate over every explicitly configured `(rule, region)` pair.
@@ -395,6 +412,51 @@ impl CountsManager {
         })
     }
 
+    /// Gets the budget for suppressed violations of a rule in a region
+    ///
+    /// Uses the same inheritance logic as `get_budget_by_region()`. If the
+    /// rule has no suppressed budgets, returns `None`: its suppressions are
+    /// unlimited until `bump` or a hand-written table adds a budget.
+    pub fn get_suppressed_budget_by_region(
+        &self,
+        rule_id: &RuleId,
+        region: &RegionPath,
+    ) -> Option<u64> {
+        self.suppressed
+            .get(rule_id)
+            .map(|tree| tree.get_budget_by_region(region))
+    }
+
+    /// Returns true if the given region has a suppressed budget for a rule
+    ///
+    /// If the rule has no suppressed budgets, returns true only for "." (root).
+    pub fn is_configured_suppressed_region(&self, rule_id: &RuleId, region: &RegionPath) -> bool {
+        self.suppressed
+            .get(rule_id)
+            .map(|tree| tree.is_configured(region))
+            .unwrap_or_else(|| region.as_str() == ".")
+    }
+
+    /// Sets the suppressed budget for a specific rule and region
+    pub fn set_suppressed_count(&mut self, rule_id: &RuleId, region: &RegionPath, count: u64) {
+        self.suppressed
+            .entry(rule_id.clone())
+            .or_default()
+            .set_count(region, count);
+    }
+
+    /// Iterate over every `(rule, region)` pair with a suppressed budget.
+    ///
+    /// The suppressed counterpart of `iter_configured()`, used by
+    /// `ratchets tighten` in the same way.
+    pub fn iter_configured_suppressed(&self) -> impl Iterator<Item = (&RuleId, &RegionPath)> {
+        self.suppressed.iter().flat_map(|(rule_id, tree)| {
+            tree.configured_regions
+                .iter()
+                .map(move |region| (rule_id, region))
+        })
+    }
+
     /// Serializes the CountsManager back to TOML format
     ///
     /// Output format matches the input format:
@@ -402,6 +464,9 @@ impl CountsManager {
     /// [rule-id]
     /// "." = 0
     /// "region/path" = 15
+    ///
+    /// [rule-id.suppressed]
+    /// "." = 2
     /// ```
     pub fn to_toml_string(&self) -> String {
         let mut result = String::new();
@@ -412,23 +477,34 @@ impl CountsManager {
         );
 
         // Sort rule IDs for deterministic output
-        let mut rule_ids: Vec<_> = self.counts.keys().collect();
+        let mut rule_ids: Vec<_> = self.iter_rule_ids().collect();
         rule_ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
 
         for rule_id in rule_ids {
-            let tree = &self.counts[rule_id];
-
-            result.push_str(&format!("[{}]\n", rule_id));
-
-            // Collect and sort regions for deterministic output
-            let mut regions: Vec<_> = tree.overrides.iter().collect();
-            regions.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+            let tables = [
+                (self.counts.get(rule_id), rule_id.to_string()),
+                (
+                    self.suppressed.get(rule_id),
+                    format!("{}.{}", rule_id, SUPPRESSED_TABLE),
+                ),
+            ];
+            for (tree, header) in tables {
+                let Some(tree) = tree else {
+                    continue;
+                };
+
+                result.push_str(&format!("[{}]\n", header));
+
+                // Collect and sort regions for deterministic output
+                let mut regions: Vec<_> = tree.overrides.iter().collect();
+                regions.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+
+                for (region, count) in regions {
+                    result.push_str(&format!("\"{}\" = {}\n", region, count));
+                }
 
-            for (region, count) in regions {
-                result.push_str(&format!("\"{}\" = {}\n", region, count));
+                result.push('\n');
             }
-
-            result.push('\n');
         }
 
         // Ensure exactly one trailing newline
@@ -437,6 +513,31 @@ impl CountsManager {
     }
 }
 
+/// Parse a table of region counts, naming `field` in errors
+fn parse_region_tree(field: &str, regions: toml::Table) -> Result<RegionTree, ConfigError> {
+    let mut tree = RegionTree::new();
+    for (region_str, count_value) in regions {
+        let region = RegionPath::new(region_str);
+
+        let count = count_value
+            .as_integer()
+            .ok_or_else(|| ConfigError::InvalidValue {
+                field: format!("{}.{}", field, region),
+                message: "Count must be a non-negative integer".to_string(),
+            })?;
+
+        if count < 0 {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.{}", field, region),
+                message: "Count must be non-negative".to_string(),
+            });
+        }
+
+        tree.set_count(&region, count as u64);
+    }
+    Ok(tree)
+}
+
 impl Default for CountsManager {
     fn default() -> Self {
         Self::new()
@@ -948,6 +1049,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_counts_manager_suppressed_budgets() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[no-unwrap]
+"." = 4
+
+[no-unwrap.suppressed]
+"." = 2
+"src/legacy" = 5
+
+[no-panic.suppressed]
+"." = 3
+        "#;
+
+        let manager = CountsManager::parse(toml)?;
+        let no_unwrap = RuleId::new("no-unwrap").ok_or("invalid rule id")?;
+        let no_panic = RuleId::new("no-panic").ok_or("invalid rule id")?;
+        let legacy = RegionPath::new("src/legacy/db");
+
+        assert_eq!(manager.get_budget_by_region(&no_unwrap, &legacy), 4);
+        assert_eq!(
+            manager.get_suppressed_budget_by_region(&no_unwrap, &legacy),
+            Some(5)
+        );
+        assert_eq!(
+            manager.get_suppressed_budget_by_region(&no_panic, &RegionPath::new("src")),
+            Some(3)
+        );
+        assert!(!manager.counts.contains_key(&no_panic));
+
+        // Rules without a suppressed table have no limit on suppressions
+        let no_todo = RuleId::new("no-todo").ok_or("invalid rule id")?;
+        assert_eq!(
+            manager.get_suppressed_budget_by_region(&no_todo, &legacy),
+            None
+        );
+
+        // Serialization keeps both tables, and rules with only suppressed budgets
+        let reparsed = CountsManager::parse(&manager.to_toml_string())?;
+        assert_eq!(
+            reparsed.get_suppressed_budget_by_region(&no_unwrap, &legacy),
+            Some(5)
+        );
+        assert_eq!(
+            reparsed.get_suppressed_budget_by_region(&no_panic, &RegionPath::new(".")),
+            Some(3)
+        );
+        assert_eq!(reparsed.iter_configured_suppressed().count(), 3);
+        Ok(())
+    }
+
     #[test]
     fn test_region_tree_get_budget_deeply_nested() {
         let mut tree = RegionTree::new();
@@ -29,6 +29,10 @@ pub struct Config {
     #[serde(default)]
     pub output: OutputConfig,
 
+    /// Language detection configuration
+    #[serde(default)]
+    pub languages: LanguagesConfig,
+
     /// Reusable pattern definitions
     #[serde(default)]
     pub patterns: HashMap<String, Vec<GlobPattern>>,
@@ -80,6 +84,15 @@ impl Config {
             ));
         }
 
+        if let Some(language) = self.ratchets.header_language
+            && !matches!(language, Language::C | Language::Cpp)
+        {
+            return Err(ConfigError::Validation(format!(
+                "Invalid header_language {:?}: .h headers are \"c\" or \"cpp\"",
+                language
+            )));
+        }
+
         // Validate glob patterns by attempting to compile them with globset
         for pattern in &self.ratchets.include {
             globset::Glob::new(pattern.as_str()).map_err(|e| {
@@ -134,6 +147,16 @@ impl Config {
             }
         }
 
+        for pattern in self.languages.map.keys() {
+            globset::Glob::new(pattern.as_str()).map_err(|e| {
+                ConfigError::Validation(format!(
+                    "Invalid glob pattern '{}' in [languages.map]: {}",
+                    pattern.as_str(),
+                    e
+                ))
+            })?;
+        }
+
         // Validate pattern definitions
         for (pattern_name, patterns) in &self.patterns {
             for pattern in patterns {
@@ -171,6 +194,21 @@ pub struct RatchetsMeta {
     /// File patterns to exclude
     #[serde(default)]
     pub exclude: Vec<GlobPattern>,
+
+    /// Language of `.h` headers, `c` (the default) or `cpp`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_language: Option<Language>,
+}
+
+/// Language detection section (`[languages]`)
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LanguagesConfig {
+    /// Languages of files matching glob patterns, e.g. `"*.bzl" = "python"`
+    ///
+    /// Entries override detection by extension, shebang and `header_language`.
+    /// If several patterns match a file, the longest pattern wins.
+    #[serde(default)]
+    pub map: HashMap<GlobPattern, Language>,
 }
 
 fn default_include() -> Vec<GlobPattern> {
@@ -190,6 +228,32 @@ pub struct RulesConfig {
     /// Custom rules from `[rules.custom]` section
     #[serde(default)]
     pub custom: HashMap<RuleId, RuleSettings>,
+
+    /// Rules instantiated from templates, from `[[rules.instances]]` entries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instances: Vec<RuleInstance>,
+}
+
+/// A rule instantiated from a rule template
+///
+/// Like any other rule, the instance must be enabled through
+/// `enabled_ratchets` (directly or via a set), and its budgets are keyed by
+/// its `id` in ratchet-counts.toml.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleInstance {
+    /// ID of the template to instantiate
+    pub template: String,
+
+    /// ID of the instantiated rule
+    pub id: RuleId,
+
+    /// Description of the instantiated rule, overriding the template's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Values of the template's parameters
+    #[serde(default)]
+    pub params: HashMap<String, String>,
 }
 
 /// Settings for individual rules
@@ -477,6 +541,62 @@ include = ["[invalid"]
         );
     }
 
+    #[test]
+    fn test_header_language() -> Result<(), Box<dyn std::error::Error>> {
+        let config = Config::parse("[ratchets]\nversion = \"2\"\nlanguages = [\"c\"]\n")?;
+        assert_eq!(config.ratchets.header_language, None);
+
+        let config = Config::parse(
+            "[ratchets]\nversion = \"2\"\nlanguages = [\"cpp\"]\nheader_language = \"cpp\"\n",
+        )?;
+        assert_eq!(config.ratchets.header_language, Some(Language::Cpp));
+
+        let result = Config::parse(
+            "[ratchets]\nversion = \"2\"\nlanguages = [\"c\"]\nheader_language = \"python\"\n",
+        );
+        assert!(
+            result
+                .err()
+                .is_some_and(|e| e.to_string().contains("Invalid header_language"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_languages_map() -> Result<(), Box<dyn std::error::Error>> {
+        let config = Config::parse(
+            r#"
+[ratchets]
+version = "2"
+languages = ["python", "rust"]
+
+[languages.map]
+"*.bzl" = "python"
+"**/generated/*.rs.in" = "rust"
+"#,
+        )?;
+        assert_eq!(config.languages.map.len(), 2);
+        assert_eq!(
+            config.languages.map.get(&GlobPattern::new("*.bzl")),
+            Some(&Language::Python)
+        );
+
+        let result = Config::parse(
+            "[ratchets]\nversion = \"2\"\nlanguages = [\"python\"]\n[languages.map]\n\"[bzl\" = \"python\"\n",
+        );
+        assert!(
+            result
+                .err()
+                .is_some_and(|e| e.to_string().contains("in [languages.map]"))
+        );
+
+        let result = Config::parse(
+            "[ratchets]\nversion = \"2\"\nlanguages = [\"python\"]\n[languages.map]\n\"*.bzl\" = \"starlark\"\n",
+        );
+        assert!(result.is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_invalid_glob_pattern_exclude() {
         let invalid = r#"
@@ -923,17 +1043,32 @@ my-rule = { regions = ["[invalid"] }
         let config_str = r#"
 [ratchets]
 version = "2"
-languages = ["rust", "typescript", "javascript", "python", "go"]
+languages = [
+    "rust", "typescript", "tsx", "javascript", "python", "go", "java", "kotlin", "c", "cpp",
+    "bash",
+]
 "#;
 
         let config = Config::parse(config_str)?;
-        assert_eq!(config.ratchets.languages.len(), 5);
+        assert_eq!(config.ratchets.languages.len(), 11);
         Ok(())
     }
 
     #[test]
     fn test_single_language() -> Result<(), Box<dyn std::error::Error>> {
-        for lang in &["rust", "typescript", "javascript", "python", "go"] {
+        for lang in &[
+            "rust",
+            "typescript",
+            "tsx",
+            "javascript",
+            "python",
+            "go",
+            "java",
+            "kotlin",
+            "c",
+            "cpp",
+            "bash",
+        ] {
             let config_str = format!(
                 r#"
 [ratchets]
@@ -1041,6 +1176,40 @@ languages = ["rust"]
         Ok(())
     }
 
+    #[test]
+    fn test_rule_instances_parsed() -> Result<(), Box<dyn std::error::Error>> {
+        let config_str = r#"
+[ratchets]
+version = "2"
+languages = ["python"]
+
+[rules]
+no-unwrap = { severity = "warning" }
+
+[[rules.instances]]
+template = "python-no-module-import"
+id = "no-pickle"
+description = "pickle is unsafe on untrusted data"
+params = { module = "pickle" }
+"#;
+
+        let config = Config::parse(config_str)?;
+        assert_eq!(config.rules.builtin.len(), 1);
+        assert_eq!(config.rules.instances.len(), 1);
+        let instance = &config.rules.instances[0];
+        assert_eq!(instance.template, "python-no-module-import");
+        assert_eq!(instance.id.as_str(), "no-pickle");
+        assert_eq!(
+            instance.description.as_deref(),
+            Some("pickle is unsafe on untrusted data")
+        );
+        assert_eq!(
+            instance.params.get("module").map(String::as_str),
+            Some("pickle")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_ratchet_ref_invalid_set_id_rejected() {
         // `$` followed by an invalid identifier must fail at parse time.
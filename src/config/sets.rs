@@ -210,6 +210,20 @@ impl SetRegistry {
         self.sets.is_empty()
     }
 
+    /// Load every set: embedded, then filesystem builtin from
+    /// `builtin-ratchets/sets/`, then user-defined from `ratchets/sets/`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuleError`] if any set fails to read or parse.
+    pub fn load_all() -> Result<Self, RuleError> {
+        let mut registry = Self::new();
+        registry.load_embedded_builtin_sets()?;
+        registry.load_builtin_sets(&Path::new("builtin-ratchets").join("sets"))?;
+        registry.load_custom_sets(&Path::new("ratchets").join("sets"))?;
+        Ok(registry)
+    }
+
     /// Load embedded builtin sets via [`crate::rules::load_builtin_sets`].
     ///
     /// # Errors
@@ -332,6 +346,26 @@ impl SetRegistry {
         Ok(resolved)
     }
 
+    /// IDs of the sets that include `rule_id`, directly or through composed
+    /// sets, sorted by ID.
+    ///
+    /// Sets that fail to resolve (unknown references or cycles) are skipped.
+    pub fn sets_including(&self, rule_id: &RuleId) -> Vec<SetId> {
+        let mut ids: Vec<SetId> = self
+            .sets
+            .keys()
+            .filter(|set_id| {
+                let mut rules = HashSet::new();
+                let root = RatchetRef::Set((*set_id).clone());
+                self.dfs_expand(&root, &mut rules, &mut Vec::new()).is_ok()
+                    && rules.contains(rule_id)
+            })
+            .cloned()
+            .collect();
+        ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        ids
+    }
+
     /// DFS helper used by [`SetRegistry::resolve`]. `visiting` is the active
     /// recursion stack; we use a `Vec<SetId>` (rather than a `HashSet`) so the
     /// cycle chain reported back to the user preserves traversal order.
@@ -574,6 +608,26 @@ rules = ["rule-alpha"]
         Ok(())
     }
 
+    #[test]
+    fn sets_including_follows_composition_and_skips_cycles() -> TestResult {
+        let mut registry = SetRegistry::new();
+        registry.insert(make_set("s", vec![rule_ref("a")?, set_ref("t")?])?);
+        registry.insert(make_set("t", vec![rule_ref("b")?])?);
+        registry.insert(make_set("u", vec![rule_ref("b")?, set_ref("u")?])?);
+
+        let ids = |rule: &str| -> Result<Vec<String>, TestError> {
+            Ok(registry
+                .sets_including(&rule_id(rule)?)
+                .iter()
+                .map(|id| id.as_str().to_string())
+                .collect())
+        };
+        assert_eq!(ids("b")?, vec!["s", "t"]);
+        assert_eq!(ids("a")?, vec!["s"]);
+        assert!(ids("c")?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn resolve_two_set_cycle_returns_chain() -> TestResult {
         let mut registry = SetRegistry::new();
@@ -798,7 +852,7 @@ rules = ["a"]
         let set = registry
             .get(&common_starter_id)
             .ok_or("common-starter must be embedded")?;
-        assert_eq!(set.rules().len(), 2);
+        assert_eq!(set.rules().len(), 3);
         Ok(())
     }
 }
@@ -1,8 +1,12 @@
 //! Rule execution engine and violation aggregation
 
 pub mod aggregator;
+pub mod cache;
 pub mod executor;
 pub mod file_walker;
+pub(crate) mod shebang;
+pub mod suppression;
 
-pub use aggregator::{AggregationResult, RuleRegionStatus, ViolationAggregator};
+pub use aggregator::{AggregationResult, RuleRegionStatus, SuppressedCount, ViolationAggregator};
+pub use cache::{CacheError, ResultCache};
 pub use executor::{ExecutionEngine, ExecutionResult};
@@ -5,11 +5,149 @@
 //! This module aggregates violations by (rule_id, region) and compares
 //! actual counts against budgets from the CountsManager to determine
 //! pass/fail status.
+//!
+//! Violations are counted into a [`ViolationTally`] as they are produced, so
+//! only the per-(rule, region) counters need to stay in memory unless the
+//! caller asks to keep the violations for detailed output.
 
 use crate::config::counts::CountsManager;
 use crate::rules::Violation;
 use crate::types::{RegionPath, RuleId};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// How much detail a [`ViolationTally`] keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TallyMode {
+    /// Keep every violation for detailed output
+    Detailed,
+    /// Count violations and drop them once counted
+    Summary,
+    /// Rules count their matches without building violations at all
+    CountOnly,
+}
+
+/// Running per-(rule, region) violation counts
+///
+/// Tallies from parallel workers are combined with [`ViolationTally::merge`]
+/// and turned into an [`AggregationResult`] by
+/// [`ViolationAggregator::aggregate_tally`].
+#[derive(Debug, Clone)]
+pub struct ViolationTally {
+    mode: TallyMode,
+    groups: HashMap<(RuleId, RegionPath), TallyGroup>,
+    /// Violations dropped by inline suppression comments
+    suppressed: HashMap<(RuleId, RegionPath), u64>,
+}
+
+/// Counters for a single (rule, region) pair
+#[derive(Debug, Clone)]
+struct TallyGroup {
+    count: u64,
+    /// A file in the group, used for the budget lookup
+    file: PathBuf,
+    /// The group's violations (only kept in detailed mode)
+    violations: Vec<Violation>,
+}
+
+impl ViolationTally {
+    /// Creates an empty tally
+    pub fn new(mode: TallyMode) -> Self {
+        Self {
+            mode,
+            groups: HashMap::new(),
+            suppressed: HashMap::new(),
+        }
+    }
+
+    /// Returns the tally's mode
+    pub fn mode(&self) -> TallyMode {
+        self.mode
+    }
+
+    /// Counts a violation, keeping it only in detailed mode
+    pub fn add(&mut self, violation: Violation) {
+        let detailed = self.mode == TallyMode::Detailed;
+        let group = self.group(&violation.rule_id, &violation.region, &violation.file);
+        group.count += 1;
+        if detailed {
+            group.violations.push(violation);
+        }
+    }
+
+    /// Adds `count` violations of a rule found in `file` without the violations themselves
+    pub fn add_count(&mut self, rule_id: &RuleId, region: RegionPath, file: &Path, count: u64) {
+        if count > 0 {
+            self.group(rule_id, &region, file).count += count;
+        }
+    }
+
+    /// Counts `count` violations of a rule that inline comments suppressed
+    pub fn add_suppressed(&mut self, rule_id: &RuleId, region: RegionPath, count: u64) {
+        if count > 0 {
+            *self
+                .suppressed
+                .entry((rule_id.clone(), region))
+                .or_insert(0) += count;
+        }
+    }
+
+    /// Folds another tally into this one, keeping `other`'s violations after this one's
+    ///
+    /// Violations are only kept if this tally is in detailed mode.
+    pub fn merge(&mut self, other: ViolationTally) {
+        let detailed = self.mode == TallyMode::Detailed;
+        for (key, mut other_group) in other.groups {
+            if !detailed {
+                other_group.violations.clear();
+            }
+            match self.groups.get_mut(&key) {
+                Some(group) => {
+                    group.count += other_group.count;
+                    if other_group.file < group.file {
+                        group.file = other_group.file;
+                    }
+                    group.violations.extend(other_group.violations);
+                }
+                None => {
+                    self.groups.insert(key, other_group);
+                }
+            }
+        }
+        for (key, count) in other.suppressed {
+            *self.suppressed.entry(key).or_insert(0) += count;
+        }
+    }
+
+    /// Returns the total number of violations counted
+    pub fn total(&self) -> u64 {
+        self.groups.values().map(|g| g.count).sum()
+    }
+
+    /// Iterates over the violations kept in detailed mode
+    pub fn violations(&self) -> impl Iterator<Item = &Violation> {
+        self.groups.values().flat_map(|g| g.violations.iter())
+    }
+
+    /// Returns the group for a (rule, region) pair, creating it if needed
+    ///
+    /// The group's budget file is the smallest path seen so results do not
+    /// depend on the order in which parallel workers finish.
+    fn group(&mut self, rule_id: &RuleId, region: &RegionPath, file: &Path) -> &mut TallyGroup {
+        let group = self
+            .groups
+            .entry((rule_id.clone(), region.clone()))
+            .or_insert_with(|| TallyGroup {
+                count: 0,
+                file: file.to_path_buf(),
+                violations: Vec::new(),
+            });
+        if file < group.file.as_path() {
+            group.file = file.to_path_buf();
+        }
+        group
+    }
+}
 
 /// Result of aggregating violations against budgets
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,6 +160,16 @@ pub struct AggregationResult {
     pub total_violations: usize,
     /// Total violations over budget
     pub violations_over_budget: usize,
+    /// Per-rule/region counts of violations suppressed by inline comments
+    pub suppressed: Vec<SuppressedCount>,
+}
+
+impl AggregationResult {
+    /// Number of (rule, region) pairs whose violations or suppressions exceed their budget
+    pub fn exceeded_count(&self) -> usize {
+        self.statuses.iter().filter(|s| !s.passed).count()
+            + self.suppressed.iter().filter(|s| !s.passed).count()
+    }
 }
 
 /// Status for a single (rule, region) pair
@@ -35,6 +183,17 @@ pub struct RuleRegionStatus {
     pub violations: Vec<Violation>,
 }
 
+/// Number of violations inline comments suppressed for a single (rule, region) pair
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressedCount {
+    pub rule_id: RuleId,
+    pub region: RegionPath,
+    pub count: u64,
+    /// None if the rule has no suppressed budgets, which leaves it unlimited
+    pub budget: Option<u64>,
+    pub passed: bool,
+}
+
 /// Aggregates violations and compares against budgets
 pub struct ViolationAggregator {
     counts: CountsManager,
@@ -53,34 +212,35 @@ impl ViolationAggregator {
     /// 2. For each group, count violations
     /// 3. Look up budget from CountsManager using the first file path in the group
     /// 4. Compare count vs budget: if actual > budget, status is FAIL
-    /// 5. Overall pass = all rule/regions pass
+    /// 5. Compare suppressed counts against their budgets the same way, if the
+    ///    rule has any
+    /// 6. Overall pass = all rule/regions and their suppressions pass
     pub fn aggregate(&self, violations: Vec<Violation>) -> AggregationResult {
-        // Group violations by (rule_id, region)
-        let mut groups: HashMap<(RuleId, RegionPath), Vec<Violation>> = HashMap::new();
-
+        let mut tally = ViolationTally::new(TallyMode::Detailed);
         for violation in violations {
-            let key = (violation.rule_id.clone(), violation.region.clone());
-            groups.entry(key).or_default().push(violation);
+            tally.add(violation);
         }
+        self.aggregate_tally(tally)
+    }
 
+    /// Check a tally of violations against budgets
+    ///
+    /// Statuses carry the tally's violations in detailed mode and no
+    /// violations otherwise.
+    pub fn aggregate_tally(&self, tally: ViolationTally) -> AggregationResult {
         // Calculate status for each group
         let mut statuses = Vec::new();
         let mut total_violations = 0;
         let mut violations_over_budget = 0;
         let mut all_passed = true;
 
-        for ((rule_id, region), group_violations) in groups.into_iter() {
-            let actual_count = group_violations.len() as u64;
+        for ((rule_id, region), group) in tally.groups.into_iter() {
+            let actual_count = group.count;
             total_violations += actual_count as usize;
 
-            // Look up budget using the file path from the first violation
+            // Look up budget using a file path from the group
             // The CountsManager uses the file path for inheritance lookup
-            let budget = if let Some(first_violation) = group_violations.first() {
-                self.counts.get_budget(&rule_id, &first_violation.file)
-            } else {
-                // This shouldn't happen since we only create groups with violations
-                0
-            };
+            let budget = self.counts.get_budget(&rule_id, &group.file);
 
             let passed = actual_count <= budget;
 
@@ -95,7 +255,7 @@ impl ViolationAggregator {
                 actual_count,
                 budget,
                 passed,
-                violations: group_violations,
+                violations: group.violations,
             });
         }
 
@@ -107,11 +267,44 @@ impl ViolationAggregator {
                 .then_with(|| a.region.as_str().cmp(b.region.as_str()))
         });
 
+        // Suppressed violations are checked against their own budgets
+        let mut suppressed: Vec<SuppressedCount> = tally
+            .suppressed
+            .into_iter()
+            .map(|((rule_id, region), count)| {
+                let budget = self
+                    .counts
+                    .get_suppressed_budget_by_region(&rule_id, &region);
+                let passed = match budget {
+                    Some(budget) if count > budget => {
+                        all_passed = false;
+                        violations_over_budget += (count - budget) as usize;
+                        false
+                    }
+                    _ => true,
+                };
+                SuppressedCount {
+                    rule_id,
+                    region,
+                    count,
+                    budget,
+                    passed,
+                }
+            })
+            .collect();
+        suppressed.sort_by(|a, b| {
+            a.rule_id
+                .as_str()
+                .cmp(b.rule_id.as_str())
+                .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+        });
+
         AggregationResult {
             statuses,
             passed: all_passed,
             total_violations,
             violations_over_budget,
+            suppressed,
         }
     }
 }
@@ -582,6 +775,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         // Test clone
@@ -613,4 +807,66 @@ mod tests {
         assert!(debug_str.contains("RuleRegionStatus"));
         Ok(())
     }
+
+    #[test]
+    fn test_tally_summary_drops_violations() -> Result<(), Box<dyn std::error::Error>> {
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").ok_or("invalid rule id")?,
+            &RegionPath::new("src"),
+            1,
+        );
+        let aggregator = ViolationAggregator::new(counts);
+
+        let mut tally = ViolationTally::new(TallyMode::Summary);
+        tally.add(create_test_violation("no-unwrap", "src/a.rs", "src", 1)?);
+        tally.add(create_test_violation("no-unwrap", "src/b.rs", "src", 2)?);
+        assert_eq!(tally.total(), 2);
+
+        let result = aggregator.aggregate_tally(tally);
+        assert!(!result.passed);
+        assert_eq!(result.total_violations, 2);
+        assert_eq!(result.violations_over_budget, 1);
+        assert_eq!(result.statuses.len(), 1);
+        assert_eq!(result.statuses[0].actual_count, 2);
+        assert!(result.statuses[0].violations.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tally_merge_matches_aggregate() -> Result<(), Box<dyn std::error::Error>> {
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/a.rs", "src", 1)?,
+            create_test_violation("no-unwrap", "src/b.rs", "src", 2)?,
+            create_test_violation("no-hack", "tests/a.rs", "tests", 3)?,
+        ];
+        let aggregator = ViolationAggregator::new(CountsManager::new());
+        let expected = aggregator.aggregate(violations.clone());
+
+        // Split across two partial tallies, one of them count-only
+        let mut first = ViolationTally::new(TallyMode::Detailed);
+        first.add(violations[0].clone());
+        let mut second = ViolationTally::new(TallyMode::Detailed);
+        second.add(violations[1].clone());
+        second.add_count(
+            &violations[2].rule_id,
+            violations[2].region.clone(),
+            &violations[2].file,
+            1,
+        );
+        first.merge(second);
+
+        let result = aggregator.aggregate_tally(first);
+        assert_eq!(result.passed, expected.passed);
+        assert_eq!(result.total_violations, expected.total_violations);
+        assert_eq!(result.statuses.len(), expected.statuses.len());
+        for (status, expected) in result.statuses.iter().zip(&expected.statuses) {
+            assert_eq!(status.rule_id, expected.rule_id);
+            assert_eq!(status.region, expected.region);
+            assert_eq!(status.actual_count, expected.actual_count);
+        }
+        // Violations added in detailed mode are kept in order
+        assert_eq!(result.statuses[1].violations, violations[..2]);
+        Ok(())
+    }
 }
@@ -0,0 +1,529 @@
+#![forbid(unsafe_code)]
+
+//! Persistent result cache for incremental checking
+//!
+//! This module stores the violations each rule produced for each file, keyed by
+//! the file's content hash and the rule's definition hash. Runs that only report
+//! counts store just the number of violations, so summary runs over large trees
+//! do not keep every snippet and message. The whole cache is
+//! discarded when the ratchets version changes. Regions are not cached; they are
+//! resolved again on every run because ratchet-counts.toml may have changed.
+
+use crate::rules::{StableHasher, Violation};
+use crate::types::{Language, RegionPath, RuleId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+/// Default cache directory, relative to the repository root
+pub const CACHE_DIR: &str = ".ratchets-cache";
+
+/// File inside the cache directory holding cached results
+const RESULTS_FILE: &str = "results.json";
+
+/// Errors that can occur when loading or saving the cache
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid cache file: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// A violation as stored in the cache
+///
+/// The rule ID, file, and region are implied by where the entry is stored or
+/// recomputed when the entry is reused.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedViolation {
+    line: u32,
+    column: u32,
+    end_line: u32,
+    end_column: u32,
+    snippet: String,
+    message: String,
+}
+
+/// Cached results from one rule for one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RuleEntry {
+    definition_hash: u64,
+    results: CachedResults,
+}
+
+/// What a run kept of a rule's results: the violations, or only their number
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CachedResults {
+    Violations(Vec<CachedViolation>),
+    Count(usize),
+}
+
+/// Cached results for a single file
+///
+/// Entries are only valid for the content they were computed from, so the
+/// whole entry is replaced when the content hash changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedFile {
+    content_hash: u64,
+    rules: HashMap<String, RuleEntry>,
+}
+
+impl CachedFile {
+    /// Creates an empty entry for content with the given hash
+    pub fn new(content_hash: u64) -> Self {
+        Self {
+            content_hash,
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Records the violations a rule produced for this file
+    pub fn insert(&mut self, rule_id: &RuleId, definition_hash: u64, violations: &[Violation]) {
+        let violations = violations
+            .iter()
+            .map(|v| CachedViolation {
+                line: v.line,
+                column: v.column,
+                end_line: v.end_line,
+                end_column: v.end_column,
+                snippet: v.snippet.clone(),
+                message: v.message.clone(),
+            })
+            .collect();
+        self.insert_results(
+            rule_id,
+            definition_hash,
+            CachedResults::Violations(violations),
+        );
+    }
+
+    /// Records only how many violations a rule produced for this file
+    ///
+    /// Later runs can reuse the count, but runs that need the violations
+    /// themselves run the rule again.
+    pub fn insert_count(&mut self, rule_id: &RuleId, definition_hash: u64, count: usize) {
+        self.insert_results(rule_id, definition_hash, CachedResults::Count(count));
+    }
+
+    /// Records a rule's results, replacing any previous entry for the rule
+    fn insert_results(&mut self, rule_id: &RuleId, definition_hash: u64, results: CachedResults) {
+        self.rules.insert(
+            rule_id.as_str().to_string(),
+            RuleEntry {
+                definition_hash,
+                results,
+            },
+        );
+    }
+
+    /// Returns cached violations for a rule, if its definition is unchanged
+    ///
+    /// The returned violations have their region set to `region`. Returns None
+    /// if only the rule's violation count was recorded.
+    pub fn violations(
+        &self,
+        path: &Path,
+        rule_id: &RuleId,
+        definition_hash: u64,
+        region: &RegionPath,
+    ) -> Option<Vec<Violation>> {
+        let CachedResults::Violations(violations) =
+            &self.rule_entry(rule_id, definition_hash)?.results
+        else {
+            return None;
+        };
+
+        Some(
+            violations
+                .iter()
+                .map(|v| Violation {
+                    rule_id: rule_id.clone(),
+                    file: path.to_path_buf(),
+                    line: v.line,
+                    column: v.column,
+                    end_line: v.end_line,
+                    end_column: v.end_column,
+                    snippet: v.snippet.clone(),
+                    message: v.message.clone(),
+                    region: region.clone(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the cached violation count for a rule, if its definition is unchanged
+    pub fn count(&self, rule_id: &RuleId, definition_hash: u64) -> Option<usize> {
+        match &self.rule_entry(rule_id, definition_hash)?.results {
+            CachedResults::Violations(violations) => Some(violations.len()),
+            CachedResults::Count(count) => Some(*count),
+        }
+    }
+
+    /// Returns the entry for a rule if it was recorded with the given definition
+    fn rule_entry(&self, rule_id: &RuleId, definition_hash: u64) -> Option<&RuleEntry> {
+        self.rules
+            .get(rule_id.as_str())
+            .filter(|e| e.definition_hash == definition_hash)
+    }
+}
+
+/// On-disk representation of the cache
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile {
+    version: String,
+    files: HashMap<String, CachedFile>,
+}
+
+/// Cache of rule results keyed by file content and rule definition
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    files: HashMap<String, CachedFile>,
+}
+
+impl ResultCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the cache from the given directory
+    ///
+    /// Returns an empty cache if the directory has no cache file or if the file
+    /// was written by a different ratchets version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache file exists but cannot be read or parsed.
+    pub fn load(dir: &Path) -> Result<Self, CacheError> {
+        let path = dir.join(RESULTS_FILE);
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let file: CacheFile = serde_json::from_str(&content)?;
+        if file.version != env!("CARGO_PKG_VERSION") {
+            return Ok(Self::new());
+        }
+
+        Ok(Self { files: file.files })
+    }
+
+    /// Saves the cache to the given directory, creating it if needed
+    ///
+    /// Entries for files that no longer exist are dropped. The directory gets
+    /// its own `.gitignore` so the cache is never committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or cache file cannot be written.
+    pub fn save(mut self, dir: &Path) -> Result<(), CacheError> {
+        self.files.retain(|path, _| Path::new(path).exists());
+
+        std::fs::create_dir_all(dir)?;
+        let gitignore = dir.join(".gitignore");
+        if !gitignore.exists() {
+            std::fs::write(&gitignore, "*\n")?;
+        }
+
+        let file = CacheFile {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            files: self.files,
+        };
+        let content = serde_json::to_string(&file)?;
+
+        // Write to a uniquely named temporary file and rename it, so
+        // concurrent runs never write to the same file or see a partially
+        // written cache.
+        let mut tmp = NamedTempFile::new_in(dir)?;
+        tmp.write_all(content.as_bytes())?;
+        tmp.persist(dir.join(RESULTS_FILE))
+            .map_err(|e| CacheError::Io(e.error))?;
+        Ok(())
+    }
+
+    /// Removes the cache directory and everything in it
+    ///
+    /// Returns true if a cache directory was removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory exists but cannot be removed.
+    pub fn clean(dir: &Path) -> Result<bool, CacheError> {
+        if !dir.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_dir_all(dir)?;
+        Ok(true)
+    }
+
+    /// Returns the cached entry for a file if its content is unchanged
+    pub fn get(&self, path: &Path, content_hash: u64) -> Option<&CachedFile> {
+        self.files
+            .get(path.to_string_lossy().as_ref())
+            .filter(|entry| entry.content_hash == content_hash)
+    }
+
+    /// Stores the entry for a file, replacing any previous entry
+    pub fn insert(&mut self, path: &Path, entry: CachedFile) {
+        self.files.insert(path.to_string_lossy().to_string(), entry);
+    }
+
+    /// Returns the number of files with cached results
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns true if no files have cached results
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Hash file content and its detected language for cache lookups
+///
+/// The language decides which rules apply and how the file is parsed, so a
+/// file whose detected language changes is checked again.
+pub fn content_hash(content: &str, language: Option<Language>) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_str(&language.map(|l| format!("{:?}", l)).unwrap_or_default());
+    hasher.write_str(content);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn violation(rule_id: &RuleId, file: &Path) -> Violation {
+        Violation {
+            rule_id: rule_id.clone(),
+            file: file.to_path_buf(),
+            line: 1,
+            column: 4,
+            end_line: 1,
+            end_column: 8,
+            snippet: "HACK".to_string(),
+            message: "No HACK comments".to_string(),
+            region: RegionPath::new("src"),
+        }
+    }
+
+    #[test]
+    fn test_content_hash_differs_for_different_content() {
+        let rust = Some(Language::Rust);
+        assert_eq!(
+            content_hash("fn main() {}", rust),
+            content_hash("fn main() {}", rust)
+        );
+        assert_ne!(
+            content_hash("fn main() {}", rust),
+            content_hash("fn main() { }", rust)
+        );
+    }
+
+    #[test]
+    fn test_content_hash_differs_for_different_languages() {
+        let content = "echo hi\n";
+        assert_ne!(
+            content_hash(content, Some(Language::Bash)),
+            content_hash(content, None)
+        );
+        assert_ne!(
+            content_hash(content, Some(Language::TypeScript)),
+            content_hash(content, Some(Language::Tsx))
+        );
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        // Cache files are read by later builds, so the hash must not change
+        assert_eq!(
+            content_hash("fn main() {}", Some(Language::Rust)),
+            8802225481016952550
+        );
+    }
+
+    #[test]
+    fn test_get_requires_matching_content_hash() {
+        let path = Path::new("src/main.rs");
+        let mut cache = ResultCache::new();
+        cache.insert(path, CachedFile::new(42));
+
+        assert!(cache.get(path, 42).is_some());
+        assert!(cache.get(path, 43).is_none());
+        assert!(cache.get(Path::new("src/lib.rs"), 42).is_none());
+    }
+
+    #[test]
+    fn test_violations_require_matching_definition_hash() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let rule_id = RuleId::new("no-hack").ok_or("invalid rule id")?;
+        let path = PathBuf::from("src/main.rs");
+        let mut entry = CachedFile::new(1);
+        entry.insert(&rule_id, 7, &[violation(&rule_id, &path)]);
+
+        let region = RegionPath::new("src/legacy");
+        let cached = entry
+            .violations(&path, &rule_id, 7, &region)
+            .ok_or("expected cached violations")?;
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].snippet, "HACK");
+        assert_eq!(cached[0].file, path);
+        assert_eq!(cached[0].region, region);
+
+        assert!(entry.violations(&path, &rule_id, 8, &region).is_none());
+        let other = RuleId::new("no-xxx").ok_or("invalid rule id")?;
+        assert!(entry.violations(&path, &other, 7, &region).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_entries_have_no_violations() -> Result<(), Box<dyn std::error::Error>> {
+        let rule_id = RuleId::new("no-hack").ok_or("invalid rule id")?;
+        let path = PathBuf::from("src/main.rs");
+        let mut entry = CachedFile::new(1);
+        entry.insert_count(&rule_id, 7, 3);
+
+        assert_eq!(entry.count(&rule_id, 7), Some(3));
+        assert_eq!(entry.count(&rule_id, 8), None);
+        let region = RegionPath::new("src");
+        assert!(entry.violations(&path, &rule_id, 7, &region).is_none());
+
+        // Recording the violations replaces the count
+        entry.insert(&rule_id, 7, &[violation(&rule_id, &path)]);
+        assert_eq!(entry.count(&rule_id, 7), Some(1));
+        assert!(entry.violations(&path, &rule_id, 7, &region).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("main.rs");
+        std::fs::write(&source, "// HACK\n")?;
+        let cache_dir = temp_dir.path().join(CACHE_DIR);
+
+        let rule_id = RuleId::new("no-hack").ok_or("invalid rule id")?;
+        let mut entry = CachedFile::new(5);
+        entry.insert(&rule_id, 9, &[violation(&rule_id, &source)]);
+
+        let mut cache = ResultCache::new();
+        cache.insert(&source, entry.clone());
+        cache.save(&cache_dir)?;
+
+        assert!(cache_dir.join(".gitignore").exists());
+
+        let loaded = ResultCache::load(&cache_dir)?;
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get(&source, 5), Some(&entry));
+        Ok(())
+    }
+
+    #[test]
+    fn test_concurrent_saves_leave_a_complete_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let cache_dir = temp_dir.path().join(CACHE_DIR);
+        let rule_id = RuleId::new("no-hack").ok_or("invalid rule id")?;
+
+        std::thread::scope(|scope| -> Result<(), Box<dyn std::error::Error>> {
+            let handles: Vec<_> = (0..8)
+                .map(|run| {
+                    let (temp_dir, cache_dir, rule_id) = (&temp_dir, &cache_dir, &rule_id);
+                    scope.spawn(move || -> Result<(), CacheError> {
+                        let source = temp_dir.path().join(format!("file{}.rs", run));
+                        std::fs::write(&source, "// HACK\n")?;
+                        let mut entry = CachedFile::new(run);
+                        entry.insert(rule_id, 9, &[violation(rule_id, &source)]);
+                        let mut cache = ResultCache::new();
+                        cache.insert(&source, entry);
+                        cache.save(cache_dir)
+                    })
+                })
+                .collect();
+            for handle in handles {
+                handle.join().map_err(|_| "save thread failed")??;
+            }
+            Ok(())
+        })?;
+
+        // One run's cache wins, and no temporary files are left behind
+        assert_eq!(ResultCache::load(&cache_dir)?.len(), 1);
+        let mut names: Vec<String> = std::fs::read_dir(&cache_dir)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().to_string()))
+            .collect::<Result<_, std::io::Error>>()?;
+        names.sort();
+        assert_eq!(names, [".gitignore", RESULTS_FILE]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_drops_missing_files() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let cache_dir = temp_dir.path().join(CACHE_DIR);
+
+        let mut cache = ResultCache::new();
+        cache.insert(&temp_dir.path().join("deleted.rs"), CachedFile::new(1));
+        cache.save(&cache_dir)?;
+
+        assert!(ResultCache::load(&cache_dir)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_missing_cache_is_empty() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let cache = ResultCache::load(&temp_dir.path().join(CACHE_DIR))?;
+        assert!(cache.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_discards_other_versions() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("main.rs");
+        std::fs::write(&source, "fn main() {}\n")?;
+        std::fs::write(
+            temp_dir.path().join(RESULTS_FILE),
+            format!(
+                r#"{{"version":"0.0.0-old","files":{{{:?}:{{"content_hash":1,"rules":{{}}}}}}}}"#,
+                source.to_string_lossy()
+            ),
+        )?;
+
+        let cache = ResultCache::load(temp_dir.path())?;
+        assert!(cache.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_invalid_cache_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        std::fs::write(temp_dir.path().join(RESULTS_FILE), "not json")?;
+
+        let result = ResultCache::load(temp_dir.path());
+        assert!(matches!(result, Err(CacheError::Serialization(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_clean_removes_directory() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let cache_dir = temp_dir.path().join(CACHE_DIR);
+
+        assert!(!ResultCache::clean(&cache_dir)?);
+
+        ResultCache::new().save(&cache_dir)?;
+        assert!(cache_dir.exists());
+        assert!(ResultCache::clean(&cache_dir)?);
+        assert!(!cache_dir.exists());
+        Ok(())
+    }
+}
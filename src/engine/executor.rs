@@ -6,13 +6,22 @@
 //! to execute rules against discovered files in parallel using rayon.
 
 use crate::config::counts::CountsManager;
+use crate::engine::aggregator::{TallyMode, ViolationTally};
+use crate::engine::cache::{self, CachedFile, ResultCache};
 use crate::engine::file_walker::FileEntry;
+use crate::engine::suppression::Suppressions;
+use crate::error::RuleError;
 use crate::rules::{
-    AstRule, ExecutionContext, ParserCache, RegionResolver, Rule, RuleRegistry, Violation,
+    ExecutionContext, Fix, ParserCache, RegionResolver, Rule, RuleKind, RuleRegistry, Violation,
+    compute_line_offsets, normalize_for_glob_match,
 };
-use crate::types::Language;
+use crate::types::{Language, RegionPath, RuleId};
 use rayon::prelude::*;
+use regex::RegexSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Result of executing all rules against all files
@@ -32,11 +41,57 @@ pub struct ExecutionResult {
 /// - Executes all enabled rules against discovered files
 /// - Uses rayon for parallel file processing
 /// - Parses ASTs once per file and shares across applicable rules
+/// - Scans each file once with a combined RegexSet to skip non-matching regex rules
+/// - Drops violations suppressed by inline `ratchets-ignore` comments
 /// - Collects violations from all rules
+///
+/// Batch rules, such as external commands, check every file at once and run
+/// only through [`execute_batch_rules`](Self::execute_batch_rules).
 pub struct ExecutionEngine {
     registry: Arc<RuleRegistry>,
     parser_cache: Arc<ParserCache>,
     region_resolver: Option<RegionResolver>,
+    prefilters: HashMap<Language, RegexPrefilter>,
+    /// Paths of every walked file, when set by the caller
+    walked_files: Option<HashSet<PathBuf>>,
+}
+
+/// Combined pattern set over the regex rules that apply to one language
+///
+/// A single pass of `set` over a file tells which regex rules can match at
+/// all; only those run their full search.
+struct RegexPrefilter {
+    set: RegexSet,
+    /// Index of each rule's pattern within `set`
+    indices: HashMap<RuleId, usize>,
+}
+
+impl RegexPrefilter {
+    /// Build the prefilter for the regex rules in `registry` that apply to `language`
+    ///
+    /// Returns None if no regex rule applies or the combined set fails to compile
+    /// (e.g. it exceeds the regex size limit); the rules then run unfiltered.
+    fn build(registry: &RuleRegistry, language: Language) -> Option<Self> {
+        let mut patterns = Vec::new();
+        let mut indices = HashMap::new();
+        for rule in registry.iter_rules() {
+            let languages = rule.languages();
+            if !languages.is_empty() && !languages.iter().any(|l| l.includes(language)) {
+                continue;
+            }
+            if let Some(regex) = rule.regex() {
+                indices.insert(rule.id().clone(), patterns.len());
+                patterns.push(regex.as_str());
+            }
+        }
+
+        if patterns.is_empty() {
+            return None;
+        }
+
+        let set = RegexSet::new(patterns).ok()?;
+        Some(Self { set, indices })
+    }
 }
 
 impl ExecutionEngine {
@@ -56,13 +111,53 @@ impl ExecutionEngine {
             ) as RegionResolver
         });
 
+        let prefilters = Language::all()
+            .filter_map(|lang| Some((lang, RegexPrefilter::build(&registry, lang)?)))
+            .collect();
+
         Self {
             registry: Arc::new(registry),
             parser_cache: Arc::new(ParserCache::new()),
             region_resolver,
+            prefilters,
+            walked_files: None,
         }
     }
 
+    /// Sets the paths of every walked file
+    ///
+    /// Rules that read directories look up other files among these. Callers
+    /// that check only some files at a time, such as `watch` or `check` with
+    /// explicit paths, set them to a walk of the whole tree; otherwise the
+    /// files passed to each run are used. A leading `./` is dropped, so the
+    /// files match however the walk spelled them.
+    pub fn set_walked_files(&mut self, files: impl IntoIterator<Item = PathBuf>) {
+        self.walked_files = Some(
+            files
+                .into_iter()
+                .map(|path| normalize_for_glob_match(&path).into_owned())
+                .collect(),
+        );
+    }
+
+    /// The walked files rules that read directories look up during a run over `files`
+    ///
+    /// None if no rule reads directories.
+    fn walked_files<'a>(&'a self, files: &[FileEntry]) -> Option<Cow<'a, HashSet<PathBuf>>> {
+        if !self.reads_directories() {
+            return None;
+        }
+        Some(match self.walked_files {
+            Some(ref walked) => Cow::Borrowed(walked),
+            None => Cow::Owned(
+                files
+                    .iter()
+                    .map(|file| normalize_for_glob_match(&file.path).into_owned())
+                    .collect(),
+            ),
+        })
+    }
+
     /// Execute all rules against the discovered files
     ///
     /// This method processes files in parallel using rayon, parsing ASTs
@@ -78,11 +173,16 @@ impl ExecutionEngine {
     pub fn execute(&self, files: Vec<FileEntry>) -> ExecutionResult {
         let files_checked = files.len();
         let rules_executed = self.registry.len();
+        let walked = self.walked_files(&files);
 
         // Process files in parallel
         let violations: Vec<Violation> = files
             .par_iter()
-            .flat_map(|file| self.execute_file(file))
+            .flat_map(|file| {
+                let mut violations = Vec::new();
+                self.execute_file(file, None, walked.as_deref(), &mut violations);
+                violations
+            })
             .collect();
 
         ExecutionResult {
@@ -92,15 +192,291 @@ impl ExecutionEngine {
         }
     }
 
-    /// Execute all applicable rules against a single file
+    /// Execute all rules against the discovered files, reusing cached results
     ///
-    /// This method:
-    /// 1. Reads the file content
-    /// 2. Determines which rules apply (based on language and file path)
-    /// 3. Parses AST if any AST rules apply
-    /// 4. Executes all applicable rules
-    /// 5. Collects violations
-    fn execute_file(&self, file: &FileEntry) -> Vec<Violation> {
+    /// A rule is skipped for a file when the cache holds its results for the
+    /// file's current content and the rule's current definition. Fresh results
+    /// are written back into `cache` so the caller can persist them.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - Vector of discovered file entries to check
+    /// * `cache` - Results from previous runs, updated in place
+    ///
+    /// # Returns
+    ///
+    /// ExecutionResult containing all violations and execution statistics
+    pub fn execute_with_cache(
+        &self,
+        files: Vec<FileEntry>,
+        cache: &mut ResultCache,
+    ) -> ExecutionResult {
+        let files_checked = files.len();
+        let rules_executed = self.registry.len();
+        let walked = self.walked_files(&files);
+
+        // Process files in parallel against a read-only view of the cache
+        let results: Vec<(Vec<Violation>, Option<CachedFile>)> = {
+            let cache = &*cache;
+            files
+                .par_iter()
+                .map(|file| {
+                    let mut violations = Vec::new();
+                    let entry =
+                        self.execute_file(file, Some(cache), walked.as_deref(), &mut violations);
+                    (violations, entry)
+                })
+                .collect()
+        };
+
+        let mut violations = Vec::new();
+        for (file, (file_violations, entry)) in files.iter().zip(results) {
+            if let Some(entry) = entry {
+                cache.insert(&file.path, entry);
+            }
+            violations.extend(file_violations);
+        }
+
+        ExecutionResult {
+            violations,
+            files_checked,
+            rules_executed,
+        }
+    }
+
+    /// Execute all rules and count the results into a tally as files finish
+    ///
+    /// Unlike [`execute`](Self::execute), violations are never collected into
+    /// one list: each file's results are folded into a per-worker tally, so
+    /// memory is bounded by what the tally keeps (see [`TallyMode`]). In
+    /// count-only mode rules count their matches without building
+    /// violations. Outside detailed mode the cache stores only how many
+    /// violations each rule found.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - Discovered file entries to check
+    /// * `cache` - Results from previous runs, updated in place
+    /// * `mode` - How much detail the returned tally keeps
+    pub fn execute_tally(
+        &self,
+        files: &[FileEntry],
+        cache: Option<&mut ResultCache>,
+        mode: TallyMode,
+    ) -> ViolationTally {
+        let walked = self.walked_files(files);
+        let walked = walked.as_deref();
+        let Some(cache) = cache else {
+            return files
+                .par_iter()
+                .fold(
+                    || ViolationTally::new(mode),
+                    |mut tally, file| {
+                        self.execute_file(file, None, walked, &mut tally);
+                        tally
+                    },
+                )
+                .reduce(|| ViolationTally::new(mode), merge_tallies);
+        };
+
+        let (tally, entries) = {
+            let read_only = &*cache;
+            files
+                .par_iter()
+                .fold(
+                    || (ViolationTally::new(mode), Vec::new()),
+                    |(mut tally, mut entries), file| {
+                        if let Some(entry) =
+                            self.execute_file(file, Some(read_only), walked, &mut tally)
+                        {
+                            entries.push((file, entry));
+                        }
+                        (tally, entries)
+                    },
+                )
+                .reduce(
+                    || (ViolationTally::new(mode), Vec::new()),
+                    |(a, mut a_entries), (b, b_entries)| {
+                        a_entries.extend(b_entries);
+                        (merge_tallies(a, b), a_entries)
+                    },
+                )
+        };
+
+        for (file, entry) in entries {
+            cache.insert(&file.path, entry);
+        }
+        tally
+    }
+
+    /// Execute the batch rules against the discovered files, counting into `tally`
+    ///
+    /// Each batch rule runs once, over the files it applies to by language.
+    /// Its violations are assigned their configured regions, and those
+    /// suppressed by inline comments are counted separately, as for rules
+    /// checked file by file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first batch rule that fails.
+    pub fn execute_batch_rules(
+        &self,
+        files: &[FileEntry],
+        tally: &mut ViolationTally,
+    ) -> Result<(), RuleError> {
+        self.run_batch_rules(files, tally)
+    }
+
+    /// Returns true if any rule's results depend on other files in a file's directory
+    pub fn reads_directories(&self) -> bool {
+        self.registry
+            .iter_rules()
+            .any(|rule| rule.reads_directory())
+    }
+
+    /// Run every batch rule once and pass their violations to `sink`
+    fn run_batch_rules(
+        &self,
+        files: &[FileEntry],
+        sink: &mut impl ViolationSink,
+    ) -> Result<(), RuleError> {
+        let mut violations = Vec::new();
+        for rule in self.registry.iter_rules().filter(|rule| rule.is_batch()) {
+            let rule_files: Vec<&Path> = files
+                .iter()
+                .filter(|file| self.rule_applies_to_file(rule, file))
+                .map(|file| file.path.as_path())
+                .collect();
+            if !rule_files.is_empty() {
+                violations.extend(rule.execute_batch(&rule_files)?);
+            }
+        }
+
+        // Suppression comments are read from the files that have violations
+        let indices: HashMap<&Path, usize> = files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| (file.path.as_path(), index))
+            .collect();
+        let mut by_file: HashMap<usize, Vec<Violation>> = HashMap::new();
+        for mut violation in violations {
+            if let Some(ref resolver) = self.region_resolver {
+                violation.region = resolver(&violation.file, &violation.rule_id);
+            }
+            match indices.get(violation.file.as_path()) {
+                Some(&index) => by_file.entry(index).or_default().push(violation),
+                None => sink.record_violations(vec![violation]),
+            }
+        }
+        for (index, violations) in by_file {
+            let file = &files[index];
+            let suppressions = fs::read_to_string(&file.path)
+                .map(|content| self.suppressions(file, &content, None))
+                .unwrap_or_default();
+            record_unsuppressed(sink, &suppressions, violations);
+        }
+        Ok(())
+    }
+
+    /// Execute all rules against in-memory content for a single file
+    ///
+    /// Used for unsaved editor buffers: `content` is checked as if it were
+    /// the contents of `file`, and the file on disk is never read.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file the content belongs to
+    /// * `content` - Text to check in place of the file's contents
+    pub fn execute_content(&self, file: &FileEntry, content: &str) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.execute_source(
+            file,
+            content,
+            None,
+            self.walked_files.as_ref(),
+            &mut violations,
+        );
+        violations
+    }
+
+    /// Execute all rules against in-memory content, counting into a tally
+    ///
+    /// As [`execute_content`](Self::execute_content), but violations that
+    /// inline comments suppress are counted rather than dropped.
+    pub fn execute_content_tally(
+        &self,
+        file: &FileEntry,
+        content: &str,
+        mode: TallyMode,
+    ) -> ViolationTally {
+        let mut tally = ViolationTally::new(mode);
+        self.execute_source(file, content, None, self.walked_files.as_ref(), &mut tally);
+        tally
+    }
+
+    /// Compute the fixes of every applicable rule against a single file's content
+    ///
+    /// Only rules with a fix run. The file is parsed at most once, if any of
+    /// them reads the syntax tree. Fixes starting on a line where an inline
+    /// comment suppresses their rule are dropped, as their violations would
+    /// be. Fixes are returned in no particular order and may overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file the content belongs to
+    /// * `content` - The file's current contents
+    pub fn fix_content(&self, file: &FileEntry, content: &str) -> Vec<Fix> {
+        let rules: Vec<&dyn Rule> = self
+            .registry
+            .iter_rules()
+            .filter(|&rule| rule.has_fix() && self.rule_applies_to_file(rule, file))
+            .collect();
+
+        let needs_tree = rules
+            .iter()
+            .any(|&rule| rule.kind() == RuleKind::Ast || rule.uses_syntax_tree());
+        let tree = if needs_tree {
+            file.language.and_then(|lang| self.parse_ast(content, lang))
+        } else {
+            None
+        };
+
+        let ctx = self.context(file, content, tree.as_ref(), self.walked_files.as_ref());
+        let suppressions = self.suppressions(file, content, tree.as_ref());
+        if suppressions.is_empty() {
+            return rules.iter().flat_map(|rule| rule.fixes(&ctx)).collect();
+        }
+
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset) as u32;
+        rules
+            .iter()
+            .flat_map(|&rule| {
+                rule.fixes(&ctx)
+                    .into_iter()
+                    .filter(|fix| !suppressions.suppresses_at(rule.id(), line_of(fix.range.start)))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns the registry of rules this engine runs
+    pub fn registry(&self) -> &RuleRegistry {
+        &self.registry
+    }
+
+    /// Read a file and execute all applicable rules against it
+    ///
+    /// Files that cannot be read are reported as warnings and skipped.
+    fn execute_file(
+        &self,
+        file: &FileEntry,
+        cache: Option<&ResultCache>,
+        walked: Option<&HashSet<PathBuf>>,
+        sink: &mut impl ViolationSink,
+    ) -> Option<CachedFile> {
         // Read file content - if we can't read it, log warning and skip
         let content = match fs::read_to_string(&file.path) {
             Ok(content) => content,
@@ -110,80 +486,235 @@ impl ExecutionEngine {
                     file.path.display(),
                     e
                 );
-                return vec![];
+                return None;
             }
         };
 
+        self.execute_source(file, &content, cache, walked, sink)
+    }
+
+    /// Execute all applicable rules against a single file's content
+    ///
+    /// This method:
+    /// 1. Determines which rules apply (based on language and file path)
+    /// 2. Reuses cached results for rules whose file content and definition are unchanged
+    /// 3. Scans the file once with the language's RegexSet to skip regex rules that cannot match
+    /// 4. Parses AST if any remaining AST rules, or regex rules that read the tree, apply
+    /// 5. Executes the remaining rules
+    /// 6. Drops violations suppressed by inline comments, counting them separately
+    /// 7. Reports suppression comments without a reason, if the suppression rule applies
+    /// 8. Passes the results to `sink` and returns the updated cache entry when caching
+    ///
+    /// Cached results are stored before suppression, which depends only on
+    /// the content. Rules named by a suppression comment build their
+    /// violations even in count-only runs, so suppressed ones can be told apart.
+    /// Unless the sink keeps violations, only counts are cached for the other
+    /// rules.
+    fn execute_source(
+        &self,
+        file: &FileEntry,
+        content: &str,
+        cache: Option<&ResultCache>,
+        walked: Option<&HashSet<PathBuf>>,
+        sink: &mut impl ViolationSink,
+    ) -> Option<CachedFile> {
         // Collect all rules that apply to this file
         let applicable_rules: Vec<&dyn Rule> = self
             .registry
             .iter_rules()
-            .filter(|&rule| self.rule_applies_to_file(rule, file))
+            .filter(|&rule| !rule.is_batch() && self.rule_applies_to_file(rule, file))
             .collect();
 
         if applicable_rules.is_empty() {
-            return vec![];
+            return None;
         }
 
-        // Group rules by type (AST vs Regex)
-        let (ast_rules, regex_rules): (Vec<&dyn Rule>, Vec<&dyn Rule>) = applicable_rules
-            .into_iter()
-            .partition(|&rule| self.is_ast_rule(rule));
-
-        let mut all_violations = Vec::new();
-
-        // Parse AST once if we have AST rules
-        let tree = if !ast_rules.is_empty() {
-            file.language
-                .and_then(|lang| self.parse_ast(&content, lang))
+        let count_only = sink.count_only();
+        let detailed = sink.detailed();
+        // Directives only count inside comments, which takes the syntax tree
+        let mut tree = if Suppressions::mentioned(content) {
+            file.language.and_then(|lang| self.parse_ast(content, lang))
         } else {
             None
         };
+        let suppressions = Suppressions::parse(content, file.language, tree.as_ref());
+
+        // The suppression rule reports the directives parsed here instead of running
+        let (suppression_rules, applicable_rules): (Vec<&dyn Rule>, Vec<&dyn Rule>) =
+            applicable_rules
+                .into_iter()
+                .partition(|&rule| rule.kind() == RuleKind::Suppression);
+        let counted = |rule: &dyn Rule| count_only && !suppressions.covers(rule.id());
+        // Whether a rule's violations, rather than only their number, are needed
+        let itemized = |rule: &dyn Rule| detailed || suppressions.covers(rule.id());
+
+        // Look up the file's previous results. Entries for rules that do not
+        // run this time are carried over so narrower runs keep them.
+        let (prior, mut entry) = match cache {
+            Some(cache) => {
+                let hash = cache::content_hash(content, file.language);
+                let prior = cache.get(&file.path, hash);
+                let entry = prior.cloned().unwrap_or_else(|| CachedFile::new(hash));
+                (prior, Some(entry))
+            }
+            None => (None, None),
+        };
+
+        let mut pending_rules = Vec::new();
+        for rule in applicable_rules {
+            let Some((prior, hash)) = prior.zip(rule.definition_hash()) else {
+                pending_rules.push(rule);
+                continue;
+            };
+            let region = self
+                .context(file, content, None, walked)
+                .resolve_region(rule.id());
+            if !itemized(rule) {
+                if let Some(count) = prior.count(rule.id(), hash) {
+                    sink.record_count(rule.id(), region, &file.path, count);
+                    continue;
+                }
+            } else if let Some(violations) = prior.violations(&file.path, rule.id(), hash, &region)
+            {
+                record_unsuppressed(sink, &suppressions, violations);
+                continue;
+            }
+            pending_rules.push(rule);
+        }
 
-        // Execute AST rules with the parsed tree (in parallel)
+        // Group rules by kind: AST rules run only against a syntax tree, the
+        // rest against the text (reading the tree if they use it)
+        let (ast_rules, regex_rules): (Vec<&dyn Rule>, Vec<&dyn Rule>) = pending_rules
+            .into_iter()
+            .partition(|&rule| rule.kind() == RuleKind::Ast);
+
+        // Run a rule, only counting its matches in count-only mode
+        let run = |rule: &dyn Rule, ctx: &ExecutionContext| {
+            if counted(rule) {
+                RuleResult::Count(ctx.resolve_region(rule.id()), rule.count(ctx))
+            } else {
+                RuleResult::Violations(rule.execute(ctx))
+            }
+        };
+
+        // Scan the file once for all regex rules; rules whose pattern does not
+        // occur report nothing, so only the matched ones run their full search
+        let prefilter = file.language.and_then(|lang| self.prefilters.get(&lang));
+        let (regex_rules, skipped): (Vec<&dyn Rule>, Vec<&dyn Rule>) = match prefilter {
+            Some(prefilter) if !regex_rules.is_empty() => {
+                let matched = prefilter.set.matches(content);
+                regex_rules.into_iter().partition(|rule| {
+                    prefilter
+                        .indices
+                        .get(rule.id())
+                        .is_none_or(|&idx| matched.matched(idx))
+                })
+            }
+            _ => (regex_rules, Vec::new()),
+        };
+
+        // Parse AST once if we have AST rules or regex rules that read the tree
+        if tree.is_none()
+            && (!ast_rules.is_empty() || regex_rules.iter().any(|r| r.uses_syntax_tree()))
+        {
+            tree = file.language.and_then(|lang| self.parse_ast(content, lang));
+        }
+
+        // Execute AST rules against the shared tree (in parallel)
+        let mut results: Vec<(&dyn Rule, RuleResult)> = Vec::new();
         if let Some(ref tree) = tree {
-            let ast_violations: Vec<Violation> = ast_rules
-                .par_iter()
-                .flat_map(|&rule| {
-                    // Try to downcast to AstRule to use execute_with_tree
-                    if let Some(ast_rule) = self.try_downcast_ast_rule(rule) {
-                        ast_rule.execute_with_tree(
-                            tree,
-                            &content,
-                            &file.path,
-                            self.region_resolver.as_ref(),
-                        )
-                    } else {
-                        // Fallback to regular execute (will re-parse internally)
-                        let ctx = ExecutionContext {
-                            file_path: &file.path,
-                            content: &content,
-                            ast: None,
-                            region_resolver: self.region_resolver.clone(),
-                        };
-                        rule.execute(&ctx)
+            results.par_extend(ast_rules.par_iter().map(|&rule| {
+                let ctx = self.context(file, content, Some(tree), walked);
+                (rule, run(rule, &ctx))
+            }));
+        }
+
+        results.extend(
+            skipped
+                .into_iter()
+                .map(|rule| (rule, RuleResult::Violations(Vec::new()))),
+        );
+
+        // Execute regex rules (in parallel), sharing the file's line offsets
+        let line_offsets = if regex_rules.is_empty() {
+            Vec::new()
+        } else {
+            compute_line_offsets(content)
+        };
+        results.par_extend(regex_rules.par_iter().map(|&rule| {
+            let mut ctx = self.context(file, content, tree.as_ref(), walked);
+            ctx.line_offsets = Some(&line_offsets);
+            (rule, run(rule, &ctx))
+        }));
+
+        for (rule, result) in results {
+            match result {
+                RuleResult::Violations(violations) => {
+                    if let Some(entry) = entry.as_mut()
+                        && let Some(hash) = rule.definition_hash()
+                    {
+                        if itemized(rule) {
+                            entry.insert(rule.id(), hash, &violations);
+                        } else {
+                            entry.insert_count(rule.id(), hash, violations.len());
+                        }
                     }
-                })
-                .collect();
-            all_violations.extend(ast_violations);
+                    record_unsuppressed(sink, &suppressions, violations);
+                }
+                RuleResult::Count(region, count) => {
+                    if let Some(entry) = entry.as_mut()
+                        && let Some(hash) = rule.definition_hash()
+                    {
+                        entry.insert_count(rule.id(), hash, count);
+                    }
+                    sink.record_count(rule.id(), region, &file.path, count);
+                }
+            }
         }
 
-        // Execute regex rules (in parallel)
-        let regex_violations: Vec<Violation> = regex_rules
-            .par_iter()
-            .flat_map(|&rule| {
-                let ctx = ExecutionContext {
-                    file_path: &file.path,
-                    content: &content,
-                    ast: None,
-                    region_resolver: self.region_resolver.clone(),
-                };
-                rule.execute(&ctx)
-            })
-            .collect();
-        all_violations.extend(regex_violations);
+        // Suppression comments without a reason are violations of the suppression rule
+        if !suppressions.is_empty() && !suppression_rules.is_empty() {
+            sink.record_violations(
+                suppressions.unjustified(&self.context(file, content, None, walked)),
+            );
+        }
+
+        entry
+    }
 
-        all_violations
+    /// Parse the suppression directives of a file's content
+    ///
+    /// The content is parsed for its comments if it mentions a directive and
+    /// `tree` is None.
+    fn suppressions(
+        &self,
+        file: &FileEntry,
+        content: &str,
+        tree: Option<&tree_sitter::Tree>,
+    ) -> Suppressions {
+        if tree.is_some() || !Suppressions::mentioned(content) {
+            return Suppressions::parse(content, file.language, tree);
+        }
+        let tree = file.language.and_then(|lang| self.parse_ast(content, lang));
+        Suppressions::parse(content, file.language, tree.as_ref())
+    }
+
+    /// Build the execution context for running a rule against a file
+    fn context<'a>(
+        &self,
+        file: &'a FileEntry,
+        content: &'a str,
+        ast: Option<&'a tree_sitter::Tree>,
+        walked_files: Option<&'a HashSet<PathBuf>>,
+    ) -> ExecutionContext<'a> {
+        ExecutionContext {
+            file_path: &file.path,
+            content,
+            ast,
+            line_offsets: None,
+            region_resolver: self.region_resolver.clone(),
+            walked_files,
+        }
     }
 
     /// Check if a rule applies to a file
@@ -201,24 +732,8 @@ impl ExecutionEngine {
             return true;
         }
 
-        // Check if file's language is in rule's language list
-        languages.contains(&file_lang)
-    }
-
-    /// Check if a rule is an AST rule
-    ///
-    /// Heuristic: AST rules are language-specific, so they declare exactly one language.
-    fn is_ast_rule(&self, rule: &dyn Rule) -> bool {
-        let languages = rule.languages();
-        languages.len() == 1
-    }
-
-    /// Try to downcast a rule to AstRule, returning None if it is not one.
-    ///
-    /// Always returns None: AST rules fall back to `Rule::execute`, which parses
-    /// internally, so no downcast is performed here.
-    fn try_downcast_ast_rule<'a>(&self, _rule: &'a dyn Rule) -> Option<&'a AstRule> {
-        None
+        // Check if one of the rule's languages includes the file's
+        languages.iter().any(|l| l.includes(file_lang))
     }
 
     /// Parse AST for a given language
@@ -235,11 +750,116 @@ impl ExecutionEngine {
     }
 }
 
+/// What running a rule against one file produced
+enum RuleResult {
+    Violations(Vec<Violation>),
+    /// Count-only runs: the number of violations and their region
+    Count(RegionPath, usize),
+}
+
+/// Destination for the results of running rules against a file
+trait ViolationSink {
+    /// Whether rules should only count their matches
+    fn count_only(&self) -> bool;
+
+    /// Whether the sink keeps violations rather than only counting them
+    fn detailed(&self) -> bool;
+
+    /// Record the violations a rule found in a file
+    fn record_violations(&mut self, violations: Vec<Violation>);
+
+    /// Record how many violations a rule found in a file
+    fn record_count(&mut self, rule_id: &RuleId, region: RegionPath, file: &Path, count: usize);
+
+    /// Record how many of a rule's violations inline comments suppressed
+    fn record_suppressed(&mut self, rule_id: &RuleId, region: RegionPath, count: usize);
+}
+
+/// Pass a rule's violations to `sink`, counting suppressed ones separately
+fn record_unsuppressed(
+    sink: &mut impl ViolationSink,
+    suppressions: &Suppressions,
+    violations: Vec<Violation>,
+) {
+    if suppressions.is_empty() {
+        sink.record_violations(violations);
+        return;
+    }
+
+    let (suppressed, kept): (Vec<Violation>, Vec<Violation>) = violations
+        .into_iter()
+        .partition(|violation| suppressions.suppresses(violation));
+    for violation in suppressed {
+        sink.record_suppressed(&violation.rule_id, violation.region, 1);
+    }
+    sink.record_violations(kept);
+}
+
+impl ViolationSink for Vec<Violation> {
+    fn count_only(&self) -> bool {
+        false
+    }
+
+    fn detailed(&self) -> bool {
+        true
+    }
+
+    fn record_violations(&mut self, violations: Vec<Violation>) {
+        self.extend(violations);
+    }
+
+    fn record_count(
+        &mut self,
+        _rule_id: &RuleId,
+        _region: RegionPath,
+        _file: &Path,
+        _count: usize,
+    ) {
+        // Never called: a Vec sink does not ask for counts
+    }
+
+    fn record_suppressed(&mut self, _rule_id: &RuleId, _region: RegionPath, _count: usize) {
+        // Suppressed violations are dropped
+    }
+}
+
+impl ViolationSink for ViolationTally {
+    fn count_only(&self) -> bool {
+        self.mode() == TallyMode::CountOnly
+    }
+
+    fn detailed(&self) -> bool {
+        self.mode() == TallyMode::Detailed
+    }
+
+    fn record_violations(&mut self, violations: Vec<Violation>) {
+        for violation in violations {
+            self.add(violation);
+        }
+    }
+
+    fn record_count(&mut self, rule_id: &RuleId, region: RegionPath, file: &Path, count: usize) {
+        self.add_count(rule_id, region, file, count as u64);
+    }
+
+    fn record_suppressed(&mut self, rule_id: &RuleId, region: RegionPath, count: usize) {
+        self.add_suppressed(rule_id, region, count as u64);
+    }
+}
+
+/// Combine two partial tallies, keeping `a`'s results first
+fn merge_tallies(mut a: ViolationTally, b: ViolationTally) -> ViolationTally {
+    a.merge(b);
+    a
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::aggregator::ViolationAggregator;
     use crate::engine::file_walker::LanguageDetector;
     use crate::rules::RegexRule;
+    use crate::types::{RegionPath, RuleId};
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -398,27 +1018,21 @@ languages = ["rust"]
     }
 
     #[test]
-    fn test_is_ast_rule_heuristic() -> Result<(), Box<dyn std::error::Error>> {
-        let registry = RuleRegistry::new();
-        let engine = ExecutionEngine::new(registry, None);
-
-        // Regex rule with no language restrictions
-        let regex_rule = create_test_regex_rule()?;
-        assert!(!engine.is_ast_rule(&regex_rule));
-
-        // Regex rule with multiple languages
+    fn test_rule_kind_is_explicit() -> Result<(), Box<dyn std::error::Error>> {
+        // A regex rule scoped to one language is still a regex rule
         let toml = r#"
 [rule]
-id = "multi-lang"
-description = "Multi language rule"
+id = "rust-only"
+description = "Rust only rule"
 severity = "warning"
 
 [match]
-pattern = "TODO"
-languages = ["rust", "python"]
+pattern = "unsafe"
+languages = ["rust"]
 "#;
-        let multi_lang_rule = RegexRule::from_toml(toml)?;
-        assert!(!engine.is_ast_rule(&multi_lang_rule));
+        let rule = RegexRule::from_toml(toml)?;
+        assert_eq!(rule.kind(), RuleKind::Regex);
+        assert_eq!(create_test_regex_rule()?.kind(), RuleKind::Regex);
         Ok(())
     }
 
@@ -515,6 +1129,53 @@ language = "rust"
         Ok(())
     }
 
+    #[cfg(all(feature = "lang-javascript", feature = "lang-typescript"))]
+    #[test]
+    fn test_multi_language_ast_rule_execution() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let js_file = temp_dir.path().join("app.js");
+        let ts_file = temp_dir.path().join("app.ts");
+        fs::write(&js_file, "debugger;\nrun();\n")?;
+        fs::write(&ts_file, "const x: number = 1;\ndebugger;\n")?;
+
+        let ast_dir = temp_dir.path().join("ast");
+        fs::create_dir(&ast_dir)?;
+        let ast_rule_content = r#"
+[rule]
+id = "no-debugger"
+description = "No debugger statements"
+severity = "error"
+
+[match]
+query = "(debugger_statement) @violation"
+language = ["javascript", "typescript"]
+"#;
+        fs::write(ast_dir.join("no-debugger.toml"), ast_rule_content)?;
+
+        let mut registry = RuleRegistry::new();
+        registry.load_custom_ast_rules(&ast_dir, None)?;
+
+        let engine = ExecutionEngine::new(registry, None);
+        let detector = test_detector();
+        let files = vec![
+            FileEntry::new(js_file, &detector),
+            FileEntry::new(ts_file, &detector),
+        ];
+        let result = engine.execute(files);
+
+        // The shared query matches in both grammars
+        let mut lines: Vec<(String, u32)> = result
+            .violations
+            .iter()
+            .map(|v| (v.file.to_string_lossy().to_string(), v.line))
+            .collect();
+        lines.sort();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].0.ends_with("app.js") && lines[0].1 == 1);
+        assert!(lines[1].0.ends_with("app.ts") && lines[1].1 == 2);
+        Ok(())
+    }
+
     #[test]
     fn test_mixed_rules_execution() -> Result<(), Box<dyn std::error::Error>> {
         let temp_dir = TempDir::new()?;
@@ -551,4 +1212,308 @@ pattern = "TODO"
         assert_eq!(result.rules_executed, 1);
         Ok(())
     }
+
+    #[test]
+    fn test_regex_prefilter_skips_non_matching_rules() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(
+            &test_file,
+            "fn main() {}\n// legacy: fix\nlet x = y; // HACK\n",
+        )?;
+
+        let mut registry = RuleRegistry::new();
+        let regex_dir = temp_dir.path().join("regex");
+        fs::create_dir(&regex_dir)?;
+        for (id, pattern, languages) in [
+            ("no-legacy", "legacy", ""),
+            ("no-xxx", "XXX", ""),
+            ("no-hack", "HACK", "languages = [\"rust\"]\n"),
+            ("no-python-legacy", "legacy", "languages = [\"python\"]\n"),
+        ] {
+            fs::write(
+                regex_dir.join(format!("{}.toml", id)),
+                format!(
+                    "[rule]\nid = \"{}\"\ndescription = \"test\"\nseverity = \"warning\"\n\n[match]\npattern = \"{}\"\n{}",
+                    id, pattern, languages
+                ),
+            )?;
+        }
+        registry.load_custom_regex_rules(&regex_dir, None)?;
+
+        let engine = ExecutionEngine::new(registry, None);
+        let rust = engine
+            .prefilters
+            .get(&Language::Rust)
+            .ok_or("expected a Rust prefilter")?;
+        assert_eq!(rust.indices.len(), 3);
+        assert!(
+            !rust
+                .indices
+                .contains_key(&RuleId::new("no-python-legacy").ok_or("invalid rule id")?)
+        );
+
+        let detector = test_detector();
+        let files = vec![FileEntry::new(test_file, &detector)];
+        let mut result = engine.execute(files);
+        result.violations.sort_by_key(|v| v.line);
+
+        assert_eq!(result.violations.len(), 2);
+        assert_eq!(result.violations[0].rule_id.as_str(), "no-legacy");
+        assert_eq!(
+            (result.violations[0].line, result.violations[0].column),
+            (2, 4)
+        );
+        assert_eq!(result.violations[1].rule_id.as_str(), "no-hack");
+        assert_eq!(
+            (result.violations[1].line, result.violations[1].column),
+            (3, 15)
+        );
+        Ok(())
+    }
+
+    #[cfg(all(feature = "lang-rust", feature = "lang-python"))]
+    #[test]
+    fn test_scoped_regex_rules_use_shared_tree() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let rust_file = temp_dir.path().join("test.rs");
+        let python_file = temp_dir.path().join("test.py");
+        fs::write(
+            &rust_file,
+            "// XXX note
+let s = \"XXX\"; XXX();\n",
+        )?;
+        fs::write(&python_file, "# XXX\nx = f\"XXX {XXX}\"\n")?;
+
+        let mut registry = RuleRegistry::new();
+        let regex_dir = temp_dir.path().join("regex");
+        fs::create_dir(&regex_dir)?;
+        for scope in ["comments", "strings", "code"] {
+            fs::write(
+                regex_dir.join(format!("{}.toml", scope)),
+                format!(
+                    "[rule]\nid = \"xxx-{}\"\ndescription = \"test\"\nseverity = \"warning\"\n\n[match]\npattern = \"XXX\"\nscope = \"{}\"\n",
+                    scope, scope
+                ),
+            )?;
+        }
+        registry.load_custom_regex_rules(&regex_dir, None)?;
+
+        // Regex rules without a single language still get the file's tree
+        let engine = ExecutionEngine::new(registry, None);
+        let detector = test_detector();
+        for file in [rust_file, python_file] {
+            let result = engine.execute(vec![FileEntry::new(file, &detector)]);
+            let mut found: Vec<(&str, u32)> = result
+                .violations
+                .iter()
+                .map(|v| (v.rule_id.as_str(), v.line))
+                .collect();
+            found.sort();
+            assert_eq!(
+                found,
+                [("xxx-code", 2), ("xxx-comments", 1), ("xxx-strings", 2)]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_tally_count_only() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(&test_file, "// HACK: fix\n// HACK: again\nfn main() {}")?;
+
+        let mut registry = RuleRegistry::new();
+        let regex_dir = temp_dir.path().join("regex");
+        fs::create_dir(&regex_dir)?;
+        fs::write(
+            regex_dir.join("hack.toml"),
+            "[rule]\nid = \"no-hack\"\ndescription = \"No HACK\"\nseverity = \"warning\"\n\n[match]\npattern = \"HACK\"\n",
+        )?;
+        registry.load_custom_regex_rules(&regex_dir, None)?;
+        let engine = ExecutionEngine::new(registry, None);
+        let detector = test_detector();
+        let files = vec![FileEntry::new(test_file, &detector)];
+
+        let tally = engine.execute_tally(&files, None, TallyMode::CountOnly);
+        assert_eq!(tally.total(), 2);
+
+        // Count-only and summary runs cache only the counts
+        let mut result_cache = ResultCache::new();
+        let tally = engine.execute_tally(&files, Some(&mut result_cache), TallyMode::CountOnly);
+        assert_eq!(tally.total(), 2);
+        assert_eq!(result_cache.len(), 1);
+        let content_hash =
+            cache::content_hash(&fs::read_to_string(&files[0].path)?, files[0].language);
+        let rule_id = RuleId::new("no-hack").ok_or("invalid rule id")?;
+        let definition_hash = engine
+            .registry
+            .get_rule(&rule_id)
+            .and_then(|rule| rule.definition_hash())
+            .ok_or("expected a definition hash")?;
+        let entry = result_cache
+            .get(&files[0].path, content_hash)
+            .ok_or("expected a cache entry")?;
+        assert_eq!(entry.count(&rule_id, definition_hash), Some(2));
+        let region = RegionPath::new(".");
+        assert!(
+            entry
+                .violations(&files[0].path, &rule_id, definition_hash, &region)
+                .is_none()
+        );
+        let tally = engine.execute_tally(&files, Some(&mut result_cache), TallyMode::Summary);
+        assert_eq!(tally.total(), 2);
+
+        // Detailed runs need the violations, so they run the rule and cache them
+        let tally = engine.execute_tally(&files, Some(&mut result_cache), TallyMode::Detailed);
+        assert_eq!(tally.total(), 2);
+        let entry = result_cache
+            .get(&files[0].path, content_hash)
+            .ok_or("expected a cache entry")?;
+        assert!(
+            entry
+                .violations(&files[0].path, &rule_id, definition_hash, &region)
+                .is_some_and(|v| v.len() == 2)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_tally_drops_suppressed_violations() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(
+            &test_file,
+            // Split so that this file does not carry an unjustified directive itself
+            concat!(
+                "let a = legacy(); // kept\n",
+                "// ratchets-ignore-next-line[no-legacy]: removed with the old API\n",
+                "let b = legacy();\n",
+                "let c = legacy(); // ",
+                "ratchets-ignore[no-legacy]\n",
+                "let d = \"// ratchets-ignore[no-legacy]: quoted\"; legacy();\n",
+            ),
+        )?;
+
+        let mut registry = RuleRegistry::new();
+        let regex_dir = temp_dir.path().join("regex");
+        fs::create_dir(&regex_dir)?;
+        fs::write(
+            regex_dir.join("legacy.toml"),
+            "[rule]\nid = \"no-legacy\"\ndescription = \"No legacy\"\nseverity = \"warning\"\n\n[match]\npattern = \"legacy\\\\(\"\n",
+        )?;
+        registry.load_custom_regex_rules(&regex_dir, None)?;
+        registry.load_builtin_suppression_rule();
+        let engine = ExecutionEngine::new(registry, None);
+        let detector = test_detector();
+        let files = vec![FileEntry::new(test_file, &detector)];
+        let aggregator = ViolationAggregator::new(CountsManager::new());
+
+        // Fresh, count-only and cached runs all drop the same violations, and
+        // a directive inside a string literal suppresses nothing
+        let mut result_cache = ResultCache::new();
+        for (mode, cache) in [
+            (TallyMode::Summary, Some(&mut result_cache)),
+            (TallyMode::CountOnly, None),
+        ] {
+            let result = aggregator.aggregate_tally(engine.execute_tally(&files, cache, mode));
+            let counts: Vec<(&str, u64)> = result
+                .statuses
+                .iter()
+                .map(|s| (s.rule_id.as_str(), s.actual_count))
+                .collect();
+            assert_eq!(
+                counts,
+                [("no-legacy", 2), (crate::rules::UNJUSTIFIED_SUPPRESSION, 1)]
+            );
+            assert_eq!(result.suppressed.len(), 1);
+            assert_eq!(result.suppressed[0].count, 2);
+        }
+        let tally = engine.execute_tally(&files, Some(&mut result_cache), TallyMode::CountOnly);
+        assert_eq!(tally.total(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_with_cache_reuses_results() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let test_file = temp_dir.path().join("test.rs");
+        let content = "// HACK: fix\nfn main() {}";
+        fs::write(&test_file, content)?;
+
+        let mut registry = RuleRegistry::new();
+        let regex_dir = temp_dir.path().join("regex");
+        fs::create_dir(&regex_dir)?;
+        fs::write(
+            regex_dir.join("hack.toml"),
+            "[rule]\nid = \"no-hack\"\ndescription = \"No HACK\"\nseverity = \"warning\"\n\n[match]\npattern = \"HACK\"\n",
+        )?;
+        registry.load_custom_regex_rules(&regex_dir, None)?;
+
+        let rule_id = RuleId::new("no-hack").ok_or("invalid rule id")?;
+        let definition_hash = registry
+            .get_rule(&rule_id)
+            .and_then(|rule| rule.definition_hash())
+            .ok_or("expected definition hash")?;
+
+        // Seed the cache with a result the rule could never produce
+        let mut entry = CachedFile::new(cache::content_hash(content, Some(Language::Rust)));
+        let seeded = Violation {
+            rule_id: rule_id.clone(),
+            file: test_file.clone(),
+            line: 99,
+            column: 1,
+            end_line: 99,
+            end_column: 5,
+            snippet: "SEEDED".to_string(),
+            message: "No HACK".to_string(),
+            region: RegionPath::new("."),
+        };
+        entry.insert(&rule_id, definition_hash, &[seeded]);
+        let mut result_cache = ResultCache::new();
+        result_cache.insert(&test_file, entry);
+
+        let engine = ExecutionEngine::new(registry, None);
+        let detector = test_detector();
+
+        let files = vec![FileEntry::new(test_file.clone(), &detector)];
+        let result = engine.execute_with_cache(files.clone(), &mut result_cache);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].snippet, "SEEDED");
+
+        // Changing the content invalidates the entry and the rule runs again
+        fs::write(&test_file, "// HACK: fix\n// HACK: again\nfn main() {}")?;
+        let result = engine.execute_with_cache(files, &mut result_cache);
+        assert_eq!(result.violations.len(), 2);
+        assert!(result.violations.iter().all(|v| v.snippet == "HACK"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_with_cache_populates_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let test_file = temp_dir.path().join("test.rs");
+        fs::write(&test_file, "// HACK: fix\nfn main() {}")?;
+
+        let mut registry = RuleRegistry::new();
+        let regex_dir = temp_dir.path().join("regex");
+        fs::create_dir(&regex_dir)?;
+        fs::write(
+            regex_dir.join("hack.toml"),
+            "[rule]\nid = \"no-hack\"\ndescription = \"No HACK\"\nseverity = \"warning\"\n\n[match]\npattern = \"HACK\"\n",
+        )?;
+        registry.load_custom_regex_rules(&regex_dir, None)?;
+        let engine = ExecutionEngine::new(registry, None);
+        let detector = test_detector();
+
+        let mut result_cache = ResultCache::new();
+        let files = vec![FileEntry::new(test_file, &detector)];
+        let first = engine.execute_with_cache(files.clone(), &mut result_cache);
+        assert_eq!(result_cache.len(), 1);
+
+        let second = engine.execute_with_cache(files, &mut result_cache);
+        assert_eq!(first.violations, second.violations);
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@
 //! This module provides gitignore-aware file walking with glob-based filtering
 //! and automatic language detection using the ignore crate's TypesBuilder.
 
+use crate::engine::shebang;
 use crate::types::{GlobPattern, Language};
 use globset::{Glob, GlobSetBuilder};
 use ignore::WalkBuilder;
@@ -28,15 +29,60 @@ pub enum FileWalkerError {
     Io(#[from] std::io::Error),
 }
 
+/// Extensions of plain TypeScript files, i.e. ripgrep's `ts` type without `.tsx`
+const TYPESCRIPT_GLOBS: [&str; 3] = ["*.ts", "*.cts", "*.mts"];
+
+/// Shell script extensions and bash init files, i.e. ripgrep's `sh` type
+/// without csh, ksh, zsh and dotenv files
+const SHELL_GLOBS: [&str; 8] = [
+    "*.sh",
+    "*.bash",
+    ".bashrc",
+    ".bash_profile",
+    ".bash_login",
+    ".bash_logout",
+    ".bash_aliases",
+    ".profile",
+];
+
+/// Extensions of C sources; `.h` headers go to the configured header language
+const C_GLOBS: [&str; 1] = ["*.c"];
+
+/// Extensions of C++ sources and headers other than `.h`
+const CPP_GLOBS: [&str; 9] = [
+    "*.cc", "*.cpp", "*.cxx", "*.C", "*.hh", "*.hpp", "*.hxx", "*.H", "*.inl",
+];
+
 /// Detects programming languages for files using the ignore crate's TypesBuilder.
 ///
 /// This uses the well-maintained file type definitions from ripgrep, gaining
 /// support for additional extensions like `.mts`, `.cts` for TypeScript,
-/// `.vue`, `.cjs`, `.mjs` for JavaScript, and `.pyi` for Python.
+/// `.vue`, `.cjs`, `.mjs` for JavaScript, `.pyi` for Python and `.kts` for
+/// Kotlin. `.tsx` files are TSX rather than TypeScript, and `.h` headers are
+/// C unless the detector is built with another header language. Files
+/// without an extension are detected from their shebang or modeline.
+/// A language map from `[languages.map]` overrides all of these.
 #[derive(Clone)]
 pub struct LanguageDetector {
     /// Map from Language to its Types matcher
     matchers: Arc<HashMap<Language, Types>>,
+    /// Glob patterns assigning languages ahead of the matchers
+    language_map: Option<Arc<LanguageMap>>,
+}
+
+/// Compiled `[languages.map]` patterns, longest pattern first
+struct LanguageMap {
+    globs: globset::GlobSet,
+    languages: Vec<Language>,
+}
+
+impl LanguageMap {
+    /// The language of the longest pattern matching `path`
+    fn language_of(&self, path: &Path) -> Option<Language> {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let index = self.globs.matches(path).into_iter().min()?;
+        self.languages.get(index).copied()
+    }
 }
 
 impl LanguageDetector {
@@ -44,15 +90,19 @@ impl LanguageDetector {
     ///
     /// If building a matcher for a language fails, that language is logged and skipped.
     pub fn new() -> Self {
+        Self::with_header_language(Language::C)
+    }
+
+    /// Creates a LanguageDetector that detects `.h` headers as `header_language`
+    ///
+    /// `.h` is shared by C and C++, so projects choose which grammar parses
+    /// their headers.
+    pub fn with_header_language(header_language: Language) -> Self {
         let mut matchers = HashMap::new();
 
         for lang in Language::all() {
             let type_name = lang.ignore_type_name();
-            let mut builder = TypesBuilder::new();
-            builder.add_defaults();
-            builder.select(type_name);
-
-            match builder.build() {
+            match build_types(type_name, header_language) {
                 Ok(types) => {
                     matchers.insert(lang, types);
                 }
@@ -67,13 +117,55 @@ impl LanguageDetector {
 
         Self {
             matchers: Arc::new(matchers),
+            language_map: None,
         }
     }
 
+    /// Assigns the language of files matching the patterns of `map`
+    ///
+    /// Mapped files take the language of the longest matching pattern,
+    /// whatever their extension or shebang says.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileWalkerError::InvalidGlob` if a pattern does not compile.
+    pub fn with_language_map(
+        mut self,
+        map: &HashMap<GlobPattern, Language>,
+    ) -> Result<Self, FileWalkerError> {
+        if map.is_empty() {
+            self.language_map = None;
+            return Ok(self);
+        }
+        let mut entries: Vec<(&GlobPattern, &Language)> = map.iter().collect();
+        entries.sort_by(|(a, _), (b, _)| {
+            b.as_str()
+                .len()
+                .cmp(&a.as_str().len())
+                .then_with(|| a.as_str().cmp(b.as_str()))
+        });
+        let patterns: Vec<GlobPattern> = entries.iter().map(|(p, _)| (*p).clone()).collect();
+        self.language_map = Some(Arc::new(LanguageMap {
+            globs: FileWalker::build_globset(&patterns)?,
+            languages: entries.iter().map(|(_, language)| **language).collect(),
+        }));
+        Ok(self)
+    }
+
     /// Detects the language of a file based on its path.
     ///
-    /// Returns the first matching language, or None if no language matches.
+    /// Returns the language mapped by the longest matching `[languages.map]`
+    /// pattern, else the first matching language. Files without an extension that
+    /// no language matches are read for a shebang or modeline, e.g.
+    /// `#!/usr/bin/env bash` in `bin/deploy`. Returns None if nothing matches.
     pub fn detect(&self, path: &Path) -> Option<Language> {
+        if let Some(language) = self
+            .language_map
+            .as_ref()
+            .and_then(|map| map.language_of(path))
+        {
+            return Some(language);
+        }
         for lang in Language::all() {
             if let Some(types) = self.matchers.get(&lang)
                 && types.matched(path, false).is_whitelist()
@@ -81,10 +173,43 @@ impl LanguageDetector {
                 return Some(lang);
             }
         }
+        if path.extension().is_none() {
+            return shebang::detect_file(path);
+        }
         None
     }
 }
 
+/// Build the file type matcher selecting ripgrep's `type_name` type
+fn build_types(type_name: &str, header_language: Language) -> Result<Types, ignore::Error> {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+    // ripgrep's `ts` type also covers `.tsx`, which has its own grammar
+    builder.clear("ts");
+    for glob in TYPESCRIPT_GLOBS {
+        builder.add("ts", glob)?;
+    }
+    builder.add("tsx", "*.tsx")?;
+    // ripgrep's `java` type also covers JSP pages and `.properties` files
+    builder.clear("java");
+    builder.add("java", "*.java")?;
+    // ripgrep's `c` and `cpp` types both cover `.h` and `.in` templates
+    builder.clear("c");
+    for glob in C_GLOBS {
+        builder.add("c", glob)?;
+    }
+    builder.clear("cpp");
+    for glob in CPP_GLOBS {
+        builder.add("cpp", glob)?;
+    }
+    builder.add(header_language.ignore_type_name(), "*.h")?;
+    builder.clear("sh");
+    for glob in SHELL_GLOBS {
+        builder.add("sh", glob)?;
+    }
+    builder.select(type_name).build()
+}
+
 impl Default for LanguageDetector {
     fn default() -> Self {
         Self::new()
@@ -215,6 +340,12 @@ impl FileWalker {
         })
     }
 
+    /// Replaces the detector that assigns languages to walked files
+    pub fn with_language_detector(mut self, language_detector: LanguageDetector) -> Self {
+        self.language_detector = language_detector;
+        self
+    }
+
     /// Builds a GlobSet from patterns
     fn build_globset(patterns: &[GlobPattern]) -> Result<globset::GlobSet, FileWalkerError> {
         let mut builder = GlobSetBuilder::new();
@@ -342,10 +473,7 @@ mod tests {
             detector.detect(Path::new("test.ts")),
             Some(Language::TypeScript)
         );
-        assert_eq!(
-            detector.detect(Path::new("test.tsx")),
-            Some(Language::TypeScript)
-        );
+        assert_eq!(detector.detect(Path::new("test.tsx")), Some(Language::Tsx));
         // Additional extensions supported by ignore crate
         assert_eq!(
             detector.detect(Path::new("test.mts")),
@@ -399,6 +527,109 @@ mod tests {
         assert_eq!(detector.detect(Path::new("test.go")), Some(Language::Go));
     }
 
+    #[test]
+    fn test_language_detector_detect_c_family() {
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.detect(Path::new("ext.c")), Some(Language::C));
+        assert_eq!(detector.detect(Path::new("ext.h")), Some(Language::C));
+        assert_eq!(detector.detect(Path::new("ext.cpp")), Some(Language::Cpp));
+        assert_eq!(detector.detect(Path::new("ext.hpp")), Some(Language::Cpp));
+        assert_eq!(detector.detect(Path::new("config.h.in")), None);
+
+        let detector = LanguageDetector::with_header_language(Language::Cpp);
+        assert_eq!(detector.detect(Path::new("ext.h")), Some(Language::Cpp));
+        assert_eq!(detector.detect(Path::new("ext.c")), Some(Language::C));
+    }
+
+    #[test]
+    fn test_language_detector_detect_shell() {
+        let detector = LanguageDetector::new();
+        assert_eq!(
+            detector.detect(Path::new("deploy.sh")),
+            Some(Language::Bash)
+        );
+        assert_eq!(detector.detect(Path::new(".bashrc")), Some(Language::Bash));
+        assert_eq!(detector.detect(Path::new("setup.zsh")), None);
+        assert_eq!(detector.detect(Path::new(".env")), None);
+    }
+
+    #[test]
+    fn test_language_detector_detect_shebang() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let bin = temp_dir.path().join("bin");
+        fs::create_dir(&bin)?;
+        fs::write(bin.join("deploy"), "#!/usr/bin/env bash\nrm -rf \"$DIR\"\n")?;
+        fs::write(bin.join("migrate"), "#!/usr/bin/python3\nprint(1)\n")?;
+        fs::write(bin.join("notes.txt"), "#!/bin/bash\n")?;
+        fs::write(bin.join("data"), "plain text\n")?;
+
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.detect(&bin.join("deploy")), Some(Language::Bash));
+        assert_eq!(
+            detector.detect(&bin.join("migrate")),
+            Some(Language::Python)
+        );
+        // Only extensionless files are sniffed
+        assert_eq!(detector.detect(&bin.join("notes.txt")), None);
+        assert_eq!(detector.detect(&bin.join("data")), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_language_detector_language_map() -> Result<(), Box<dyn std::error::Error>> {
+        let map = HashMap::from([
+            (GlobPattern::new("*.bzl"), Language::Python),
+            (GlobPattern::new("*.in"), Language::Python),
+            (GlobPattern::new("src/gen/*.rs.in"), Language::Rust),
+            (GlobPattern::new("*.js"), Language::TypeScript),
+        ]);
+        let detector = LanguageDetector::new().with_language_map(&map)?;
+
+        assert_eq!(
+            detector.detect(Path::new("tools/defs.bzl")),
+            Some(Language::Python)
+        );
+        // Mapped patterns override extension detection
+        assert_eq!(
+            detector.detect(Path::new("web/app.js")),
+            Some(Language::TypeScript)
+        );
+        // The longest matching pattern wins, with or without a leading `./`
+        assert_eq!(
+            detector.detect(Path::new("./src/gen/ids.rs.in")),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            detector.detect(Path::new("config.h.in")),
+            Some(Language::Python)
+        );
+        // Unmapped files fall back to the usual detection
+        assert_eq!(detector.detect(Path::new("lib.rs")), Some(Language::Rust));
+
+        let invalid = HashMap::from([(GlobPattern::new("[bzl"), Language::Python)]);
+        assert!(LanguageDetector::new().with_language_map(&invalid).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_language_detector_detect_jvm() {
+        let detector = LanguageDetector::new();
+        assert_eq!(
+            detector.detect(Path::new("Main.java")),
+            Some(Language::Java)
+        );
+        assert_eq!(detector.detect(Path::new("app.properties")), None);
+        assert_eq!(detector.detect(Path::new("index.jsp")), None);
+        assert_eq!(
+            detector.detect(Path::new("Main.kt")),
+            Some(Language::Kotlin)
+        );
+        assert_eq!(
+            detector.detect(Path::new("build.gradle.kts")),
+            Some(Language::Kotlin)
+        );
+    }
+
     #[test]
     fn test_language_detector_detect_unknown() {
         let detector = LanguageDetector::new();
@@ -0,0 +1,164 @@
+#![forbid(unsafe_code)]
+
+//! Language detection from the first lines of extensionless scripts
+//!
+//! Scripts such as `bin/deploy` carry no extension, so their language comes
+//! from their content instead:
+//!
+//! - A `#!` line naming the interpreter, directly (`#!/bin/bash`) or through
+//!   `env` (`#!/usr/bin/env -S python3 -u`). Versioned interpreters such as
+//!   `python3.12` resolve to their language.
+//! - A Vim modeline (`# vim: set ft=sh:`) or Emacs mode line
+//!   (`# -*- mode: python -*-`) in the first few lines.
+
+use crate::types::Language;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of a file when sniffing its language
+const HEAD_BYTES: u64 = 1024;
+
+/// Lines searched for modelines, as in Vim's default `modelines` setting
+const MODELINE_LINES: usize = 5;
+
+/// Detect the language of the script at `path` from its first lines
+///
+/// Returns None if the file cannot be read or names no known language.
+pub(crate) fn detect_file(path: &Path) -> Option<Language> {
+    let mut head = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(HEAD_BYTES).read_to_end(&mut head))
+        .ok()?;
+    detect(&String::from_utf8_lossy(&head))
+}
+
+/// Detect the language named by the shebang or a modeline at the start of `head`
+pub(crate) fn detect(head: &str) -> Option<Language> {
+    let mut lines = head.lines().take(MODELINE_LINES);
+    let first = lines.next()?;
+    shebang_interpreter(first)
+        .and_then(language_named)
+        .or_else(|| {
+            std::iter::once(first)
+                .chain(lines)
+                .find_map(|line| vim_filetype(line).or_else(|| emacs_mode(line)))
+                .and_then(language_named)
+        })
+}
+
+/// The interpreter a `#!` line runs, looking through `env` and its options
+fn shebang_interpreter(line: &str) -> Option<&str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let program = base_name(words.next()?);
+    if program != "env" {
+        return Some(program);
+    }
+    words
+        .find(|word| !word.starts_with('-') && !word.contains('='))
+        .map(base_name)
+}
+
+/// The filetype set by a Vim modeline, e.g. `vim: set ft=sh:` or `vi: ft=sh`
+fn vim_filetype(line: &str) -> Option<&str> {
+    let start = ["vim:", "vi:", "ex:"]
+        .iter()
+        .filter_map(|marker| {
+            let index = line.find(marker)?;
+            let starts_word = line[..index].ends_with(char::is_whitespace) || index == 0;
+            starts_word.then_some(index + marker.len())
+        })
+        .min()?;
+    line[start..]
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .find_map(|option| {
+            ["ft=", "filetype=", "syntax="]
+                .iter()
+                .find_map(|name| option.strip_prefix(name))
+        })
+}
+
+/// The major mode set by an Emacs mode line, e.g. `-*- mode: sh -*-` or `-*- sh -*-`
+fn emacs_mode(line: &str) -> Option<&str> {
+    let start = line.find("-*-")? + "-*-".len();
+    let end = start + line[start..].find("-*-")?;
+    let variables = line[start..end].trim();
+    if !variables.contains(':') {
+        return Some(variables);
+    }
+    variables.split(';').find_map(|variable| {
+        let (name, value) = variable.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("mode")
+            .then_some(value.trim())
+    })
+}
+
+/// The last component of a slash-separated path
+fn base_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The language of an interpreter, Vim filetype or Emacs mode name
+///
+/// Version suffixes are ignored, so `python3.12` is Python.
+fn language_named(name: &str) -> Option<Language> {
+    let name = name.to_ascii_lowercase();
+    match name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+        "sh" | "bash" | "dash" | "shell-script" => Some(Language::Bash),
+        "python" | "pypy" => Some(Language::Python),
+        "node" | "nodejs" | "javascript" | "js" => Some(Language::JavaScript),
+        "typescript" => Some(Language::TypeScript),
+        "rust" => Some(Language::Rust),
+        "go" => Some(Language::Go),
+        "java" => Some(Language::Java),
+        "kotlin" => Some(Language::Kotlin),
+        "c" => Some(Language::C),
+        "cpp" | "c++" => Some(Language::Cpp),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_shebang() {
+        assert_eq!(detect("#!/bin/bash\nset -e\n"), Some(Language::Bash));
+        assert_eq!(detect("#!/bin/sh\n"), Some(Language::Bash));
+        assert_eq!(detect("#!/usr/bin/env bash\n"), Some(Language::Bash));
+        assert_eq!(
+            detect("#!/usr/bin/env -S PYTHONUNBUFFERED=1 python3.12 -u\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(detect("#!/usr/bin/python3\n"), Some(Language::Python));
+        assert_eq!(detect("#!/usr/bin/env node\n"), Some(Language::JavaScript));
+        assert_eq!(detect("#!/usr/bin/env ruby\n"), None);
+        assert_eq!(detect("#!/usr/bin/env\n"), None);
+    }
+
+    #[test]
+    fn test_detect_modelines() {
+        assert_eq!(detect("# vim: set ft=sh:\necho hi\n"), Some(Language::Bash));
+        assert_eq!(
+            detect("#!/usr/bin/env ruby\n# vi: ts=4 filetype=python\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            detect("# -*- mode: python; coding: utf-8 -*-\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(detect("# -*- shell-script -*-\n"), Some(Language::Bash));
+        assert_eq!(detect("# -*- coding: utf-8 -*-\n"), None);
+        // Modelines must start a word, and only the first lines are searched
+        assert_eq!(detect("# navi: ft=sh\n"), None);
+        assert_eq!(detect("a\nb\nc\nd\ne\n# vim: ft=sh\n"), None);
+    }
+
+    #[test]
+    fn test_detect_without_marker() {
+        assert_eq!(detect(""), None);
+        assert_eq!(detect("all:\n\tcargo build\n"), None);
+    }
+}
@@ -0,0 +1,306 @@
+#![forbid(unsafe_code)]
+
+//! Inline suppression comments
+//!
+//! A comment can suppress violations of the rules it names:
+//!
+//! ```text
+//! let port = env.get("PORT").unwrap(); // ratchets-ignore[no-unwrap]: set by the launcher
+//! // ratchets-ignore-next-line[no-unwrap, no-expect]: parsed from a constant
+//! # ratchets-ignore-file[no-bare-print]: this module is the CLI front end
+//! ```
+//!
+//! `ratchets-ignore` covers violations that start on its own line,
+//! `ratchets-ignore-next-line` those that start on the following line, and
+//! `ratchets-ignore-file` every violation in the file. Directives are only
+//! recognized directly after a comment marker of the file's language, and,
+//! when the file has a syntax tree, only inside comment nodes, so a string
+//! such as `"# ratchets-ignore[...]"` suppresses nothing.
+//!
+//! Every suppression needs a reason after the colon. A directive without one
+//! still suppresses, but is itself reported as an `unjustified-suppression`
+//! violation, so unexplained suppressions count against a budget.
+
+use crate::rules::{ExecutionContext, MatchScope, UNJUSTIFIED_SUPPRESSION, Violation};
+use crate::types::{Language, RuleId};
+use tree_sitter::Tree;
+
+/// Keyword that starts a suppression directive
+const DIRECTIVE: &str = "ratchets-ignore";
+
+/// Violations a directive applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    /// Violations starting on this line (1-indexed)
+    Line(u32),
+    /// Every violation in the file
+    File,
+}
+
+/// A parsed suppression directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Directive {
+    target: Target,
+    rules: Vec<RuleId>,
+    /// Whether the directive gives a reason
+    justified: bool,
+    /// Location of the directive text (1-indexed, columns in bytes)
+    line: u32,
+    column: u32,
+    /// The directive text, without a closing `*/`
+    text: String,
+}
+
+/// The suppression directives of one file
+#[derive(Debug, Default)]
+pub(crate) struct Suppressions {
+    directives: Vec<Directive>,
+}
+
+impl Suppressions {
+    /// Returns true if `content` may hold directives, so a caller that has
+    /// not parsed it yet should parse its syntax tree for [`parse`](Self::parse)
+    pub(crate) fn mentioned(content: &str) -> bool {
+        content.contains(DIRECTIVE)
+    }
+
+    /// Find the suppression directives in `content`
+    ///
+    /// Files without a language have no comment syntax, and so no directives.
+    /// With `tree`, the parse of `content`, directives outside comment nodes
+    /// (e.g. in string literals that look like comments) are ignored.
+    pub(crate) fn parse(content: &str, language: Option<Language>, tree: Option<&Tree>) -> Self {
+        let Some(language) = language else {
+            return Self::default();
+        };
+        if !Self::mentioned(content) {
+            return Self::default();
+        }
+
+        let markers = language.comment_markers();
+        let mut directives = Vec::new();
+        let mut line_start = 0;
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            let line_number = index as u32 + 1;
+            let offset = line_start;
+            line_start += line.len();
+            let line = line.trim_end_matches(['\n', '\r']);
+            for (start, _) in line.match_indices(DIRECTIVE) {
+                let before = line[..start].trim_end();
+                if !markers.iter().any(|marker| before.ends_with(marker)) {
+                    continue;
+                }
+                let range = offset + start..offset + start + DIRECTIVE.len();
+                if tree.is_some_and(|tree| !MatchScope::Comments.contains(tree, range)) {
+                    continue;
+                }
+                if let Some(directive) = parse_directive(&line[start..], line_number, start) {
+                    directives.push(directive);
+                }
+            }
+        }
+
+        Self { directives }
+    }
+
+    /// Returns true if the file has no directives
+    pub(crate) fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    /// Returns true if any directive names `rule_id`
+    pub(crate) fn covers(&self, rule_id: &RuleId) -> bool {
+        self.directives.iter().any(|d| d.rules.contains(rule_id))
+    }
+
+    /// Returns true if a directive suppresses `violation`
+    pub(crate) fn suppresses(&self, violation: &Violation) -> bool {
+        self.suppresses_at(&violation.rule_id, violation.line)
+    }
+
+    /// Returns true if a directive suppresses violations of `rule_id` starting on `line`
+    pub(crate) fn suppresses_at(&self, rule_id: &RuleId, line: u32) -> bool {
+        self.directives.iter().any(|d| {
+            d.rules.contains(rule_id)
+                && match d.target {
+                    Target::Line(target) => target == line,
+                    Target::File => true,
+                }
+        })
+    }
+
+    /// Report each directive without a reason as an `unjustified-suppression` violation
+    pub(crate) fn unjustified(&self, ctx: &ExecutionContext) -> Vec<Violation> {
+        let Some(rule_id) = RuleId::new(UNJUSTIFIED_SUPPRESSION) else {
+            return Vec::new();
+        };
+        let region = ctx.resolve_region(&rule_id);
+
+        self.directives
+            .iter()
+            .filter(|d| !d.justified)
+            .map(|d| {
+                let rules: Vec<&str> = d.rules.iter().map(RuleId::as_str).collect();
+                Violation {
+                    rule_id: rule_id.clone(),
+                    file: ctx.file_path.to_path_buf(),
+                    line: d.line,
+                    column: d.column,
+                    end_line: d.line,
+                    end_column: d.column + d.text.len() as u32,
+                    snippet: d.text.clone(),
+                    message: format!(
+                        "Suppression of {} needs a reason after a colon",
+                        rules.join(", ")
+                    ),
+                    region: region.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parse a directive from `text`, which starts with the directive keyword
+///
+/// Returns None if the text is not a well-formed directive, e.g. the rule
+/// list is missing or names an invalid rule ID.
+fn parse_directive(text: &str, line: u32, start: usize) -> Option<Directive> {
+    let rest = &text[DIRECTIVE.len()..];
+    let (target, rest) = if let Some(rest) = rest.strip_prefix("-next-line") {
+        (Target::Line(line + 1), rest)
+    } else if let Some(rest) = rest.strip_prefix("-file") {
+        (Target::File, rest)
+    } else {
+        (Target::Line(line), rest)
+    };
+
+    let (ids, rest) = rest.strip_prefix('[')?.split_once(']')?;
+    let rules = ids
+        .split(',')
+        .map(|id| RuleId::new(id.trim()))
+        .collect::<Option<Vec<_>>>()?;
+
+    let reason = rest
+        .strip_prefix(':')
+        .map(|reason| reason.trim().trim_end_matches("*/").trim());
+    let text = text.trim_end().trim_end_matches("*/").trim_end();
+
+    Some(Directive {
+        target,
+        rules,
+        justified: reason.is_some_and(|reason| !reason.is_empty()),
+        line,
+        column: start as u32 + 1,
+        text: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::ParserCache;
+    use crate::types::RegionPath;
+    use std::path::{Path, PathBuf};
+
+    fn violation(rule_id: &str, line: u32) -> Result<Violation, Box<dyn std::error::Error>> {
+        Ok(Violation {
+            rule_id: RuleId::new(rule_id).ok_or("invalid rule id")?,
+            file: PathBuf::from("src/main.rs"),
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 2,
+            snippet: String::new(),
+            message: String::new(),
+            region: RegionPath::new("src"),
+        })
+    }
+
+    #[test]
+    fn test_line_next_line_and_file_directives() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+let a = x.unwrap(); // ratchets-ignore[no-unwrap]: checked above
+// ratchets-ignore-next-line[no-unwrap, no-expect]: constant input
+let b = y.expect(\"b\");
+/* ratchets-ignore-file[no-print]: CLI front end */
+";
+        let suppressions = Suppressions::parse(content, Some(Language::Rust), None);
+        assert!(suppressions.suppresses(&violation("no-unwrap", 1)?));
+        assert!(suppressions.suppresses(&violation("no-expect", 3)?));
+        assert!(suppressions.suppresses(&violation("no-unwrap", 3)?));
+        assert!(suppressions.suppresses(&violation("no-print", 40)?));
+        assert!(!suppressions.suppresses(&violation("no-expect", 1)?));
+        assert!(!suppressions.suppresses(&violation("no-unwrap", 2)?));
+        assert!(!suppressions.suppresses(&violation("no-unwrap", 4)?));
+        assert!(suppressions.covers(&RuleId::new("no-expect").ok_or("invalid rule id")?));
+
+        let ctx = ExecutionContext {
+            file_path: Path::new("src/main.rs"),
+            content,
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        };
+        assert!(suppressions.unjustified(&ctx).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_directives_require_comment_marker() {
+        let content = "print(\"ratchets-ignore[no-print]: text\")\n";
+        assert!(Suppressions::parse(content, Some(Language::Python), None).is_empty());
+        assert!(Suppressions::parse("x  # ratchets-ignore[no-print]: ok\n", None, None).is_empty());
+
+        // Rust comments do not start with `#`
+        let content = "#[ratchets-ignore[no-unwrap]: attribute]\n";
+        assert!(Suppressions::parse(content, Some(Language::Rust), None).is_empty());
+
+        // Malformed rule lists are not directives
+        let content = "# ratchets-ignore no-print\n# ratchets-ignore[bad id]: reason\n";
+        assert!(Suppressions::parse(content, Some(Language::Python), None).is_empty());
+    }
+
+    #[cfg(feature = "lang-python")]
+    #[test]
+    fn test_directives_in_strings_are_ignored() -> Result<(), Box<dyn std::error::Error>> {
+        let content = concat!(
+            "s = \"# ratchets-",
+            "ignore[hack-code]: x\"; HACK = 2\n",
+            "t = 1  # ratchets-",
+            "ignore[hack-code]: generated\n",
+        );
+        let mut parser = ParserCache::new().get_parser(Language::Python)?;
+        let tree = parser.parse(content, None).ok_or("parse failed")?;
+
+        let suppressions = Suppressions::parse(content, Some(Language::Python), Some(&tree));
+        assert!(!suppressions.suppresses(&violation("hack-code", 1)?));
+        assert!(suppressions.suppresses(&violation("hack-code", 2)?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_reason_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "x = 1\nprint(x)  # ratchets-ignore[no-print]\nprint(x)  # ratchets-ignore[no-print]:  \n";
+        let suppressions = Suppressions::parse(content, Some(Language::Python), None);
+        assert!(suppressions.suppresses(&violation("no-print", 2)?));
+
+        let ctx = ExecutionContext {
+            file_path: Path::new("src/app.py"),
+            content,
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        };
+        let unjustified = suppressions.unjustified(&ctx);
+        assert_eq!(unjustified.len(), 2);
+        assert_eq!(unjustified[0].rule_id.as_str(), UNJUSTIFIED_SUPPRESSION);
+        assert_eq!((unjustified[0].line, unjustified[0].column), (2, 13));
+        assert_eq!(unjustified[0].snippet, "ratchets-ignore[no-print]");
+        assert_eq!(unjustified[0].end_column, 38);
+        assert_eq!(unjustified[1].line, 3);
+        assert_eq!(unjustified[0].region.as_str(), "src");
+        Ok(())
+    }
+}
@@ -63,6 +63,10 @@ pub enum RuleError {
     #[error("Invalid tree-sitter query: {0}")]
     InvalidQuery(String),
 
+    /// An external rule's command failed, timed out or printed invalid output
+    #[error("Rule '{rule}' failed: {message}")]
+    Execution { rule: String, message: String },
+
     /// Failed to resolve `enabled_ratchets` / `disabled_ratchets` against
     /// the [`crate::config::SetRegistry`].
     ///
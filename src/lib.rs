@@ -5,6 +5,7 @@
 //! Ratchets is a progressive lint enforcement tool that allows codebases to contain
 //! existing violations while preventing new ones.
 
+pub mod adapters;
 pub mod cli;
 pub mod config;
 pub mod engine;
@@ -1,7 +1,7 @@
 //! Ratchet CLI entry point
 
 use clap::Parser;
-use ratchets::cli::{Command, args::Cli};
+use ratchets::cli::{CacheCommand, Command, args::Cli};
 use std::process;
 
 fn main() {
@@ -33,17 +33,63 @@ fn main() {
             format,
             verbose,
             since,
-        } => ratchets::cli::check::run_check(&paths, format, verbose, since.as_deref()),
+            no_cache,
+            count_only,
+            imports,
+        } => ratchets::cli::check::run_check(
+            &paths,
+            format,
+            verbose,
+            since.as_deref(),
+            no_cache,
+            count_only,
+            &imports,
+        ),
         Command::Bump {
             rule_id,
             region,
             count,
             all,
-        } => ratchets::cli::bump::run_bump(rule_id.as_deref(), &region, count, all),
-        Command::Tighten { rule_id, region } => {
-            ratchets::cli::tighten::run_tighten(rule_id.as_deref(), region.as_deref())
+            no_cache,
+            count_only,
+            suppressed,
+        } => ratchets::cli::bump::run_bump(
+            rule_id.as_deref(),
+            &region,
+            count,
+            all,
+            no_cache,
+            count_only,
+            suppressed,
+        ),
+        Command::Tighten {
+            rule_id,
+            region,
+            no_cache,
+            count_only,
+        } => ratchets::cli::tighten::run_tighten(
+            rule_id.as_deref(),
+            region.as_deref(),
+            no_cache,
+            count_only,
+        ),
+        Command::Fix {
+            rule_id,
+            paths,
+            dry_run,
+        } => ratchets::cli::fix::run_fix(rule_id.as_deref(), &paths, dry_run),
+        Command::TestRules { rule_id } => {
+            ratchets::cli::test_rules::run_test_rules(rule_id.as_deref())
+        }
+        Command::Explain { rule_id, format } => {
+            ratchets::cli::explain::run_explain(&rule_id, format)
         }
         Command::List { format } => ratchets::cli::list::run_list(format),
+        Command::Watch { paths, interval } => ratchets::cli::watch::run_watch(&paths, interval),
+        Command::Lsp => ratchets::cli::lsp::run_lsp(),
+        Command::Cache { command } => match command {
+            CacheCommand::Clean => ratchets::cli::cache::run_cache_clean(),
+        },
         Command::MergeDriver {
             base,
             current,
@@ -2,10 +2,14 @@
 
 pub mod human;
 pub mod jsonl;
+pub mod rule_explanation;
 pub mod rule_status;
 
 pub use human::HumanFormatter;
 pub use jsonl::JsonlFormatter;
+pub use rule_explanation::{
+    RegionStanding, RuleExplanation, RuleExplanationHumanFormatter, RuleExplanationJsonlFormatter,
+};
 pub use rule_status::{
     CheckStatus, RuleSource, RuleStatus, RuleStatusHumanFormatter, RuleStatusJsonlFormatter,
 };
@@ -80,6 +80,33 @@ impl HumanFormatter {
             }
         }
 
+        // Violations suppressed by inline comments, which stay visible
+        if !result.suppressed.is_empty() {
+            output.push_str("Suppressed:\n\n");
+            for suppressed in &result.suppressed {
+                let symbol = if suppressed.passed { "✓" } else { "✗" };
+                let mut status_text = format!("{} suppressed", suppressed.count);
+                match suppressed.budget {
+                    Some(budget) => {
+                        status_text.push_str(&format!(" (budget: {})", budget));
+                        if !suppressed.passed {
+                            let exceeded = suppressed.count - budget;
+                            status_text.push_str(&format!(" exceeded by {}", exceeded));
+                        }
+                    }
+                    None => status_text.push_str(" (no budget)"),
+                }
+                output.push_str(&format!(
+                    "  {} {} [{}]: {}\n",
+                    symbol,
+                    suppressed.rule_id.as_str(),
+                    suppressed.region.as_str(),
+                    status_text
+                ));
+            }
+            output.push('\n');
+        }
+
         // Summary section
         if !result.statuses.is_empty() {
             output.push_str("Summary:\n\n");
@@ -109,20 +136,20 @@ impl HumanFormatter {
             }
 
             output.push('\n');
+        }
 
-            // Final check status
-            if result.passed {
-                output.push_str("Check PASSED\n");
-            } else {
-                let rules_exceeded = result.statuses.iter().filter(|s| !s.passed).count();
-                output.push_str(&format!(
-                    "Check FAILED: {} rule{} exceeded budget\n",
-                    rules_exceeded,
-                    if rules_exceeded == 1 { "" } else { "s" }
-                ));
-            }
-        } else {
+        // Final check status, which suppressed counts alone can fail
+        if result.statuses.is_empty() && result.passed {
             output.push_str("No violations found\n");
+        } else if result.passed {
+            output.push_str("Check PASSED\n");
+        } else {
+            let rules_exceeded = result.exceeded_count();
+            output.push_str(&format!(
+                "Check FAILED: {} rule{} exceeded budget\n",
+                rules_exceeded,
+                if rules_exceeded == 1 { "" } else { "s" }
+            ));
         }
 
         output
@@ -204,6 +231,55 @@ impl HumanFormatter {
             }
         }
 
+        // Violations suppressed by inline comments, which stay visible
+        if !result.suppressed.is_empty() {
+            stdout.set_color(ColorSpec::new().set_bold(true))?;
+            writeln!(stdout, "Suppressed:")?;
+            stdout.reset()?;
+            writeln!(stdout)?;
+            for suppressed in &result.suppressed {
+                write!(stdout, "  ")?;
+
+                if suppressed.passed {
+                    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+                    write!(stdout, "✓")?;
+                } else {
+                    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+                    write!(stdout, "✗")?;
+                }
+                stdout.reset()?;
+
+                write!(
+                    stdout,
+                    " {} [{}]: ",
+                    suppressed.rule_id.as_str(),
+                    suppressed.region.as_str()
+                )?;
+                stdout.set_color(ColorSpec::new().set_bold(true))?;
+                write!(stdout, "{}", suppressed.count)?;
+                stdout.reset()?;
+                let Some(budget) = suppressed.budget else {
+                    writeln!(stdout, " suppressed (no budget)")?;
+                    continue;
+                };
+                write!(stdout, " suppressed (budget: ")?;
+                stdout.set_color(ColorSpec::new().set_bold(true))?;
+                write!(stdout, "{}", budget)?;
+                stdout.reset()?;
+                write!(stdout, ")")?;
+                if !suppressed.passed {
+                    let exceeded = suppressed.count - budget;
+                    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+                    write!(stdout, " exceeded by ")?;
+                    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+                    write!(stdout, "{}", exceeded)?;
+                    stdout.reset()?;
+                }
+                writeln!(stdout)?;
+            }
+            writeln!(stdout)?;
+        }
+
         // Summary section
         if !result.statuses.is_empty() {
             stdout.set_color(ColorSpec::new().set_bold(true))?;
@@ -259,29 +335,28 @@ impl HumanFormatter {
             }
 
             writeln!(stdout)?;
+        }
 
-            // Final check status
-            if result.passed {
-                stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
-                writeln!(stdout, "Check PASSED")?;
-            } else {
-                let rules_exceeded = result.statuses.iter().filter(|s| !s.passed).count();
-                stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
-                write!(
-                    stdout,
-                    "Check FAILED: {} rule{} exceeded budget",
-                    rules_exceeded,
-                    if rules_exceeded == 1 { "" } else { "s" }
-                )?;
-                stdout.reset()?;
-                writeln!(stdout)?;
-            }
-            stdout.reset()?;
-        } else {
+        // Final check status, which suppressed counts alone can fail
+        if result.statuses.is_empty() && result.passed {
             stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
             writeln!(stdout, "No violations found")?;
+        } else if result.passed {
+            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
+            writeln!(stdout, "Check PASSED")?;
+        } else {
+            let rules_exceeded = result.exceeded_count();
+            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+            write!(
+                stdout,
+                "Check FAILED: {} rule{} exceeded budget",
+                rules_exceeded,
+                if rules_exceeded == 1 { "" } else { "s" }
+            )?;
             stdout.reset()?;
+            writeln!(stdout)?;
         }
+        stdout.reset()?;
 
         Ok(())
     }
@@ -290,6 +365,7 @@ impl HumanFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::aggregator::SuppressedCount;
     use crate::rules::Violation;
     use crate::types::{RegionPath, RuleId};
     use std::path::PathBuf;
@@ -339,6 +415,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -361,6 +438,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -375,6 +453,55 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_format_suppressed_counts() -> Result<(), Box<dyn std::error::Error>> {
+        let formatter = HumanFormatter::new(ColorChoice::Never);
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+            suppressed: vec![SuppressedCount {
+                rule_id: RuleId::new("no-unwrap").ok_or("invalid rule id")?,
+                region: RegionPath::new("src"),
+                count: 3,
+                budget: Some(3),
+                passed: true,
+            }],
+        };
+
+        let output = formatter.format(&result, false);
+        assert!(output.contains("Suppressed:\n\n  ✓ no-unwrap [src]: 3 suppressed (budget: 3)\n"));
+        assert!(output.contains("No violations found"));
+
+        // Rules without suppressed budgets allow any number of suppressions
+        let unbudgeted = AggregationResult {
+            suppressed: vec![SuppressedCount {
+                budget: None,
+                ..result.suppressed[0].clone()
+            }],
+            ..result.clone()
+        };
+        let output = formatter.format(&unbudgeted, false);
+        assert!(output.contains("✓ no-unwrap [src]: 3 suppressed (no budget)\n"));
+
+        // Suppressions over budget fail the check on their own
+        let result = AggregationResult {
+            passed: false,
+            violations_over_budget: 1,
+            suppressed: vec![SuppressedCount {
+                budget: Some(2),
+                passed: false,
+                ..result.suppressed[0].clone()
+            }],
+            ..result
+        };
+        let output = formatter.format(&result, false);
+        assert!(output.contains("✗ no-unwrap [src]: 3 suppressed (budget: 2) exceeded by 1\n"));
+        assert!(output.contains("Check FAILED: 1 rule exceeded budget"));
+        Ok(())
+    }
+
     #[test]
     fn test_format_multiple_violations_same_rule() -> Result<(), Box<dyn std::error::Error>> {
         let formatter = HumanFormatter::new(ColorChoice::Never);
@@ -388,6 +515,7 @@ mod tests {
             passed: true,
             total_violations: 2,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -414,6 +542,7 @@ mod tests {
             passed: false,
             total_violations: 2,
             violations_over_budget: 1,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -450,6 +579,7 @@ mod tests {
             passed: true,
             total_violations: 2,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -485,6 +615,7 @@ mod tests {
             passed: false,
             total_violations: 5,
             violations_over_budget: 1,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -516,6 +647,7 @@ mod tests {
             passed: false,
             total_violations: 4,
             violations_over_budget: 3,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -539,6 +671,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let _ = formatter.write_to_stdout(&result, true);
@@ -580,6 +713,7 @@ mod tests {
             passed: true,
             total_violations: 2,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -618,6 +752,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -647,6 +782,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -678,6 +814,7 @@ mod tests {
             passed: true,
             total_violations: 2,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output1 = formatter.format(&result, true);
@@ -705,6 +842,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -728,6 +866,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -751,6 +890,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -777,6 +917,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -805,6 +946,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -825,6 +967,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -853,6 +996,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, false);
@@ -889,6 +1033,7 @@ mod tests {
             passed: false,
             total_violations: 5,
             violations_over_budget: 1,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, false);
@@ -5,7 +5,8 @@
 //! Outputs one JSON object per line in a deterministic order:
 //! 1. All violation records (sorted by rule, file, line)
 //! 2. All summary records (sorted by rule, region)
-//! 3. One status record
+//! 3. All suppressed records (sorted by rule, region)
+//! 4. One status record
 
 use crate::engine::aggregator::AggregationResult;
 use serde::Serialize;
@@ -27,6 +28,7 @@ impl JsonlFormatter {
     /// Returns a string with one JSON object per line:
     /// - First: All violation records (sorted by rule, file, line) - only if verbose is true
     /// - Then: All summary records (sorted by rule, region)
+    /// - Then: All suppressed records (sorted by rule, region)
     /// - Finally: One status record
     ///
     /// # Arguments
@@ -93,7 +95,23 @@ impl JsonlFormatter {
             }
         }
 
-        let rules_exceeded = result.statuses.iter().filter(|s| !s.passed).count() as u64;
+        // Suppressed counts are already sorted by rule, then region
+        for suppressed in &result.suppressed {
+            let record = SuppressedRecord {
+                record_type: "suppressed".to_string(),
+                rule: suppressed.rule_id.as_str().to_string(),
+                region: suppressed.region.as_str().to_string(),
+                count: suppressed.count,
+                budget: suppressed.budget,
+                status: if suppressed.passed { "pass" } else { "fail" }.to_string(),
+            };
+            if let Ok(json) = serde_json::to_string(&record) {
+                output.push_str(&json);
+                output.push('\n');
+            }
+        }
+
+        let rules_exceeded = result.exceeded_count() as u64;
         let status = StatusRecord {
             record_type: "status".to_string(),
             passed: result.passed,
@@ -145,6 +163,19 @@ struct SummaryRecord {
     status: String,
 }
 
+/// Suppressed record for JSONL output
+#[derive(Debug, Serialize)]
+struct SuppressedRecord {
+    #[serde(rename = "type")]
+    record_type: String,
+    rule: String,
+    region: String,
+    count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    budget: Option<u64>,
+    status: String,
+}
+
 /// Status record for JSONL output
 #[derive(Debug, Serialize)]
 struct StatusRecord {
@@ -159,7 +190,7 @@ struct StatusRecord {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::aggregator::RuleRegionStatus;
+    use crate::engine::aggregator::{RuleRegionStatus, SuppressedCount};
     use crate::rules::Violation;
     use crate::types::{RegionPath, RuleId};
     use std::path::PathBuf;
@@ -211,6 +242,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -245,6 +277,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -299,6 +332,7 @@ mod tests {
             passed: true,
             total_violations: 2,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -347,6 +381,7 @@ mod tests {
             passed: false,
             total_violations: 2,
             violations_over_budget: 1,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -405,6 +440,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -449,6 +485,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -468,6 +505,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -490,6 +528,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -548,6 +587,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -621,6 +661,7 @@ mod tests {
             passed: true,
             total_violations: 4,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -670,6 +711,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output1 = formatter.format(&result, true);
@@ -729,6 +771,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -770,6 +813,7 @@ mod tests {
             passed: true,
             total_violations: 1,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -820,6 +864,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -850,6 +895,7 @@ mod tests {
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -887,6 +933,7 @@ mod tests {
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, true);
@@ -931,6 +978,7 @@ mod tests {
             passed: true,
             total_violations: 2,
             violations_over_budget: 0,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, false);
@@ -958,6 +1006,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_format_suppressed_records() -> Result<(), Box<dyn std::error::Error>> {
+        let formatter = JsonlFormatter::new();
+        let status = create_test_status("no-unwrap", "src", 1, 5, vec![])?;
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: false,
+            total_violations: 1,
+            violations_over_budget: 1,
+            suppressed: vec![SuppressedCount {
+                rule_id: RuleId::new("no-unwrap").ok_or("invalid rule id")?,
+                region: RegionPath::new("src"),
+                count: 2,
+                budget: Some(1),
+                passed: false,
+            }],
+        };
+
+        let output = formatter.format(&result, false);
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "summary");
+        assert_eq!(
+            lines[1],
+            serde_json::json!({
+                "type": "suppressed",
+                "rule": "no-unwrap",
+                "region": "src",
+                "count": 2,
+                "budget": 1,
+                "status": "fail",
+            })
+        );
+        assert_eq!(lines[2]["type"], "status");
+        assert_eq!(lines[2]["rules_exceeded"], 1);
+        Ok(())
+    }
+
     #[test]
     fn test_format_non_verbose_preserves_summary_records() -> Result<(), Box<dyn std::error::Error>>
     {
@@ -985,6 +1074,7 @@ mod tests {
             passed: false,
             total_violations: 3,
             violations_over_budget: 1,
+            suppressed: Vec::new(),
         };
 
         let output = formatter.format(&result, false);
@@ -0,0 +1,235 @@
+#![forbid(unsafe_code)]
+
+//! RuleExplanation output formatters
+//!
+//! This module provides formatters for the rule details and per-region
+//! standing printed by the `ratchets explain` command. It supports both
+//! human-readable and JSONL output formats.
+
+use crate::output::CheckStatus;
+use serde::Serialize;
+
+/// Violations and budget of a rule in one region
+#[derive(Debug, Clone, Serialize)]
+pub struct RegionStanding {
+    pub region: String,
+    pub violations: u64,
+    pub budget: u64,
+    pub status: CheckStatus,
+}
+
+/// Everything `ratchets explain` reports about a rule
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleExplanation {
+    pub rule_id: String,
+    pub description: String,
+    pub severity: String,
+    pub languages: Vec<String>,
+    pub rationale: Option<String>,
+    pub help_url: Option<String>,
+    pub good: Option<String>,
+    pub bad: Option<String>,
+    /// Regex pattern, for regex rules
+    pub pattern: Option<String>,
+    /// Tree-sitter query, for AST rules
+    pub query: Option<String>,
+    /// Sets that include the rule, directly or through composition
+    pub sets: Vec<String>,
+    /// Whether the rule is in the resolved set of ratchets.toml
+    pub enabled: bool,
+    /// Standing per region, worst first
+    pub regions: Vec<RegionStanding>,
+}
+
+/// Human-readable formatter for rule explanations
+pub struct RuleExplanationHumanFormatter;
+
+impl RuleExplanationHumanFormatter {
+    /// Create a new human formatter
+    pub fn new() -> Self {
+        RuleExplanationHumanFormatter
+    }
+
+    /// Format a rule explanation for human consumption
+    pub fn format(&self, explanation: &RuleExplanation) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("{}\n", explanation.rule_id));
+        output.push_str(&format!("  Description: {}\n", explanation.description));
+        output.push_str(&format!("  Severity: {}\n", explanation.severity));
+        let languages = if explanation.languages.is_empty() {
+            "all".to_string()
+        } else {
+            explanation.languages.join(", ")
+        };
+        output.push_str(&format!("  Languages: {}\n", languages));
+        let sets = if explanation.sets.is_empty() {
+            "none".to_string()
+        } else {
+            explanation.sets.join(", ")
+        };
+        output.push_str(&format!("  Sets: {}\n", sets));
+        output.push_str(&format!(
+            "  Enabled: {}\n",
+            if explanation.enabled { "yes" } else { "no" }
+        ));
+
+        if let Some(ref help_url) = explanation.help_url {
+            output.push_str(&format!("  Help: {}\n", help_url));
+        }
+        push_block(&mut output, "Rationale", explanation.rationale.as_deref());
+        push_block(&mut output, "Bad", explanation.bad.as_deref());
+        push_block(&mut output, "Good", explanation.good.as_deref());
+        push_block(&mut output, "Pattern", explanation.pattern.as_deref());
+        push_block(&mut output, "Query", explanation.query.as_deref());
+
+        output.push('\n');
+        if explanation.regions.is_empty() {
+            output.push_str("No budgets configured and no violations found.\n");
+            return output;
+        }
+
+        let violations: u64 = explanation.regions.iter().map(|r| r.violations).sum();
+        let budget: u64 = explanation.regions.iter().map(|r| r.budget).sum();
+        output.push_str(&format!(
+            "Regions ({} violations, budget {}):\n",
+            violations, budget
+        ));
+        let width = explanation
+            .regions
+            .iter()
+            .map(|r| r.region.len())
+            .max()
+            .unwrap_or(0);
+        for standing in &explanation.regions {
+            let status_text = match standing.status {
+                CheckStatus::Pass => "✓".to_string(),
+                CheckStatus::OverBudget => format!(
+                    "✗ exceeded by {}",
+                    standing.violations.saturating_sub(standing.budget)
+                ),
+            };
+            output.push_str(&format!(
+                "  {:<width$}  {} / {}  {}\n",
+                standing.region, standing.violations, standing.budget, status_text
+            ));
+        }
+
+        output
+    }
+
+    /// Write the formatted output to stdout
+    pub fn write_to_stdout(&self, explanation: &RuleExplanation) {
+        print!("{}", self.format(explanation));
+    }
+}
+
+impl Default for RuleExplanationHumanFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Append a labelled, indented block of code or pattern text
+fn push_block(output: &mut String, label: &str, text: Option<&str>) {
+    let Some(text) = text else {
+        return;
+    };
+    output.push_str(&format!("  {}:\n", label));
+    for line in text.trim_matches('\n').lines() {
+        output.push_str(&format!("    {}\n", line));
+    }
+}
+
+/// JSONL formatter for rule explanations
+pub struct RuleExplanationJsonlFormatter;
+
+impl RuleExplanationJsonlFormatter {
+    /// Create a new JSONL formatter
+    pub fn new() -> Self {
+        RuleExplanationJsonlFormatter
+    }
+
+    /// Format a rule explanation as a single JSON line
+    pub fn format(&self, explanation: &RuleExplanation) -> String {
+        match serde_json::to_string(explanation) {
+            Ok(json) => format!("{}\n", json),
+            Err(_) => String::new(),
+        }
+    }
+
+    /// Write the formatted output to stdout
+    pub fn write_to_stdout(&self, explanation: &RuleExplanation) {
+        print!("{}", self.format(explanation));
+    }
+}
+
+impl Default for RuleExplanationJsonlFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_explanation() -> RuleExplanation {
+        RuleExplanation {
+            rule_id: "no-unwrap".to_string(),
+            description: "Disallow .unwrap()".to_string(),
+            severity: "error".to_string(),
+            languages: vec!["rust".to_string()],
+            rationale: Some("Unwrap panics on bad input.".to_string()),
+            help_url: None,
+            good: Some("let x = y?;\n".to_string()),
+            bad: Some("let x = y.unwrap();\n".to_string()),
+            pattern: Some(r"\.unwrap\(\)".to_string()),
+            query: None,
+            sets: vec!["rust-starter".to_string()],
+            enabled: true,
+            regions: vec![
+                RegionStanding {
+                    region: "src/legacy".to_string(),
+                    violations: 12,
+                    budget: 10,
+                    status: CheckStatus::OverBudget,
+                },
+                RegionStanding {
+                    region: ".".to_string(),
+                    violations: 1,
+                    budget: 3,
+                    status: CheckStatus::Pass,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_human_formatter() {
+        let output = RuleExplanationHumanFormatter::new().format(&create_test_explanation());
+        assert!(output.starts_with("no-unwrap\n"));
+        assert!(output.contains("  Sets: rust-starter\n"));
+        assert!(output.contains("  Rationale:\n    Unwrap panics on bad input.\n"));
+        assert!(output.contains("  Bad:\n    let x = y.unwrap();\n"));
+        assert!(output.contains("  Pattern:\n    \\.unwrap\\(\\)\n"));
+        assert!(!output.contains("Query:"));
+        assert!(output.contains("Regions (13 violations, budget 13):\n"));
+        assert!(output.contains("  src/legacy  12 / 10  ✗ exceeded by 2\n"));
+        assert!(output.contains("  .           1 / 3  ✓\n"));
+    }
+
+    #[test]
+    fn test_jsonl_formatter() -> Result<(), Box<dyn std::error::Error>> {
+        let output = RuleExplanationJsonlFormatter::new().format(&create_test_explanation());
+        assert_eq!(output.lines().count(), 1);
+
+        let json: serde_json::Value = serde_json::from_str(output.trim())?;
+        assert_eq!(json["rule_id"], "no-unwrap");
+        assert_eq!(json["enabled"], true);
+        assert_eq!(json["query"], serde_json::Value::Null);
+        assert_eq!(json["regions"][0]["region"], "src/legacy");
+        assert_eq!(json["regions"][0]["status"], "over_budget");
+        Ok(())
+    }
+}
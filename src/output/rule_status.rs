@@ -24,6 +24,12 @@ impl CheckStatus {
     }
 }
 
+impl Serialize for CheckStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 /// Source of a rule (builtin or custom)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuleSource {
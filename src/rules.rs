@@ -4,13 +4,34 @@
 
 mod ast;
 mod builtin;
+mod docs;
+mod external_rule;
+mod file_rule;
+mod import_rule;
+mod message;
 mod regex_rule;
 mod registry;
 mod rule;
+mod scope;
+mod self_test;
+mod suppression_rule;
+mod template;
 
 // Re-export core types
 pub use ast::{AstRule, ParserCache};
-pub use builtin::{load_builtin_ast_rules, load_builtin_regex_rules, load_builtin_sets};
+pub use builtin::{
+    load_builtin_ast_rules, load_builtin_regex_rules, load_builtin_sets, load_builtin_templates,
+};
+pub use docs::RuleDocs;
+pub use external_rule::ExternalRule;
+pub use file_rule::FileRule;
+pub use import_rule::ImportRule;
 pub use regex_rule::RegexRule;
+pub(crate) use regex_rule::compute_line_offsets;
 pub use registry::RuleRegistry;
-pub use rule::{AstPlaceholder, ExecutionContext, RegionResolver, Rule, RuleContext, Violation};
+pub use rule::{ExecutionContext, Fix, RegionResolver, Rule, RuleContext, RuleKind, Violation};
+pub(crate) use rule::{StableHasher, normalize_for_glob_match};
+pub(crate) use scope::MatchScope;
+pub use self_test::{Expectation, RuleTest};
+pub use suppression_rule::{UNJUSTIFIED_SUPPRESSION, UnjustifiedSuppressionRule};
+pub use template::{RuleTemplate, TemplateKind, TemplateRegistry};
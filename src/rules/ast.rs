@@ -3,7 +3,11 @@
 //! AST-based rule execution using tree-sitter
 
 mod ast_rule;
+mod imports;
+mod metric;
 mod parser_cache;
+mod predicate;
 
 pub use ast_rule::AstRule;
+pub(crate) use imports::{Import, extract_imports};
 pub use parser_cache::ParserCache;
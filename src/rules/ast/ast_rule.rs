@@ -3,17 +3,26 @@
 //! AST-based rule implementation using tree-sitter queries
 //!
 //! This module provides AstRule, which uses tree-sitter queries to match
-//! patterns in parsed abstract syntax trees.
+//! patterns in parsed abstract syntax trees. A rule may apply one query to
+//! several languages whose grammars share node types, such as JavaScript and
+//! TypeScript; the query is compiled for each of them.
 
 use crate::error::RuleError;
 use crate::rules::ast::ParserCache;
-use crate::rules::rule::normalize_for_glob_match;
-use crate::rules::{ExecutionContext, RegionResolver, Rule, RuleContext, Violation};
+use crate::rules::ast::metric::{METRIC_PLACEHOLDERS, Metric, MetricSection};
+use crate::rules::ast::predicate::{Predicate, WhereSection, post_filter_sections};
+use crate::rules::message::MessageTemplate;
+use crate::rules::rule::{definition_hash, normalize_for_glob_match};
+use crate::rules::self_test::TestSection;
+use crate::rules::{
+    ExecutionContext, Fix, RegionResolver, Rule, RuleContext, RuleDocs, RuleKind, RuleTest,
+    Violation,
+};
 use crate::types::{GlobPattern, Language, RegionPath, RuleId, Severity};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use serde::Deserialize;
 use std::path::Path;
-use tree_sitter::{Query, QueryCursor, Tree};
+use tree_sitter::{Node, Query, QueryCapture, QueryCursor, Tree};
 
 /// TOML structure for AST rule definitions
 ///
@@ -24,6 +33,12 @@ struct AstRuleDefinition {
     rule: RuleSection,
     #[serde(rename = "match")]
     match_section: MatchSection,
+    #[serde(default)]
+    fix: Option<FixSection>,
+    #[serde(default)]
+    metric: Option<MetricSection>,
+    #[serde(default, rename = "test")]
+    tests: Vec<TestSection>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -31,6 +46,10 @@ struct RuleSection {
     id: String,
     description: String,
     severity: Severity,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(flatten)]
+    docs: RuleDocs,
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,54 +59,160 @@ enum GlobPatternList {
     Multiple(Vec<String>),
 }
 
+/// One language, or a list of languages sharing the query
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LanguageList {
+    Single(Language),
+    Multiple(Vec<Language>),
+}
+
+impl LanguageList {
+    /// The languages in order, without duplicates
+    fn into_vec(self) -> Vec<Language> {
+        match self {
+            LanguageList::Single(language) => vec![language],
+            LanguageList::Multiple(languages) => {
+                let mut unique = Vec::with_capacity(languages.len());
+                for language in languages {
+                    if !unique.contains(&language) {
+                        unique.push(language);
+                    }
+                }
+                unique
+            }
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct MatchSection {
     query: String,
-    language: Language,
+    language: LanguageList,
     #[serde(default)]
     include: Option<GlobPatternList>,
     #[serde(default)]
     exclude: Option<GlobPatternList>,
     #[serde(default)]
     post_filter: Option<String>,
+    #[serde(default, rename = "where")]
+    where_sections: Vec<WhereSection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixSection {
+    replacement: String,
+}
+
+/// A rule's query compiled for one language's grammar
+///
+/// Capture indices differ between grammars, so the reported capture,
+/// predicates and metric are resolved against each compiled query.
+struct LanguageQuery {
+    language: Language,
+    grammar: tree_sitter::Language,
+    query: Query,
+    violation_capture_idx: usize,
+    predicates: Vec<Predicate>,
+    metric: Option<Metric>,
+}
+
+impl LanguageQuery {
+    /// Compile the query, `[[match.where]]` predicates and metric for `language`
+    fn compile(
+        source: &str,
+        language: Language,
+        where_sections: &[WhereSection],
+        metric: Option<&MetricSection>,
+    ) -> Result<Self, RuleError> {
+        let parser_cache = ParserCache::new();
+        let parser = parser_cache
+            .get_parser(language)
+            .map_err(|e| RuleError::InvalidQuery(format!("Failed to get parser: {}", e)))?;
+        let grammar = parser
+            .language()
+            .ok_or_else(|| RuleError::InvalidQuery("Parser language not configured".to_string()))?;
+        let query = Query::new(&grammar, source)
+            .map_err(|e| RuleError::InvalidQuery(format!("Failed to compile query: {}", e)))?;
+
+        // Find the @violation capture index, or use 0 if not found
+        let violation_capture_idx = query
+            .capture_names()
+            .iter()
+            .position(|name| *name == "violation")
+            .unwrap_or(0);
+
+        let predicates = where_sections
+            .iter()
+            .cloned()
+            .map(|section| Predicate::compile(section, &query))
+            .collect::<Result<Vec<_>, _>>()?;
+        let metric = metric
+            .cloned()
+            .map(|section| Metric::compile(section, &query))
+            .transpose()?;
+
+        Ok(Self {
+            language,
+            grammar,
+            query,
+            violation_capture_idx,
+            predicates,
+            metric,
+        })
+    }
+
+    /// Returns true if the query has a capture with this name
+    fn has_capture(&self, name: &str) -> bool {
+        self.query.capture_names().contains(&name)
+    }
+
+    /// Look up the text of a named capture in a match
+    fn capture_text<'c>(
+        &self,
+        captures: &[QueryCapture],
+        content: &'c str,
+        name: &str,
+    ) -> Option<&'c str> {
+        let index = self.query.capture_index_for_name(name)?;
+        let capture = captures.iter().find(|c| c.index == index)?;
+        content.get(capture.node.byte_range())
+    }
+}
+
+impl std::fmt::Debug for LanguageQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LanguageQuery")
+            .field("language", &self.language)
+            .field("predicates", &self.predicates)
+            .field("metric", &self.metric)
+            .finish()
+    }
 }
 
 /// A rule that matches AST patterns using tree-sitter queries
 ///
-/// AstRule compiles a tree-sitter query and executes it against parsed ASTs,
-/// reporting matches at the @violation capture (or the first capture if @violation is not present).
+/// AstRule compiles its tree-sitter query once per language at load time and
+/// executes it against parsed ASTs, reporting matches at the @violation capture (or the
+/// first capture if @violation is not present). Violation messages interpolate
+/// the match's capture texts into the rule's message template, if it has one.
+/// A rule with a fix replaces the reported node with its replacement template,
+/// interpolating captures the same way. A rule with a metric reports only the
+/// matches whose measurement exceeds its threshold.
 pub struct AstRule {
     id: RuleId,
     description: String,
     severity: Severity,
+    message: Option<MessageTemplate>,
     query_source: String,
-    language: Language,
+    languages: Vec<Language>,
+    queries: Vec<LanguageQuery>,
     include: Option<GlobSet>,
     exclude: Option<GlobSet>,
-    post_filter: Option<PostFilter>,
-}
-
-/// Post-filter function for additional violation filtering
-///
-/// Some rules require filtering based on captured node text, which tree-sitter
-/// queries cannot express (e.g., negative string matching).
-#[derive(Debug, Clone, Copy)]
-enum PostFilter {
-    /// Filter out classes whose names end with "Exception" or "Error"
-    ClassNameNotException,
-    /// Keep only matches whose `@violation` capture is nested inside an enclosing
-    /// `function_definition` (without crossing a `class_definition` first).
-    ///
-    /// Walks up the captured node's parent chain. Returns `true` (keep the
-    /// violation) when a `function_definition` ancestor is reached before any
-    /// `class_definition`. Returns `false` (drop) if a `class_definition` is
-    /// encountered first or no enclosing function is found.
-    ///
-    /// This catches inline functions whose AST structure is more than one level
-    /// removed from the enclosing function's body block — for example, functions
-    /// wrapped in a `decorated_definition` node, or nested inside `if`, `with`,
-    /// `for`, `try`, etc. statements within the enclosing function.
-    NestedInFunctionDefinition,
+    definition_hash: u64,
+    fix: Option<MessageTemplate>,
+    tests: Vec<RuleTest>,
+    docs: RuleDocs,
 }
 
 impl std::fmt::Debug for AstRule {
@@ -96,11 +221,14 @@ impl std::fmt::Debug for AstRule {
             .field("id", &self.id)
             .field("description", &self.description)
             .field("severity", &self.severity)
+            .field("message", &self.message)
             .field("query_source", &self.query_source)
-            .field("language", &self.language)
+            .field("queries", &self.queries)
             .field("include", &"<GlobSet>")
             .field("exclude", &"<GlobSet>")
-            .field("post_filter", &self.post_filter)
+            .field("fix", &self.fix)
+            .field("tests", &self.tests)
+            .field("docs", &self.docs)
             .finish()
     }
 }
@@ -114,6 +242,9 @@ impl AstRule {
     /// - TOML syntax is invalid
     /// - Required fields are missing
     /// - Rule ID is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message or fix template is invalid or names an unknown capture
+    /// - The `[metric]` table is invalid
     /// - Glob patterns are invalid
     ///
     /// Returns `RuleError::InvalidQuery` if the tree-sitter query is invalid
@@ -132,6 +263,9 @@ impl AstRule {
     /// - TOML syntax is invalid
     /// - Required fields are missing
     /// - Rule ID is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message or fix template is invalid or names an unknown capture
+    /// - The `[metric]` table is invalid
     /// - Glob patterns are invalid
     /// - A pattern reference is not found in the context
     ///
@@ -149,11 +283,72 @@ impl AstRule {
             RuleError::InvalidDefinition(format!("Invalid rule ID: {}", def.rule.id))
         })?;
 
-        // Store query source for later compilation
+        let languages = def.match_section.language.into_vec();
+        if languages.is_empty() {
+            return Err(RuleError::InvalidDefinition(
+                "[match] language must name at least one language".to_string(),
+            ));
+        }
+
+        // Compile the query once per language; it is reused for every file.
+        // A legacy post_filter adds its equivalent [[match.where]] predicates.
         let query_source = def.match_section.query;
+        let mut where_sections = def.match_section.where_sections;
+        if let Some(filter_name) = def.match_section.post_filter {
+            where_sections.extend(post_filter_sections(&filter_name)?);
+        }
+        let mut queries = Vec::with_capacity(languages.len());
+        let mut errors = Vec::new();
+        for &language in &languages {
+            match LanguageQuery::compile(
+                &query_source,
+                language,
+                &where_sections,
+                def.metric.as_ref(),
+            ) {
+                Ok(query) => queries.push(query),
+                Err(RuleError::InvalidQuery(message)) if languages.len() > 1 => {
+                    errors.push(format!("{:?}: {}", language, message));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(RuleError::InvalidQuery(errors.join("; ")));
+        }
+
+        // Dialects the languages include (TSX for TypeScript) get the query
+        // too, unless it uses syntax their grammar lacks
+        for dialect in Language::all() {
+            if languages.contains(&dialect)
+                || !languages.iter().any(|language| language.includes(dialect))
+            {
+                continue;
+            }
+            if let Ok(query) =
+                LanguageQuery::compile(&query_source, dialect, &where_sections, def.metric.as_ref())
+            {
+                queries.push(query);
+            }
+        }
 
-        // The query is compiled fresh on each execution; validate it here.
-        validate_query(&query_source, def.match_section.language)?;
+        // Parse the message and fix templates; placeholders name a capture of
+        // every language's query, or in messages of metric rules the measurement
+        let is_capture = |name: &str| queries.iter().all(|query| query.has_capture(name));
+        let is_metric = def.metric.is_some();
+        let message = def
+            .rule
+            .message
+            .map(|template| {
+                MessageTemplate::parse(&template, |name| {
+                    is_capture(name) || (is_metric && METRIC_PLACEHOLDERS.contains(&name))
+                })
+            })
+            .transpose()?;
+        let fix = def
+            .fix
+            .map(|section| MessageTemplate::parse(&section.replacement, is_capture))
+            .transpose()?;
 
         // Build include GlobSet if specified
         let include = if let Some(patterns) = def.match_section.include {
@@ -169,22 +364,22 @@ impl AstRule {
             None
         };
 
-        // Parse post_filter if specified
-        let post_filter = if let Some(filter_name) = def.match_section.post_filter {
-            Some(parse_post_filter(&filter_name)?)
-        } else {
-            None
-        };
+        let tests = RuleTest::compile_all(def.tests, languages.first().copied())?;
 
         Ok(AstRule {
             id,
             description: def.rule.description,
             severity: def.rule.severity,
+            message,
             query_source,
-            language: def.match_section.language,
+            languages,
+            queries,
             include,
             exclude,
-            post_filter,
+            definition_hash: definition_hash(content, ctx),
+            fix,
+            tests,
+            docs: def.rule.docs,
         })
     }
 
@@ -226,8 +421,10 @@ impl AstRule {
 
     /// Execute the query with an actual tree-sitter tree
     ///
-    /// This method performs the actual query execution against a parsed tree.
-    /// It's called by the Rule trait's execute() method when a tree is available.
+    /// This method runs the query precompiled for the tree's grammar; trees
+    /// of languages the rule does not apply to have no violations. The
+    /// execution engine parses each file once and passes the shared tree to
+    /// every applicable AST rule through `ExecutionContext::ast`.
     ///
     /// # Parameters
     ///
@@ -245,59 +442,9 @@ impl AstRule {
         file_path: &Path,
         region_resolver: Option<&RegionResolver>,
     ) -> Vec<Violation> {
-        // Compile the query
-        let parser_cache = ParserCache::new();
-        let parser = match parser_cache.get_parser(self.language) {
-            Ok(p) => p,
-            Err(_) => return vec![],
-        };
-
-        let tree_sitter_lang = match parser.language() {
-            Some(lang) => lang,
-            None => return vec![], // Parser not properly configured
-        };
-
-        let query = match Query::new(&tree_sitter_lang, &self.query_source) {
-            Ok(q) => q,
-            Err(_) => return vec![],
-        };
-
-        // Find the @violation capture index, or use 0 if not found
-        let violation_capture_idx = query
-            .capture_names()
-            .iter()
-            .position(|name| *name == "violation")
-            .unwrap_or(0);
-
-        // Execute query
-        let mut cursor = QueryCursor::new();
-        let matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
-
         let mut violations = Vec::new();
 
-        for match_result in matches {
-            // Apply post-filter if specified
-            if let Some(filter) = self.post_filter
-                && !apply_post_filter(filter, &query, &match_result, content)
-            {
-                continue;
-            }
-
-            // Find the violation capture (or first capture if @violation doesn't exist)
-            let capture = if let Some(capture) = match_result
-                .captures
-                .iter()
-                .find(|c| c.index as usize == violation_capture_idx)
-            {
-                capture
-            } else if let Some(first) = match_result.captures.first() {
-                first
-            } else {
-                continue;
-            };
-
-            let node = capture.node;
-
+        self.for_each_match(tree, content, |query, node, captures, value| {
             // Convert tree-sitter positions (0-indexed) to 1-indexed line/column
             let start_pos = node.start_position();
             let end_pos = node.end_position();
@@ -327,30 +474,103 @@ impl AstRule {
                 end_line,
                 end_column,
                 snippet,
-                message: self.description.clone(),
+                message: self.message(query, captures, content, value),
                 region,
             });
-        }
+        });
 
         violations
     }
-}
 
-/// Validate that a query can be compiled for the given language
-fn validate_query(query_source: &str, language: Language) -> Result<(), RuleError> {
-    let parser_cache = ParserCache::new();
-    let parser = parser_cache
-        .get_parser(language)
-        .map_err(|e| RuleError::InvalidQuery(format!("Failed to get parser: {}", e)))?;
+    /// Build the violation message for a match from its captures and measurement
+    fn message(
+        &self,
+        query: &LanguageQuery,
+        captures: &[QueryCapture],
+        content: &str,
+        value: Option<usize>,
+    ) -> String {
+        let Some(ref template) = self.message else {
+            return self.description.clone();
+        };
+        let value = value.map(|value| value.to_string());
+        let max = query.metric.as_ref().map(|metric| metric.max().to_string());
+        template.render(|name| match name {
+            "value" if value.is_some() => value.as_deref(),
+            "max" if max.is_some() => max.as_deref(),
+            _ => query.capture_text(captures, content, name),
+        })
+    }
+
+    /// Call `f` with the tree's compiled query, the reported node, all
+    /// captures and the measurement (for metric rules) of each query match in
+    /// `tree`
+    fn for_each_match<'t>(
+        &self,
+        tree: &'t Tree,
+        content: &str,
+        mut f: impl FnMut(&LanguageQuery, Node<'t>, &[QueryCapture<'t>], Option<usize>),
+    ) {
+        let grammar = tree.language();
+        let Some(query) = self.queries.iter().find(|query| query.grammar == *grammar) else {
+            return;
+        };
 
-    let tree_sitter_lang = parser
-        .language()
-        .ok_or_else(|| RuleError::InvalidQuery("Parser language not configured".to_string()))?;
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(&query.query, tree.root_node(), content.as_bytes());
 
-    Query::new(&tree_sitter_lang, query_source)
-        .map_err(|e| RuleError::InvalidQuery(format!("Failed to compile query: {}", e)))?;
+        for match_result in matches {
+            // Find the violation capture (or first capture if @violation doesn't exist)
+            let capture = if let Some(capture) = match_result
+                .captures
+                .iter()
+                .find(|c| c.index as usize == query.violation_capture_idx)
+            {
+                capture
+            } else if let Some(first) = match_result.captures.first() {
+                first
+            } else {
+                continue;
+            };
+
+            // Report the match only if every [[match.where]] predicate holds
+            if !query
+                .predicates
+                .iter()
+                .all(|p| p.holds(capture.node, match_result.captures, content))
+            {
+                continue;
+            }
+
+            // Metric rules report only measurements over the threshold
+            let value = match query.metric {
+                Some(ref metric) => match metric.exceeded(capture.node, match_result.captures) {
+                    Some(value) => Some(value),
+                    None => continue,
+                },
+                None => None,
+            };
+
+            f(query, capture.node, match_result.captures, value);
+        }
+    }
+
+    /// Run `f` against the engine's shared tree, parsing the content if there is none
+    fn with_tree<R>(&self, ctx: &ExecutionContext, f: impl FnOnce(&Tree) -> R) -> Option<R> {
+        if let Some(tree) = ctx.ast {
+            return Some(f(tree));
+        }
 
-    Ok(())
+        // No shared tree (e.g. direct callers); parse the content here if
+        // the rule applies to a single language
+        let [language] = self.languages[..] else {
+            return None;
+        };
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(language).ok()?;
+        let tree = parser.parse(ctx.content, None)?;
+        Some(f(&tree))
+    }
 }
 
 /// Build a GlobSet from a list of glob patterns or references
@@ -445,87 +665,6 @@ fn resolve_pattern_reference<'a>(
         })
 }
 
-/// Parse a post-filter name into a PostFilter enum
-fn parse_post_filter(filter_name: &str) -> Result<PostFilter, RuleError> {
-    match filter_name {
-        "class_name_not_exception" => Ok(PostFilter::ClassNameNotException),
-        "nested_in_function_definition" => Ok(PostFilter::NestedInFunctionDefinition),
-        _ => Err(RuleError::InvalidDefinition(format!(
-            "Unknown post_filter: {}",
-            filter_name
-        ))),
-    }
-}
-
-/// Apply a post-filter to a query match
-///
-/// Returns true if the match should be kept as a violation, false if it should be filtered out.
-fn apply_post_filter(
-    filter: PostFilter,
-    query: &Query,
-    match_result: &tree_sitter::QueryMatch,
-    content: &str,
-) -> bool {
-    match filter {
-        PostFilter::ClassNameNotException => {
-            // Find the @class_name capture
-            let class_name_idx = query
-                .capture_names()
-                .iter()
-                .position(|name| *name == "class_name");
-
-            if let Some(idx) = class_name_idx
-                && let Some(capture) = match_result
-                    .captures
-                    .iter()
-                    .find(|c| c.index as usize == idx)
-            {
-                let class_name = &content[capture.node.byte_range()];
-                // Filter out (return false) if class name ends with Exception or Error
-                if class_name.ends_with("Exception") || class_name.ends_with("Error") {
-                    return false;
-                }
-            }
-            true
-        }
-        PostFilter::NestedInFunctionDefinition => {
-            // Find the @violation capture; fall back to the first capture if absent.
-            let violation_idx = query
-                .capture_names()
-                .iter()
-                .position(|name| *name == "violation");
-
-            let violation_node = if let Some(idx) = violation_idx
-                && let Some(capture) = match_result
-                    .captures
-                    .iter()
-                    .find(|c| c.index as usize == idx)
-            {
-                capture.node
-            } else if let Some(first) = match_result.captures.first() {
-                first.node
-            } else {
-                return false;
-            };
-
-            // Walk up the parent chain. Catch when a `function_definition`
-            // ancestor is reached before any `class_definition`. Stop on
-            // `class_definition` so that methods of nested classes are not
-            // flagged by this rule.
-            let mut cur = violation_node.parent();
-            while let Some(node) = cur {
-                match node.kind() {
-                    "class_definition" => return false,
-                    "function_definition" => return true,
-                    _ => {}
-                }
-                cur = node.parent();
-            }
-            false
-        }
-    }
-}
-
 impl Rule for AstRule {
     fn id(&self) -> &RuleId {
         &self.id
@@ -536,39 +675,88 @@ impl Rule for AstRule {
     }
 
     fn languages(&self) -> &[Language] {
-        // AST rules are language-specific
-        std::slice::from_ref(&self.language)
+        &self.languages
     }
 
     fn severity(&self) -> Severity {
         self.severity
     }
 
+    fn kind(&self) -> RuleKind {
+        RuleKind::Ast
+    }
+
+    fn definition_hash(&self) -> Option<u64> {
+        Some(self.definition_hash)
+    }
+
+    fn query(&self) -> Option<&str> {
+        Some(&self.query_source)
+    }
+
     fn execute(&self, ctx: &ExecutionContext) -> Vec<Violation> {
         // Check if this rule applies to this file
         if !self.applies_to_file(ctx.file_path) {
             return vec![];
         }
 
-        // Parse the file here; ExecutionContext.ast is not yet a usable tree.
-        let parser_cache = ParserCache::new();
-        let mut parser = match parser_cache.get_parser(self.language) {
-            Ok(p) => p,
-            Err(_) => return vec![],
-        };
+        self.with_tree(ctx, |tree| {
+            self.execute_with_tree(
+                tree,
+                ctx.content,
+                ctx.file_path,
+                ctx.region_resolver.as_ref(),
+            )
+        })
+        .unwrap_or_default()
+    }
+
+    fn count(&self, ctx: &ExecutionContext) -> usize {
+        if !self.applies_to_file(ctx.file_path) {
+            return 0;
+        }
+
+        self.with_tree(ctx, |tree| {
+            let mut count = 0;
+            self.for_each_match(tree, ctx.content, |_, _, _, _| count += 1);
+            count
+        })
+        .unwrap_or(0)
+    }
+
+    fn has_fix(&self) -> bool {
+        self.fix.is_some()
+    }
 
-        let tree = match parser.parse(ctx.content, None) {
-            Some(t) => t,
-            None => return vec![],
+    fn fixes(&self, ctx: &ExecutionContext) -> Vec<Fix> {
+        let Some(ref template) = self.fix else {
+            return vec![];
         };
+        if !self.applies_to_file(ctx.file_path) {
+            return vec![];
+        }
 
-        // Execute the query using our stored query_source
-        self.execute_with_tree(
-            &tree,
-            ctx.content,
-            ctx.file_path,
-            ctx.region_resolver.as_ref(),
-        )
+        // Each reported node is replaced with the rendered template
+        self.with_tree(ctx, |tree| {
+            let mut fixes = Vec::new();
+            self.for_each_match(tree, ctx.content, |query, node, captures, _| {
+                fixes.push(Fix {
+                    range: node.byte_range(),
+                    replacement: template
+                        .render(|name| query.capture_text(captures, ctx.content, name)),
+                });
+            });
+            fixes
+        })
+        .unwrap_or_default()
+    }
+
+    fn tests(&self) -> &[RuleTest] {
+        &self.tests
+    }
+
+    fn docs(&self) -> Option<&RuleDocs> {
+        Some(&self.docs)
     }
 }
 
@@ -595,7 +783,7 @@ language = "rust"
         assert_eq!(rule.id.as_str(), "test-ast-rule");
         assert_eq!(rule.description, "Test AST rule");
         assert_eq!(rule.severity, Severity::Error);
-        assert_eq!(rule.language, Language::Rust);
+        assert_eq!(rule.languages, vec![Language::Rust]);
         assert!(rule.include.is_none());
         assert!(rule.exclude.is_none());
         Ok(())
@@ -661,6 +849,75 @@ language = "rust"
         assert!(matches!(result.unwrap_err(), RuleError::InvalidQuery(_)));
     }
 
+    #[cfg(all(feature = "lang-javascript", feature = "lang-typescript"))]
+    #[test]
+    fn test_from_toml_language_list() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "no-debugger"
+description = "No debugger statements"
+severity = "error"
+
+[match]
+query = "(debugger_statement) @violation"
+language = ["javascript", "typescript", "javascript"]
+"#;
+
+        let rule = AstRule::from_toml(toml)?;
+        assert_eq!(
+            rule.languages(),
+            &[Language::JavaScript, Language::TypeScript]
+        );
+        // TSX gets the TypeScript query too
+        let compiled: Vec<Language> = rule.queries.iter().map(|q| q.language).collect();
+        assert_eq!(
+            compiled,
+            vec![Language::JavaScript, Language::TypeScript, Language::Tsx]
+        );
+        Ok(())
+    }
+
+    #[cfg(all(feature = "lang-javascript", feature = "lang-typescript"))]
+    #[test]
+    fn test_from_toml_query_errors_name_language() {
+        // Type annotations exist only in the TypeScript grammar
+        let toml = r#"
+[rule]
+id = "no-annotations"
+description = "Test"
+severity = "error"
+
+[match]
+query = "(type_annotation) @violation"
+language = ["typescript", "javascript"]
+"#;
+
+        match AstRule::from_toml(toml) {
+            Err(RuleError::InvalidQuery(message)) => {
+                assert!(message.starts_with("JavaScript: "), "{}", message);
+                assert!(!message.contains("TypeScript"), "{}", message);
+            }
+            other => unreachable!("expected an invalid query, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_toml_empty_language_list() {
+        let toml = r#"
+[rule]
+id = "no-languages"
+description = "Test"
+severity = "error"
+
+[match]
+query = "(identifier) @violation"
+language = []
+"#;
+
+        let result = AstRule::from_toml(toml);
+        assert!(matches!(result, Err(RuleError::InvalidDefinition(_))));
+    }
+
     #[test]
     fn test_from_toml_missing_field() {
         let toml = r#"
@@ -701,7 +958,9 @@ language = "rust"
             file_path: Path::new("test.rs"),
             content: "fn main() { let x = Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -735,7 +994,9 @@ language = "rust"
             file_path: Path::new("test.rs"),
             content: "fn main() {\n    let x = Some(5).unwrap();\n    let y = Some(10).unwrap();\n}",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -743,6 +1004,42 @@ language = "rust"
         Ok(())
     }
 
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_count_matches_execute() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "find-unwrap"
+description = "Find unwrap in src only"
+severity = "error"
+
+[match]
+query = """
+(call_expression
+  function: (field_expression
+    field: (field_identifier) @method)
+  (#eq? @method "unwrap")) @violation
+"""
+language = "rust"
+include = ["src/**"]
+"#;
+
+        let rule = AstRule::from_toml(toml)?;
+
+        for path in ["src/main.rs", "tests/test.rs"] {
+            let ctx = ExecutionContext {
+                file_path: Path::new(path),
+                content: "fn main() {\n    let x = Some(5).unwrap();\n    let y = Some(10).unwrap();\n}",
+                ast: None,
+                line_offsets: None,
+                region_resolver: None,
+                walked_files: None,
+            };
+            assert_eq!(rule.count(&ctx), rule.execute(&ctx).len());
+        }
+        Ok(())
+    }
+
     #[cfg(feature = "lang-rust")]
     #[test]
     fn test_execute_no_match() -> Result<(), Box<dyn std::error::Error>> {
@@ -768,7 +1065,9 @@ language = "rust"
             file_path: Path::new("test.rs"),
             content: "fn main() { let x = Some(5); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -803,7 +1102,9 @@ include = ["src/**"]
             file_path: Path::new("src/main.rs"),
             content: "fn main() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 1);
@@ -813,7 +1114,9 @@ include = ["src/**"]
             file_path: Path::new("tests/test.rs"),
             content: "fn test() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0);
@@ -847,7 +1150,9 @@ exclude = ["tests/**"]
             file_path: Path::new("tests/test.rs"),
             content: "fn test() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0);
@@ -857,7 +1162,9 @@ exclude = ["tests/**"]
             file_path: Path::new("src/main.rs"),
             content: "fn main() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 1);
@@ -885,7 +1192,9 @@ language = "rust"
             file_path: Path::new("test.rs"),
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -915,7 +1224,9 @@ language = "rust"
             file_path: Path::new("test.rs"),
             content: "fn main() {}",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -960,6 +1271,337 @@ language = "rust"
         Ok(())
     }
 
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_execute_message_template() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "find-unwrap"
+description = "Find unwrap calls"
+message = "Avoid .{method}() on `{receiver}`"
+severity = "error"
+
+[match]
+query = """
+(call_expression
+  function: (field_expression
+    value: (_) @receiver
+    field: (field_identifier) @method)
+  (#match? @method "^(unwrap|expect)$")) @violation
+"""
+language = "rust"
+"#;
+
+        let rule = AstRule::from_toml(toml)?;
+
+        let content = "fn main() { a.unwrap(); b.expect(\"x\"); }";
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Rust)?;
+        let tree = parser
+            .parse(content, None)
+            .ok_or("failed to parse content")?;
+
+        let violations = rule.execute_with_tree(&tree, content, Path::new("test.rs"), None);
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["Avoid .unwrap() on `a`", "Avoid .expect() on `b`"]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_from_toml_message_unknown_capture() {
+        let toml = r#"
+[rule]
+id = "find-unwrap"
+description = "Find unwrap calls"
+message = "Avoid {missing}"
+severity = "error"
+
+[match]
+query = "(call_expression) @violation"
+language = "rust"
+"#;
+
+        let result = AstRule::from_toml(toml);
+        assert!(matches!(result, Err(RuleError::InvalidDefinition(_))));
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_fixes_replace_violation_capture() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "no-unwrap"
+description = "Use expect instead of unwrap"
+severity = "error"
+
+[match]
+query = """
+(call_expression
+  function: (field_expression
+    value: (_) @receiver
+    field: (field_identifier) @method)
+  (#eq? @method "unwrap")) @violation
+"""
+language = "rust"
+
+[fix]
+replacement = "{receiver}.expect(\"{{receiver}} is set\")"
+"#;
+
+        let rule = AstRule::from_toml(toml)?;
+        assert!(rule.has_fix());
+
+        let content = "fn main() { a.unwrap(); b.len(); }";
+        let ctx = ExecutionContext {
+            file_path: Path::new("test.rs"),
+            content,
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        };
+
+        let fixes = rule.fixes(&ctx);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(&content[fixes[0].range.clone()], "a.unwrap()");
+        assert_eq!(fixes[0].replacement, "a.expect(\"{receiver} is set\")");
+        Ok(())
+    }
+
+    /// Run a rule against `content`, returning the reported snippets
+    #[cfg(any(feature = "lang-rust", feature = "lang-python"))]
+    fn snippets(rule: &AstRule, content: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let mut parser = ParserCache::new().get_parser(rule.languages[0])?;
+        let tree = parser
+            .parse(content, None)
+            .ok_or("failed to parse content")?;
+        let violations = rule.execute_with_tree(&tree, content, Path::new("test"), None);
+        Ok(violations.into_iter().map(|v| v.snippet).collect())
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_where_ancestor_and_text() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "no-nested-fns"
+description = "No nested functions"
+severity = "warning"
+
+[match]
+query = "(function_item name: (identifier) @name) @violation"
+language = "rust"
+
+[[match.where]]
+has_ancestor = "function_item"
+stop_at = "impl_item"
+
+[[match.where]]
+capture = "name"
+not_matches = "^helper"
+"#;
+
+        let rule = AstRule::from_toml(toml)?;
+        let content = r#"
+fn outer() {
+    fn inner() {}
+    fn helper_a() {}
+    struct S;
+    impl S { fn method() {} }
+}
+fn top() {}
+"#;
+        assert_eq!(snippets(&rule, content)?, ["fn inner() {}"]);
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_where_child_sibling_and_depth() -> Result<(), Box<dyn std::error::Error>> {
+        let rule_with = |condition: &str| {
+            AstRule::from_toml(&format!(
+                r#"
+[rule]
+id = "fns"
+description = "Functions"
+severity = "warning"
+
+[match]
+query = "(function_item name: (identifier) @name) @violation"
+language = "rust"
+
+[[match.where]]
+capture = "name"
+matches = "^f"
+
+[[match.where]]
+{}
+"#,
+                condition
+            ))
+        };
+        let name = |snippet: &String| snippet.split(['(', ' ']).nth(1).unwrap_or("").to_string();
+        let content = "fn f1() {}\nfn f2(x: u8) {}\nmod m { fn f3() {} struct T; }\n";
+        let names = |condition: &str| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+            Ok(snippets(&rule_with(condition)?, content)?
+                .iter()
+                .map(name)
+                .collect())
+        };
+
+        assert_eq!(
+            names("lacks_child = \"visibility_modifier\"")?,
+            ["f1", "f2", "f3"]
+        );
+        assert_eq!(names("has_sibling = \"struct_item\"")?, ["f3"]);
+        assert_eq!(names("lacks_sibling = \"struct_item\"")?, ["f1", "f2"]);
+        assert_eq!(names("max_depth = 1")?, ["f1", "f2"]);
+        assert_eq!(names("min_depth = 2")?, ["f3"]);
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-python")]
+    #[test]
+    fn test_legacy_post_filter() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "no-init"
+description = "No __init__"
+severity = "warning"
+
+[match]
+language = "python"
+query = """
+(class_definition
+  name: (identifier) @class_name
+  body: (block
+    (function_definition
+      name: (identifier) @method_name
+      (#eq? @method_name "__init__")))) @violation
+"""
+post_filter = "class_name_not_exception"
+"#;
+
+        let rule = AstRule::from_toml(toml)?;
+        let content =
+            "class A:\n    def __init__(self): pass\nclass BError:\n    def __init__(self): pass\n";
+        let found = snippets(&rule, content)?;
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("class A:"));
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_where_invalid_definitions() {
+        let rule_with = |condition: &str| {
+            AstRule::from_toml(&format!(
+                r#"
+[rule]
+id = "fns"
+description = "Functions"
+severity = "warning"
+
+[match]
+query = "(function_item) @violation"
+language = "rust"
+
+[[match.where]]
+{}
+"#,
+                condition
+            ))
+        };
+
+        assert!(rule_with("").is_err());
+        assert!(rule_with("capture = \"missing\"\nmatches = \"x\"").is_err());
+        assert!(rule_with("stop_at = \"impl_item\"").is_err());
+        assert!(rule_with("matches = \"(\"").is_err());
+        assert!(rule_with("has_parent = \"mod_item\"").is_err());
+        assert!(rule_with("has_ancestor = [\"mod_item\", \"impl_item\"]").is_ok());
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_metric_reports_matches_over_threshold() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "max-params"
+description = "Too many parameters"
+severity = "warning"
+message = "{name} takes {value} parameters (max {max})"
+
+[match]
+query = "(function_item name: (identifier) @name parameters: (parameters) @params) @violation"
+language = "rust"
+
+[metric]
+measure = "children"
+max = 2
+capture = "params"
+"#;
+        let rule = AstRule::from_toml(toml)?;
+        let ctx = ExecutionContext {
+            file_path: Path::new("src/lib.rs"),
+            content: "fn a(x: u8, y: u8) {}\nfn b(x: u8, /* z */ y: u8, z: u8) {}\n",
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        };
+        let violations = rule.execute(&ctx);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, 2);
+        assert_eq!(violations[0].message, "b takes 3 parameters (max 2)");
+        assert_eq!(rule.count(&ctx), 1);
+
+        // {value} and {max} are only known to metric rules
+        let without_metric = toml.split("[metric]").next().ok_or("no [metric]")?;
+        assert!(AstRule::from_toml(without_metric).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_execute_uses_shared_tree() -> Result<(), Box<dyn std::error::Error>> {
+        let rule = AstRule::from_toml(
+            r#"
+[rule]
+id = "find-unwrap"
+description = "Find unwrap calls"
+severity = "error"
+
+[match]
+query = "(field_identifier) @violation"
+language = "rust"
+"#,
+        )?;
+
+        let parsed = "fn main() { a.unwrap(); b.unwrap(); }";
+        let mut parser = ParserCache::new().get_parser(Language::Rust)?;
+        let tree = parser
+            .parse(parsed, None)
+            .ok_or("failed to parse content")?;
+
+        let ctx = ExecutionContext {
+            file_path: Path::new("test.rs"),
+            content: parsed,
+            ast: Some(&tree),
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        };
+        assert_eq!(rule.execute(&ctx).len(), 2);
+
+        // Repeated executions reuse the compiled query
+        assert_eq!(rule.execute(&ctx).len(), 2);
+        Ok(())
+    }
+
     #[cfg(feature = "lang-rust")]
     #[test]
     fn test_ast_rule_uses_configured_region() -> Result<(), Box<dyn std::error::Error>> {
@@ -988,7 +1630,9 @@ language = "rust"
             file_path: Path::new("src/deep/nested/file.rs"),
             content: "fn main() { foo(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: Some(resolver),
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -1025,7 +1669,9 @@ include = ["src/**"]
             file_path: Path::new("./src/main.rs"),
             content: "fn main() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(
@@ -1039,7 +1685,9 @@ include = ["src/**"]
             file_path: Path::new("src/main.rs"),
             content: "fn main() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(
@@ -1053,7 +1701,9 @@ include = ["src/**"]
             file_path: Path::new("./tests/test.rs"),
             content: "fn test() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0);
@@ -1086,7 +1736,9 @@ exclude = ["**/tests/**"]
             file_path: Path::new("./src/tests/foo.rs"),
             content: "fn test() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0, "**/tests/** must still exclude");
@@ -1096,7 +1748,9 @@ exclude = ["**/tests/**"]
             file_path: Path::new("./src/main.rs"),
             content: "fn main() { Some(5).unwrap(); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 1);
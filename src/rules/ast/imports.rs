@@ -0,0 +1,606 @@
+#![forbid(unsafe_code)]
+
+//! Import extraction from syntax trees
+//!
+//! This module finds the import statements of a parsed file and resolves each
+//! imported module to a slash-separated target path, so that import rules can
+//! match imports with glob patterns. Relative imports resolve against the
+//! file's directory and then drop the longest source root they fall under, so
+//! they share the namespace of absolute imports: with the source root `src`,
+//! `from ..infra import db` in `src/myapp/domain/order.py` and
+//! `import myapp.infra.db` both import `myapp/infra/db`.
+//!
+//! - Rust: `crate::`, `self::` and `super::` paths resolve against the
+//!   file's module path and then drop the source root like relative imports,
+//!   so with the source root `src`, `use crate::infra::{db, cache}` imports
+//!   `infra/db` and `infra/cache`. Paths of other crates are kept as written,
+//!   e.g. `use std::fmt` imports `std/fmt`.
+//! - Python: `import myapp.infra` imports `myapp/infra`, and
+//!   `from myapp.infra import db` imports `myapp/infra/db`.
+//! - TypeScript/JavaScript: `import`, `export ... from`, `require()` and
+//!   dynamic `import()` of a string. Relative specifiers drop their
+//!   extension, e.g. `'../infra/db.js'` in `src/domain/order.ts` imports
+//!   `infra/db`; other specifiers are kept as written, so `'infra/db'`
+//!   resolved through a `baseUrl` of `src` matches too.
+//! - Go: import paths are kept as written.
+//! - Java/Kotlin: `import com.acme.infra.Db` imports `com/acme/infra/Db`.
+//!   Wildcard imports target the package, so `import com.acme.infra.*`
+//!   imports `com/acme/infra`; aliases and `static` are ignored.
+//! - C/C++: `#include` paths are kept as written, without quotes or angle
+//!   brackets.
+//! - Bash: `source` is dynamic, so shell scripts have no imports.
+
+use crate::types::Language;
+use std::ops::Range;
+use std::path::Path;
+use tree_sitter::{Node, Point, Tree};
+
+/// Extensions dropped from relative TypeScript/JavaScript specifiers
+const SCRIPT_EXTENSIONS: [&str; 8] = [".ts", ".tsx", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs"];
+
+/// An imported module and the statement that imports it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Import {
+    /// The module as written, e.g. `crate::infra::db` or `../infra/db`
+    pub module: String,
+    /// The module as a slash-separated path, with relative imports resolved
+    pub target: String,
+    /// Byte range of the importing statement
+    pub byte_range: Range<usize>,
+    /// Start and end of the importing statement (0-indexed)
+    pub start: Point,
+    pub end: Point,
+}
+
+/// Extract the imports of a file parsed as `language`
+///
+/// `file_path` is the file's path relative to the project root; relative
+/// imports resolve against it, and then drop the longest of the slash-separated
+/// `roots` they fall under. Imports are returned in source order.
+pub(crate) fn extract_imports(
+    tree: &Tree,
+    content: &str,
+    language: Language,
+    file_path: &Path,
+    roots: &[String],
+) -> Vec<Import> {
+    let mut imports = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let found = match language {
+            Language::Rust => rust_imports(node, content, file_path, roots),
+            Language::Python => python_imports(node, content, file_path, roots),
+            Language::TypeScript | Language::Tsx | Language::JavaScript => {
+                script_imports(node, content, file_path, roots)
+            }
+            Language::Go => go_imports(node, content),
+            Language::Java | Language::Kotlin => jvm_imports(node, content),
+            Language::C | Language::Cpp => include_imports(node, content),
+            Language::Bash => None,
+        };
+        match found {
+            Some(modules) => {
+                imports.extend(modules.into_iter().map(|(module, target)| Import {
+                    module,
+                    target,
+                    byte_range: node.byte_range(),
+                    start: node.start_position(),
+                    end: node.end_position(),
+                }));
+            }
+            None => {
+                let mut cursor = node.walk();
+                let children: Vec<Node> = node.named_children(&mut cursor).collect();
+                stack.extend(children.into_iter().rev());
+            }
+        }
+    }
+    imports
+}
+
+/// Text of a node
+fn text<'c>(node: Node, content: &'c str) -> &'c str {
+    &content[node.byte_range()]
+}
+
+/// The `(module, target)` pairs imported by `node`, or None if it is not an import
+type Modules = Option<Vec<(String, String)>>;
+
+fn rust_imports(node: Node, content: &str, file_path: &Path, roots: &[String]) -> Modules {
+    if node.kind() != "use_declaration" {
+        return None;
+    }
+    let mut paths = Vec::new();
+    if let Some(argument) = node.child_by_field_name("argument") {
+        expand_use_tree(argument, content, "", &mut paths);
+    }
+
+    let (crate_root, module) = rust_module_path(file_path, roots);
+    Some(
+        paths
+            .into_iter()
+            .map(|path| {
+                let target = resolve_rust_path(&path, &crate_root, &module, roots);
+                (path, target)
+            })
+            .collect(),
+    )
+}
+
+/// Flatten a Rust use tree into the full paths it imports
+fn expand_use_tree(node: Node, content: &str, prefix: &str, paths: &mut Vec<String>) {
+    let join = |path: &str| {
+        if prefix.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}::{}", prefix, path)
+        }
+    };
+    match node.kind() {
+        "scoped_use_list" => {
+            let prefix = match node.child_by_field_name("path") {
+                Some(path) => join(text(path, content)),
+                None => prefix.to_string(),
+            };
+            if let Some(list) = node.child_by_field_name("list") {
+                expand_use_tree(list, content, &prefix, paths);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                expand_use_tree(child, content, prefix, paths);
+            }
+        }
+        "use_as_clause" => {
+            if let Some(path) = node.child_by_field_name("path") {
+                expand_use_tree(path, content, prefix, paths);
+            }
+        }
+        "use_wildcard" => match node.named_child(0) {
+            Some(path) => expand_use_tree(path, content, prefix, paths),
+            None => paths.push(prefix.to_string()),
+        },
+        // `use a::{self, b}` imports `a` itself
+        "self" if !prefix.is_empty() => paths.push(prefix.to_string()),
+        _ => paths.push(join(text(node, content))),
+    }
+}
+
+/// The crate root directory and module path of a Rust file
+///
+/// The crate root is the longest of `roots` the file lies under, or else its
+/// last `src` directory, e.g. `src` and `["domain", "order"]` for
+/// `src/domain/order.rs`.
+fn rust_module_path(file_path: &Path, roots: &[String]) -> (String, Vec<String>) {
+    let path = join_path(
+        "",
+        &file_path
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/"),
+    );
+    let (crate_root, below) = match split_root(&path, roots) {
+        Some((root, below)) => (root.to_string(), below.to_string()),
+        None => match path.rsplit_once("/src/") {
+            Some((parent, below)) => (format!("{}/src", parent), below.to_string()),
+            None => match path.strip_prefix("src/") {
+                Some(below) => ("src".to_string(), below.to_string()),
+                None => (String::new(), path.clone()),
+            },
+        },
+    };
+
+    let mut module: Vec<String> = below.split('/').map(str::to_string).collect();
+    if module.last().is_some_and(|last| last == "mod") {
+        module.pop();
+    }
+    if matches!(module.as_slice(), [only] if only == "lib" || only == "main") {
+        module.pop();
+    }
+    (crate_root, module)
+}
+
+/// Resolve a Rust path to a target path
+///
+/// Paths starting with `crate`, `self` or `super` resolve against the file's
+/// crate root and `module`, then drop the longest of `roots` they fall
+/// under; other paths name another crate and are kept as written.
+fn resolve_rust_path(path: &str, crate_root: &str, module: &[String], roots: &[String]) -> String {
+    let mut segments = path.split("::").map(str::trim).peekable();
+    let mut resolved = Vec::new();
+    match segments.peek() {
+        Some(&"crate") => {
+            segments.next();
+        }
+        Some(&"self") => {
+            segments.next();
+            resolved.extend(module.iter().cloned());
+        }
+        Some(&"super") => {
+            resolved.extend(module.iter().cloned());
+            while segments.peek() == Some(&"super") {
+                segments.next();
+                resolved.pop();
+            }
+        }
+        _ => return segments.collect::<Vec<_>>().join("/"),
+    }
+    resolved.extend(segments.map(str::to_string));
+    strip_root(join_path(crate_root, &resolved.join("/")), roots)
+}
+
+fn python_imports(node: Node, content: &str, file_path: &Path, roots: &[String]) -> Modules {
+    let names = |node: Node| -> Vec<String> {
+        let mut cursor = node.walk();
+        node.children_by_field_name("name", &mut cursor)
+            .filter_map(|name| match name.kind() {
+                "aliased_import" => name.child_by_field_name("name"),
+                _ => Some(name),
+            })
+            .map(|name| text(name, content).to_string())
+            .collect()
+    };
+
+    match node.kind() {
+        "import_statement" => Some(
+            names(node)
+                .into_iter()
+                .map(|name| {
+                    let target = name.replace('.', "/");
+                    (name, target)
+                })
+                .collect(),
+        ),
+        "import_from_statement" => {
+            let module_node = node.child_by_field_name("module_name")?;
+            let module = text(module_node, content).to_string();
+            let base = python_module_target(&module, file_path, roots);
+            let names = names(node);
+            if names.is_empty() {
+                // `from module import *`
+                return Some(vec![(module, base)]);
+            }
+            Some(
+                names
+                    .into_iter()
+                    .map(|name| {
+                        let target = join_path(&base, &name.replace('.', "/"));
+                        (module.clone(), target)
+                    })
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Resolve a Python module name, which may start with dots, to a target path
+fn python_module_target(module: &str, file_path: &Path, roots: &[String]) -> String {
+    let dotted = module.trim_start_matches('.');
+    let dots = module.len() - dotted.len();
+    if dots == 0 {
+        return dotted.replace('.', "/");
+    }
+
+    // One dot is the file's own package, each further dot a parent package
+    let mut base = parent_dir(file_path);
+    for _ in 1..dots {
+        base = join_path(&base, "..");
+    }
+    strip_root(join_path(&base, &dotted.replace('.', "/")), roots)
+}
+
+fn script_imports(node: Node, content: &str, file_path: &Path, roots: &[String]) -> Modules {
+    let source = match node.kind() {
+        "import_statement" | "export_statement" => node.child_by_field_name("source")?,
+        "call_expression" => {
+            let function = node.child_by_field_name("function")?;
+            let is_import = match function.kind() {
+                "import" => true,
+                "identifier" => text(function, content) == "require",
+                _ => false,
+            };
+            let argument = node.child_by_field_name("arguments")?.named_child(0)?;
+            if !is_import || argument.kind() != "string" {
+                return None;
+            }
+            argument
+        }
+        _ => return None,
+    };
+
+    let module = text(source, content)
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string();
+    let target = if module.starts_with("./") || module.starts_with("../") {
+        let resolved = join_path(&parent_dir(file_path), &module);
+        let resolved = SCRIPT_EXTENSIONS
+            .iter()
+            .find_map(|ext| resolved.strip_suffix(ext))
+            .map(str::to_string)
+            .unwrap_or(resolved);
+        strip_root(resolved, roots)
+    } else {
+        module.clone()
+    };
+    Some(vec![(module, target)])
+}
+
+fn go_imports(node: Node, content: &str) -> Modules {
+    if node.kind() != "import_spec" {
+        return None;
+    }
+    let path = node.child_by_field_name("path")?;
+    let module = text(path, content).trim_matches(|c| c == '"' || c == '`');
+    Some(vec![(module.to_string(), module.to_string())])
+}
+
+fn jvm_imports(node: Node, content: &str) -> Modules {
+    if !matches!(node.kind(), "import_declaration" | "import_header") {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let name = node
+        .named_children(&mut cursor)
+        .find(|child| matches!(child.kind(), "identifier" | "scoped_identifier"))?;
+    let module = text(name, content);
+    Some(vec![(module.to_string(), module.replace('.', "/"))])
+}
+
+fn include_imports(node: Node, content: &str) -> Modules {
+    if node.kind() != "preproc_include" {
+        return None;
+    }
+    let path = node.child_by_field_name("path")?;
+    let module = text(path, content).trim_matches(|c| matches!(c, '"' | '<' | '>'));
+    Some(vec![(module.to_string(), module.to_string())])
+}
+
+/// Drop the longest of `roots` that `path` lies below from its start
+///
+/// Paths outside every root, and a root itself, are returned unchanged.
+fn strip_root(path: String, roots: &[String]) -> String {
+    split_root(&path, roots).map_or_else(|| path.clone(), |(_, rest)| rest.to_string())
+}
+
+/// Split `path` into the longest of `roots` it lies below and the rest of it
+fn split_root<'p>(path: &'p str, roots: &[String]) -> Option<(&'p str, &'p str)> {
+    roots
+        .iter()
+        .map(|root| root.trim_matches('/'))
+        .filter_map(|root| {
+            let rest = match root {
+                "" => path,
+                _ => path.strip_prefix(root)?.strip_prefix('/')?,
+            };
+            Some((&path[..root.len()], rest))
+        })
+        .max_by_key(|(root, _)| root.len())
+}
+
+/// The directory of `file_path` as a slash-separated path ("" for the root)
+fn parent_dir(file_path: &Path) -> String {
+    let parent = file_path.parent().unwrap_or(Path::new(""));
+    join_path("", &parent.to_string_lossy().replace('\\', "/"))
+}
+
+/// Join slash-separated paths, resolving `.` and `..` segments lexically
+///
+/// `..` segments that would climb above the root are kept.
+fn join_path(base: &str, relative: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in base.split('/').chain(relative.split('/')) {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|s| *s != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::ParserCache;
+
+    fn targets(
+        language: Language,
+        file: &str,
+        content: &str,
+    ) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+        let mut parser = ParserCache::new().get_parser(language)?;
+        let tree = parser.parse(content, None).ok_or("parse failed")?;
+        let roots = ["src".to_string()];
+        Ok(
+            extract_imports(&tree, content, language, Path::new(file), &roots)
+                .into_iter()
+                .map(|import| (import.module, import.target))
+                .collect(),
+        )
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(module, target)| (module.to_string(), target.to_string()))
+            .collect()
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_rust_use_trees() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+use crate::infra::{self, db, cache::Redis as R};
+use super::repo::*;
+fn f() { use std::fmt; }
+";
+        assert_eq!(
+            targets(Language::Rust, "src/domain/order.rs", content)?,
+            pairs(&[
+                ("crate::infra", "infra"),
+                ("crate::infra::db", "infra/db"),
+                ("crate::infra::cache::Redis", "infra/cache/Redis"),
+                ("super::repo", "domain/repo"),
+                ("std::fmt", "std/fmt"),
+            ])
+        );
+        assert_eq!(
+            targets(Language::Rust, "./src/domain/mod.rs", "use self::a::B;")?,
+            pairs(&[("self::a::B", "domain/a/B")])
+        );
+
+        // Files outside every root resolve against their crate's `src`
+        assert_eq!(
+            targets(
+                Language::Rust,
+                "crates/app/src/lib.rs",
+                "use crate::infra::Db;"
+            )?,
+            pairs(&[("crate::infra::Db", "crates/app/src/infra/Db")])
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-python")]
+    #[test]
+    fn test_python_imports() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+import myapp.infra, json as j
+from myapp.infra.db import Pool
+from ..infra import db as d
+from . import repo
+from os.path import *
+";
+        assert_eq!(
+            targets(Language::Python, "src/myapp/domain/order.py", content)?,
+            pairs(&[
+                ("myapp.infra", "myapp/infra"),
+                ("json", "json"),
+                ("myapp.infra.db", "myapp/infra/db/Pool"),
+                ("..infra", "myapp/infra/db"),
+                (".", "myapp/domain/repo"),
+                ("os.path", "os/path"),
+            ])
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-typescript")]
+    #[test]
+    fn test_typescript_imports() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+import { Pool } from '../infra/db.js';
+import type { T } from \"./types\";
+export * from './events';
+const lib = require('lodash');
+const lazy = await import('@acme/ui/button');
+const other = notRequire('../x');
+";
+        assert_eq!(
+            targets(Language::TypeScript, "src/domain/order.ts", content)?,
+            pairs(&[
+                ("../infra/db.js", "infra/db"),
+                ("./types", "domain/types"),
+                ("./events", "domain/events"),
+                ("lodash", "lodash"),
+                ("@acme/ui/button", "@acme/ui/button"),
+            ])
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-go")]
+    #[test]
+    fn test_go_imports() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "package main\nimport \"fmt\"\nimport (\n\tdb \"example.com/app/internal/infra/db\"\n)\n";
+        assert_eq!(
+            targets(Language::Go, "cmd/main.go", content)?,
+            pairs(&[
+                ("fmt", "fmt"),
+                (
+                    "example.com/app/internal/infra/db",
+                    "example.com/app/internal/infra/db"
+                ),
+            ])
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-java")]
+    #[test]
+    fn test_java_imports() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+import java.util.List;
+import static org.junit.Assert.*;
+import com.acme.infra.*;
+class Order {}
+";
+        assert_eq!(
+            targets(Language::Java, "src/main/java/Order.java", content)?,
+            pairs(&[
+                ("java.util.List", "java/util/List"),
+                ("org.junit.Assert", "org/junit/Assert"),
+                ("com.acme.infra", "com/acme/infra"),
+            ])
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-kotlin")]
+    #[test]
+    fn test_kotlin_imports() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+import com.acme.infra.Db
+import com.acme.domain.*
+import kotlinx.coroutines.launch as start
+fun main() {}
+";
+        assert_eq!(
+            targets(Language::Kotlin, "src/main/kotlin/Main.kt", content)?,
+            pairs(&[
+                ("com.acme.infra.Db", "com/acme/infra/Db"),
+                ("com.acme.domain", "com/acme/domain"),
+                ("kotlinx.coroutines.launch", "kotlinx/coroutines/launch"),
+            ])
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-cpp")]
+    #[test]
+    fn test_include_imports() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "#include <vector>\n#include \"infra/db.h\"\nint main() { return 0; }\n";
+        assert_eq!(
+            targets(Language::Cpp, "src/main.cpp", content)?,
+            pairs(&[("vector", "vector"), ("infra/db.h", "infra/db.h")])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_root() {
+        let roots = ["src".to_string(), "src/web/".to_string()];
+        assert_eq!(
+            strip_root("src/myapp/infra".to_string(), &roots),
+            "myapp/infra"
+        );
+        assert_eq!(strip_root("src/web/ui".to_string(), &roots), "ui");
+        assert_eq!(
+            strip_root("scripts/build".to_string(), &roots),
+            "scripts/build"
+        );
+        assert_eq!(strip_root("srcs/a".to_string(), &roots), "srcs/a");
+        assert_eq!(strip_root("src".to_string(), &roots), "src");
+    }
+
+    #[test]
+    fn test_join_path() {
+        assert_eq!(join_path("src/domain", "../infra/./db"), "src/infra/db");
+        assert_eq!(join_path("", "../../x"), "../../x");
+        assert_eq!(join_path("a", "../../x"), "../x");
+    }
+}
@@ -0,0 +1,221 @@
+#![forbid(unsafe_code)]
+
+//! Threshold metrics for AST rules
+//!
+//! An AST rule with a `[metric]` section measures every node its query
+//! reports, and reports only the nodes whose measurement exceeds `max`:
+//!
+//! ```toml
+//! [metric]
+//! measure = "lines"                 # lines, nesting, children or branches
+//! max = 200
+//! capture = "params"                # optional, defaults to the reported node
+//! kinds = ["if_expression"]         # node kinds, see below
+//! ```
+//!
+//! - `lines`: lines the node spans
+//! - `nesting`: deepest nesting of `kinds` nodes inside the node
+//! - `children`: named children of the node, e.g. of a parameter list; only
+//!   those of `kinds` if set. Comments are not counted.
+//! - `branches`: 1 plus the number of `kinds` nodes inside the node, a simple
+//!   cyclomatic complexity
+//!
+//! The measurement is available to the rule's message as `{value}`, and the
+//! threshold as `{max}`.
+
+use crate::error::RuleError;
+use crate::rules::ast::predicate::KindList;
+use serde::Deserialize;
+use tree_sitter::{Node, Query, QueryCapture};
+
+/// Message placeholders a metric adds to its rule
+pub(crate) const METRIC_PLACEHOLDERS: [&str; 2] = ["value", "max"];
+
+/// TOML structure of a `[metric]` table
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct MetricSection {
+    measure: Measure,
+    max: usize,
+    /// Capture to measure; defaults to the reported node
+    #[serde(default)]
+    capture: Option<String>,
+    #[serde(default)]
+    kinds: Option<KindList>,
+}
+
+/// What a metric measures about a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Measure {
+    Lines,
+    Nesting,
+    Children,
+    Branches,
+}
+
+/// A compiled `[metric]` table
+#[derive(Debug, Clone)]
+pub(crate) struct Metric {
+    measure: Measure,
+    max: usize,
+    /// Index of the measured capture; None for the reported node
+    capture: Option<u32>,
+    kinds: Vec<String>,
+}
+
+impl Metric {
+    /// Compile a `[metric]` table against the rule's query
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if the capture is not in the
+    /// query, or a nesting or branches metric has no `kinds`.
+    pub(crate) fn compile(section: MetricSection, query: &Query) -> Result<Self, RuleError> {
+        let capture = section
+            .capture
+            .map(|name| {
+                query.capture_index_for_name(&name).ok_or_else(|| {
+                    RuleError::InvalidDefinition(format!(
+                        "[metric] refers to unknown capture '@{}'",
+                        name
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let kinds = section.kinds.map(KindList::into_vec).unwrap_or_default();
+        if kinds.is_empty() && matches!(section.measure, Measure::Nesting | Measure::Branches) {
+            return Err(RuleError::InvalidDefinition(
+                "[metric] nesting and branches require kinds".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            measure: section.measure,
+            max: section.max,
+            capture,
+            kinds,
+        })
+    }
+
+    /// The threshold measurements may not exceed
+    pub(crate) fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Measure a query match, returning the measurement if it exceeds the threshold
+    ///
+    /// `reported` is the node the match is reported at. Matches without the
+    /// measured capture never exceed the threshold.
+    pub(crate) fn exceeded(&self, reported: Node, captures: &[QueryCapture]) -> Option<usize> {
+        let node = match self.capture {
+            None => reported,
+            Some(index) => captures.iter().find(|c| c.index == index)?.node,
+        };
+        let value = self.measure(node);
+        (value > self.max).then_some(value)
+    }
+
+    fn measure(&self, node: Node) -> usize {
+        match self.measure {
+            Measure::Lines => node.end_position().row - node.start_position().row + 1,
+            Measure::Nesting => self.nesting(node),
+            Measure::Children => {
+                let mut cursor = node.walk();
+                node.named_children(&mut cursor)
+                    .filter(|child| !child.is_extra())
+                    .filter(|child| self.kinds.is_empty() || self.is_counted(*child))
+                    .count()
+            }
+            Measure::Branches => 1 + self.descendants(node),
+        }
+    }
+
+    fn is_counted(&self, node: Node) -> bool {
+        self.kinds.iter().any(|kind| kind == node.kind())
+    }
+
+    /// Deepest nesting of counted nodes below `node`
+    fn nesting(&self, node: Node) -> usize {
+        let mut cursor = node.walk();
+        node.children(&mut cursor)
+            .map(|child| self.nesting(child) + usize::from(self.is_counted(child)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Number of counted nodes below `node`
+    fn descendants(&self, node: Node) -> usize {
+        let mut cursor = node.walk();
+        node.children(&mut cursor)
+            .map(|child| self.descendants(child) + usize::from(self.is_counted(child)))
+            .sum()
+    }
+}
+
+#[cfg(all(test, feature = "lang-python"))]
+mod tests {
+    use super::*;
+    use crate::rules::ParserCache;
+    use crate::types::Language;
+
+    const SOURCE: &str = "\
+def handle(a, b, c):
+    # comment
+    if a:
+        for x in b:
+            if x:
+                return x
+    elif c:
+        return c
+    return None
+";
+
+    fn measure(section: &str) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+        let mut parser = ParserCache::new().get_parser(Language::Python)?;
+        let tree = parser.parse(SOURCE, None).ok_or("parse failed")?;
+        let query = Query::new(
+            &tree.language(),
+            "(function_definition parameters: (parameters) @params) @violation",
+        )?;
+        let metric = Metric::compile(toml::from_str(section)?, &query)?;
+        let violation = query
+            .capture_index_for_name("violation")
+            .ok_or("no @violation")?;
+
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let m = cursor
+            .matches(&query, tree.root_node(), SOURCE.as_bytes())
+            .next()
+            .ok_or("no match")?;
+        let reported = m
+            .captures
+            .iter()
+            .find(|c| c.index == violation)
+            .ok_or("no reported node")?;
+        Ok(metric.exceeded(reported.node, m.captures))
+    }
+
+    #[test]
+    fn test_measures() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(measure("measure = \"lines\"\nmax = 5")?, Some(9));
+        assert_eq!(measure("measure = \"lines\"\nmax = 9")?, None);
+        let nesting = "measure = \"nesting\"\nmax = 0\n\
+                       kinds = [\"if_statement\", \"for_statement\"]";
+        assert_eq!(measure(nesting)?, Some(3));
+        let children = "measure = \"children\"\nmax = 0\ncapture = \"params\"";
+        assert_eq!(measure(children)?, Some(3));
+        let branches = "measure = \"branches\"\nmax = 0\n\
+                        kinds = [\"if_statement\", \"elif_clause\", \"for_statement\"]";
+        assert_eq!(measure(branches)?, Some(5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_metrics() {
+        assert!(measure("measure = \"nesting\"\nmax = 3").is_err());
+        assert!(measure("measure = \"lines\"\nmax = 3\ncapture = \"body\"").is_err());
+        assert!(measure("measure = \"depth\"\nmax = 3").is_err());
+    }
+}
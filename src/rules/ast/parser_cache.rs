@@ -90,14 +90,33 @@ impl ParserCache {
         Self::create_parser_for_language(language)
     }
 
+    /// Identifies the language a syntax tree was parsed as
+    ///
+    /// Returns None if the tree's grammar is not one of the enabled languages.
+    pub fn language_of(tree: &tree_sitter::Tree) -> Option<Language> {
+        let grammar = tree.language();
+        Language::all().find(|&language| {
+            Self::create_parser_for_language(language)
+                .ok()
+                .and_then(|parser| parser.language())
+                .is_some_and(|candidate| candidate == *grammar)
+        })
+    }
+
     /// Creates a parser for the given language
     fn create_parser_for_language(language: Language) -> Result<tree_sitter::Parser, ParserError> {
         match language {
             Language::Rust => Self::create_rust_parser(),
             Language::TypeScript => Self::create_typescript_parser(),
+            Language::Tsx => Self::create_tsx_parser(),
             Language::JavaScript => Self::create_javascript_parser(),
             Language::Python => Self::create_python_parser(),
             Language::Go => Self::create_go_parser(),
+            Language::Java => Self::create_java_parser(),
+            Language::Kotlin => Self::create_kotlin_parser(),
+            Language::C => Self::create_c_parser(),
+            Language::Cpp => Self::create_cpp_parser(),
+            Language::Bash => Self::create_bash_parser(),
         }
     }
 
@@ -131,6 +150,21 @@ impl ParserCache {
         }
     }
 
+    fn create_tsx_parser() -> Result<tree_sitter::Parser, ParserError> {
+        #[cfg(feature = "lang-typescript")]
+        {
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_typescript::language_tsx())
+                .map_err(|_| ParserError::InitializationFailed(Language::Tsx))?;
+            Ok(parser)
+        }
+        #[cfg(not(feature = "lang-typescript"))]
+        {
+            Err(ParserError::UnsupportedLanguage(Language::Tsx))
+        }
+    }
+
     fn create_javascript_parser() -> Result<tree_sitter::Parser, ParserError> {
         #[cfg(feature = "lang-javascript")]
         {
@@ -175,6 +209,81 @@ impl ParserCache {
             Err(ParserError::UnsupportedLanguage(Language::Go))
         }
     }
+
+    fn create_java_parser() -> Result<tree_sitter::Parser, ParserError> {
+        #[cfg(feature = "lang-java")]
+        {
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_java::language())
+                .map_err(|_| ParserError::InitializationFailed(Language::Java))?;
+            Ok(parser)
+        }
+        #[cfg(not(feature = "lang-java"))]
+        {
+            Err(ParserError::UnsupportedLanguage(Language::Java))
+        }
+    }
+
+    fn create_kotlin_parser() -> Result<tree_sitter::Parser, ParserError> {
+        #[cfg(feature = "lang-kotlin")]
+        {
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_kotlin::language())
+                .map_err(|_| ParserError::InitializationFailed(Language::Kotlin))?;
+            Ok(parser)
+        }
+        #[cfg(not(feature = "lang-kotlin"))]
+        {
+            Err(ParserError::UnsupportedLanguage(Language::Kotlin))
+        }
+    }
+
+    fn create_c_parser() -> Result<tree_sitter::Parser, ParserError> {
+        #[cfg(feature = "lang-c")]
+        {
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_c::language())
+                .map_err(|_| ParserError::InitializationFailed(Language::C))?;
+            Ok(parser)
+        }
+        #[cfg(not(feature = "lang-c"))]
+        {
+            Err(ParserError::UnsupportedLanguage(Language::C))
+        }
+    }
+
+    fn create_cpp_parser() -> Result<tree_sitter::Parser, ParserError> {
+        #[cfg(feature = "lang-cpp")]
+        {
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_cpp::language())
+                .map_err(|_| ParserError::InitializationFailed(Language::Cpp))?;
+            Ok(parser)
+        }
+        #[cfg(not(feature = "lang-cpp"))]
+        {
+            Err(ParserError::UnsupportedLanguage(Language::Cpp))
+        }
+    }
+
+    fn create_bash_parser() -> Result<tree_sitter::Parser, ParserError> {
+        #[cfg(feature = "lang-bash")]
+        {
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_bash::language())
+                .map_err(|_| ParserError::InitializationFailed(Language::Bash))?;
+            Ok(parser)
+        }
+        #[cfg(not(feature = "lang-bash"))]
+        {
+            Err(ParserError::UnsupportedLanguage(Language::Bash))
+        }
+    }
 }
 
 impl Default for ParserCache {
@@ -222,6 +331,26 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "lang-typescript")]
+    #[test]
+    fn test_tsx_parser_parses_jsx() -> Result<(), Box<dyn std::error::Error>> {
+        let cache = ParserCache::new();
+        let content = "const App = (): JSX.Element => <div className=\"app\">{title}</div>;\n";
+
+        let mut tsx = cache.get_parser(Language::Tsx)?;
+        let tree = tsx.parse(content, None).ok_or("failed to parse TSX")?;
+        assert!(!tree.root_node().has_error());
+        assert_eq!(ParserCache::language_of(&tree), Some(Language::Tsx));
+
+        // The plain TypeScript grammar has no JSX
+        let mut typescript = cache.get_parser(Language::TypeScript)?;
+        let tree = typescript
+            .parse(content, None)
+            .ok_or("failed to parse TypeScript")?;
+        assert!(tree.root_node().has_error());
+        Ok(())
+    }
+
     #[cfg(feature = "lang-javascript")]
     #[test]
     fn test_javascript_parser_loading() {
@@ -255,6 +384,61 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "lang-java")]
+    #[test]
+    fn test_java_parser_loading() {
+        let cache = ParserCache::new();
+        let result = cache.get_parser(Language::Java);
+        assert!(
+            result.is_ok(),
+            "Java parser should load when feature is enabled"
+        );
+    }
+
+    #[cfg(feature = "lang-kotlin")]
+    #[test]
+    fn test_kotlin_parser_loading() {
+        let cache = ParserCache::new();
+        let result = cache.get_parser(Language::Kotlin);
+        assert!(
+            result.is_ok(),
+            "Kotlin parser should load when feature is enabled"
+        );
+    }
+
+    #[cfg(feature = "lang-c")]
+    #[test]
+    fn test_c_parser_loading() {
+        let cache = ParserCache::new();
+        let result = cache.get_parser(Language::C);
+        assert!(
+            result.is_ok(),
+            "C parser should load when feature is enabled"
+        );
+    }
+
+    #[cfg(feature = "lang-cpp")]
+    #[test]
+    fn test_cpp_parser_loading() {
+        let cache = ParserCache::new();
+        let result = cache.get_parser(Language::Cpp);
+        assert!(
+            result.is_ok(),
+            "C++ parser should load when feature is enabled"
+        );
+    }
+
+    #[cfg(feature = "lang-bash")]
+    #[test]
+    fn test_bash_parser_loading() {
+        let cache = ParserCache::new();
+        let result = cache.get_parser(Language::Bash);
+        assert!(
+            result.is_ok(),
+            "Bash parser should load when feature is enabled"
+        );
+    }
+
     #[cfg(feature = "lang-rust")]
     #[test]
     fn test_parser_caching() -> Result<(), Box<dyn std::error::Error>> {
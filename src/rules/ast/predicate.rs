@@ -0,0 +1,314 @@
+#![forbid(unsafe_code)]
+
+//! Declarative match predicates for AST rules
+//!
+//! Tree-sitter queries cannot express some conditions, such as negative text
+//! matching or "has an ancestor of this kind at any depth". AST rules add
+//! these as `[[match.where]]` tables; a match is reported only if every
+//! predicate holds:
+//!
+//! ```toml
+//! [[match.where]]
+//! capture = "class_name"            # optional, defaults to the reported node
+//! not_matches = "(Exception|Error)$"
+//!
+//! [[match.where]]
+//! has_ancestor = "function_definition"
+//! stop_at = "class_definition"      # stop the ancestor search at this kind
+//! ```
+//!
+//! Keys within one table are combined with AND as well. Node kinds accept a
+//! single kind or a list of kinds.
+
+use crate::error::RuleError;
+use regex::Regex;
+use serde::Deserialize;
+use tree_sitter::{Node, Query, QueryCapture};
+
+/// TOML structure of one `[[match.where]]` table
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct WhereSection {
+    /// Capture the conditions apply to; defaults to the reported node
+    #[serde(default)]
+    capture: Option<String>,
+    #[serde(default)]
+    matches: Option<String>,
+    #[serde(default)]
+    not_matches: Option<String>,
+    #[serde(default)]
+    has_ancestor: Option<KindList>,
+    #[serde(default)]
+    lacks_ancestor: Option<KindList>,
+    /// Ancestor kinds that end the ancestor search without a match
+    #[serde(default)]
+    stop_at: Option<KindList>,
+    #[serde(default)]
+    has_child: Option<KindList>,
+    #[serde(default)]
+    lacks_child: Option<KindList>,
+    #[serde(default)]
+    has_sibling: Option<KindList>,
+    #[serde(default)]
+    lacks_sibling: Option<KindList>,
+    #[serde(default)]
+    min_depth: Option<usize>,
+    #[serde(default)]
+    max_depth: Option<usize>,
+}
+
+/// One node kind or a list of node kinds
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum KindList {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl KindList {
+    pub(crate) fn into_vec(self) -> Vec<String> {
+        match self {
+            KindList::Single(kind) => vec![kind],
+            KindList::Multiple(kinds) => kinds,
+        }
+    }
+}
+
+/// A compiled `[[match.where]]` table
+#[derive(Debug, Clone)]
+pub(crate) struct Predicate {
+    /// Index of the capture the conditions apply to; None for the reported node
+    capture: Option<u32>,
+    conditions: Vec<Condition>,
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    /// The node's text matches (or with `present: false`, does not match) the regex
+    Text { regex: Regex, present: bool },
+    /// An ancestor has one of `kinds`, found before any of `stop_at`
+    Ancestor {
+        kinds: Vec<String>,
+        stop_at: Vec<String>,
+        present: bool,
+    },
+    /// A child has one of `kinds`
+    Child { kinds: Vec<String>, present: bool },
+    /// Another child of the node's parent has one of `kinds`
+    Sibling { kinds: Vec<String>, present: bool },
+    /// The node's depth (number of ancestors) is within bounds
+    Depth {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+}
+
+impl Condition {
+    /// Whether the condition asserts the absence of something
+    ///
+    /// Negative conditions hold for captures that are absent from the match.
+    fn is_negative(&self) -> bool {
+        match self {
+            Condition::Text { present, .. }
+            | Condition::Ancestor { present, .. }
+            | Condition::Child { present, .. }
+            | Condition::Sibling { present, .. } => !present,
+            Condition::Depth { .. } => false,
+        }
+    }
+
+    fn holds(&self, node: Node, content: &str) -> bool {
+        match self {
+            Condition::Text { regex, present } => {
+                let text = content.get(node.byte_range()).unwrap_or_default();
+                regex.is_match(text) == *present
+            }
+            Condition::Ancestor {
+                kinds,
+                stop_at,
+                present,
+            } => {
+                let mut found = false;
+                let mut current = node.parent();
+                while let Some(ancestor) = current {
+                    let kind = ancestor.kind();
+                    if stop_at.iter().any(|k| k == kind) {
+                        break;
+                    }
+                    if kinds.iter().any(|k| k == kind) {
+                        found = true;
+                        break;
+                    }
+                    current = ancestor.parent();
+                }
+                found == *present
+            }
+            Condition::Child { kinds, present } => {
+                let mut cursor = node.walk();
+                let found = node
+                    .children(&mut cursor)
+                    .any(|child| kinds.iter().any(|k| k == child.kind()));
+                found == *present
+            }
+            Condition::Sibling { kinds, present } => {
+                let found = node.parent().is_some_and(|parent| {
+                    let mut cursor = parent.walk();
+                    parent.children(&mut cursor).any(|sibling| {
+                        sibling.id() != node.id() && kinds.iter().any(|k| k == sibling.kind())
+                    })
+                });
+                found == *present
+            }
+            Condition::Depth { min, max } => {
+                let mut depth = 0;
+                let mut current = node.parent();
+                while let Some(ancestor) = current {
+                    depth += 1;
+                    current = ancestor.parent();
+                }
+                min.is_none_or(|min| depth >= min) && max.is_none_or(|max| depth <= max)
+            }
+        }
+    }
+}
+
+impl Predicate {
+    /// Compile a `[[match.where]]` table against the rule's query
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if the capture is not in the
+    /// query, a regex is invalid, `stop_at` has no ancestor condition to
+    /// bound, or the table has no condition at all.
+    pub(crate) fn compile(section: WhereSection, query: &Query) -> Result<Self, RuleError> {
+        let capture = section
+            .capture
+            .map(|name| {
+                query.capture_index_for_name(&name).ok_or_else(|| {
+                    RuleError::InvalidDefinition(format!(
+                        "[[match.where]] refers to unknown capture '@{}'",
+                        name
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let regex = |pattern: String| {
+            Regex::new(&pattern).map_err(|e| {
+                RuleError::InvalidRegex(format!(
+                    "Failed to compile [[match.where]] pattern '{}': {}",
+                    pattern, e
+                ))
+            })
+        };
+
+        let mut conditions = Vec::new();
+        if let Some(pattern) = section.matches {
+            conditions.push(Condition::Text {
+                regex: regex(pattern)?,
+                present: true,
+            });
+        }
+        if let Some(pattern) = section.not_matches {
+            conditions.push(Condition::Text {
+                regex: regex(pattern)?,
+                present: false,
+            });
+        }
+
+        let stop_at = section.stop_at.map(KindList::into_vec);
+        if stop_at.is_some() && section.has_ancestor.is_none() && section.lacks_ancestor.is_none() {
+            return Err(RuleError::InvalidDefinition(
+                "[[match.where]] stop_at requires has_ancestor or lacks_ancestor".to_string(),
+            ));
+        }
+        let stop_at = stop_at.unwrap_or_default();
+        for (kinds, present) in [
+            (section.has_ancestor, true),
+            (section.lacks_ancestor, false),
+        ] {
+            if let Some(kinds) = kinds {
+                conditions.push(Condition::Ancestor {
+                    kinds: kinds.into_vec(),
+                    stop_at: stop_at.clone(),
+                    present,
+                });
+            }
+        }
+        for (kinds, present) in [(section.has_child, true), (section.lacks_child, false)] {
+            if let Some(kinds) = kinds {
+                conditions.push(Condition::Child {
+                    kinds: kinds.into_vec(),
+                    present,
+                });
+            }
+        }
+        for (kinds, present) in [(section.has_sibling, true), (section.lacks_sibling, false)] {
+            if let Some(kinds) = kinds {
+                conditions.push(Condition::Sibling {
+                    kinds: kinds.into_vec(),
+                    present,
+                });
+            }
+        }
+        if section.min_depth.is_some() || section.max_depth.is_some() {
+            conditions.push(Condition::Depth {
+                min: section.min_depth,
+                max: section.max_depth,
+            });
+        }
+
+        if conditions.is_empty() {
+            return Err(RuleError::InvalidDefinition(
+                "[[match.where]] has no condition".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            capture,
+            conditions,
+        })
+    }
+
+    /// Whether the predicate holds for a query match
+    ///
+    /// `reported` is the node the match is reported at. When the predicate's
+    /// capture is absent from the match, only negative conditions
+    /// (`not_matches` and `lacks_*`) hold.
+    pub(crate) fn holds(&self, reported: Node, captures: &[QueryCapture], content: &str) -> bool {
+        let node = match self.capture {
+            None => Some(reported),
+            Some(index) => captures.iter().find(|c| c.index == index).map(|c| c.node),
+        };
+        match node {
+            Some(node) => self.conditions.iter().all(|c| c.holds(node, content)),
+            None => self.conditions.iter().all(Condition::is_negative),
+        }
+    }
+}
+
+/// Express a legacy `post_filter` name as `[[match.where]]` tables
+///
+/// # Errors
+///
+/// Returns `RuleError::InvalidDefinition` for unknown filter names.
+pub(crate) fn post_filter_sections(filter_name: &str) -> Result<Vec<WhereSection>, RuleError> {
+    match filter_name {
+        // Drop classes whose names end with "Exception" or "Error"
+        "class_name_not_exception" => Ok(vec![WhereSection {
+            capture: Some("class_name".to_string()),
+            not_matches: Some("(Exception|Error)$".to_string()),
+            ..WhereSection::default()
+        }]),
+        // Keep nodes inside a function, without crossing a class first
+        "nested_in_function_definition" => Ok(vec![WhereSection {
+            has_ancestor: Some(KindList::Single("function_definition".to_string())),
+            stop_at: Some(KindList::Single("class_definition".to_string())),
+            ..WhereSection::default()
+        }]),
+        _ => Err(RuleError::InvalidDefinition(format!(
+            "Unknown post_filter: {}",
+            filter_name
+        ))),
+    }
+}
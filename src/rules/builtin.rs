@@ -8,6 +8,7 @@
 
 use crate::config::sets::RatchetSet;
 use crate::error::RuleError;
+use crate::rules::template::RuleTemplate;
 use crate::rules::{AstRule, RegexRule, Rule};
 use crate::types::RuleId;
 
@@ -25,6 +26,12 @@ const BUILTIN_SETS: &[(&str, &str)] = &[(
     include_str!("../../builtin-ratchets/sets/common-starter.toml"),
 )];
 
+/// Embedded built-in rule templates
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[(
+    "python-no-module-import",
+    include_str!("../../builtin-ratchets/templates/python-no-module-import.toml"),
+)];
+
 /// Embedded built-in regex rule files
 const BUILTIN_REGEX_RULES: &[(&str, &str)] = &[
     (
@@ -230,6 +237,10 @@ const BUILTIN_AST_RUST_RULES: &[(&str, &str)] = &[
         "rust-no-fixme-comments",
         include_str!("../../builtin-ratchets/rust/ast/no-fixme-comments.toml"),
     ),
+    (
+        "rust-max-function-length",
+        include_str!("../../builtin-ratchets/rust/ast/max-function-length.toml"),
+    ),
 ];
 
 /// Embedded built-in AST rule files for Python
@@ -340,15 +351,84 @@ const BUILTIN_AST_PYTHON_RULES: &[(&str, &str)] = &[
         "no-typing-builtin-imports",
         include_str!("../../builtin-ratchets/python/ast/no-typing-builtin-imports.toml"),
     ),
+    (
+        "python-max-function-length",
+        include_str!("../../builtin-ratchets/python/ast/max-function-length.toml"),
+    ),
 ];
 
 /// Embedded built-in AST rule files for TypeScript
 #[cfg(feature = "lang-typescript")]
-const BUILTIN_AST_TYPESCRIPT_RULES: &[(&str, &str)] = &[(
-    "no-any",
-    include_str!("../../builtin-ratchets/typescript/ast/no-any.toml"),
+const BUILTIN_AST_TYPESCRIPT_RULES: &[(&str, &str)] = &[
+    (
+        "no-any",
+        include_str!("../../builtin-ratchets/typescript/ast/no-any.toml"),
+    ),
+    (
+        "typescript-max-function-length",
+        include_str!("../../builtin-ratchets/typescript/ast/max-function-length.toml"),
+    ),
+];
+
+/// Embedded built-in AST rule files for Java
+#[cfg(feature = "lang-java")]
+const BUILTIN_AST_JAVA_RULES: &[(&str, &str)] = &[
+    (
+        "java-no-system-out-println",
+        include_str!("../../builtin-ratchets/java/ast/no-system-out-println.toml"),
+    ),
+    (
+        "java-no-catch-exception",
+        include_str!("../../builtin-ratchets/java/ast/no-catch-exception.toml"),
+    ),
+];
+
+/// Embedded built-in AST rule files for Kotlin
+#[cfg(feature = "lang-kotlin")]
+const BUILTIN_AST_KOTLIN_RULES: &[(&str, &str)] = &[
+    (
+        "kotlin-no-println",
+        include_str!("../../builtin-ratchets/kotlin/ast/no-println.toml"),
+    ),
+    (
+        "kotlin-no-catch-exception",
+        include_str!("../../builtin-ratchets/kotlin/ast/no-catch-exception.toml"),
+    ),
+];
+
+/// Embedded built-in AST rule files for C
+#[cfg(feature = "lang-c")]
+const BUILTIN_AST_C_RULES: &[(&str, &str)] = &[(
+    "c-no-unsafe-string-functions",
+    include_str!("../../builtin-ratchets/c/ast/no-unsafe-string-functions.toml"),
 )];
 
+/// Embedded built-in AST rule files for C++
+#[cfg(feature = "lang-cpp")]
+const BUILTIN_AST_CPP_RULES: &[(&str, &str)] = &[
+    (
+        "cpp-no-unsafe-string-functions",
+        include_str!("../../builtin-ratchets/cpp/ast/no-unsafe-string-functions.toml"),
+    ),
+    (
+        "cpp-no-c-style-casts",
+        include_str!("../../builtin-ratchets/cpp/ast/no-c-style-casts.toml"),
+    ),
+];
+
+/// Embedded built-in AST rule files for shell scripts
+#[cfg(feature = "lang-bash")]
+const BUILTIN_AST_BASH_RULES: &[(&str, &str)] = &[
+    (
+        "bash-no-unquoted-rm-expansion",
+        include_str!("../../builtin-ratchets/bash/ast/no-unquoted-rm-expansion.toml"),
+    ),
+    (
+        "bash-no-eval",
+        include_str!("../../builtin-ratchets/bash/ast/no-eval.toml"),
+    ),
+];
+
 /// Parse `source` as embedded regex rules and append them to `rules`. `label`
 /// is interpolated into the parse-error message verbatim.
 fn extend_regex_rules(
@@ -477,6 +557,36 @@ pub fn load_builtin_ast_rules() -> Result<RuleList, RuleError> {
         "TypeScript AST",
     )?;
 
+    #[cfg(feature = "lang-java")]
+    extend_ast_rules(
+        &mut rules,
+        BUILTIN_AST_JAVA_RULES,
+        &rule_context,
+        "Java AST",
+    )?;
+
+    #[cfg(feature = "lang-kotlin")]
+    extend_ast_rules(
+        &mut rules,
+        BUILTIN_AST_KOTLIN_RULES,
+        &rule_context,
+        "Kotlin AST",
+    )?;
+
+    #[cfg(feature = "lang-c")]
+    extend_ast_rules(&mut rules, BUILTIN_AST_C_RULES, &rule_context, "C AST")?;
+
+    #[cfg(feature = "lang-cpp")]
+    extend_ast_rules(&mut rules, BUILTIN_AST_CPP_RULES, &rule_context, "C++ AST")?;
+
+    #[cfg(feature = "lang-bash")]
+    extend_ast_rules(
+        &mut rules,
+        BUILTIN_AST_BASH_RULES,
+        &rule_context,
+        "Bash AST",
+    )?;
+
     Ok(rules)
 }
 
@@ -515,6 +625,25 @@ pub fn load_builtin_sets() -> Result<Vec<RatchetSet>, RuleError> {
     Ok(sets)
 }
 
+/// Load all built-in rule templates from embedded resources
+///
+/// # Errors
+///
+/// Returns [`RuleError`] if any embedded template TOML fails to parse.
+pub fn load_builtin_templates() -> Result<Vec<RuleTemplate>, RuleError> {
+    BUILTIN_TEMPLATES
+        .iter()
+        .map(|(template_name, toml_content)| {
+            RuleTemplate::from_toml(toml_content).map_err(|e| {
+                RuleError::InvalidDefinition(format!(
+                    "Failed to parse built-in template '{}': {}",
+                    template_name, e
+                ))
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -575,6 +704,26 @@ mod tests {
         {
             expected += BUILTIN_AST_TYPESCRIPT_RULES.len();
         }
+        #[cfg(feature = "lang-java")]
+        {
+            expected += BUILTIN_AST_JAVA_RULES.len();
+        }
+        #[cfg(feature = "lang-kotlin")]
+        {
+            expected += BUILTIN_AST_KOTLIN_RULES.len();
+        }
+        #[cfg(feature = "lang-c")]
+        {
+            expected += BUILTIN_AST_C_RULES.len();
+        }
+        #[cfg(feature = "lang-cpp")]
+        {
+            expected += BUILTIN_AST_CPP_RULES.len();
+        }
+        #[cfg(feature = "lang-bash")]
+        {
+            expected += BUILTIN_AST_BASH_RULES.len();
+        }
         assert_eq!(rules.len(), expected);
 
         // Verify Rust rules are present when lang-rust feature is enabled
@@ -614,6 +763,41 @@ mod tests {
             let rule_ids: Vec<&str> = rules.iter().map(|(id, _)| id.as_str()).collect();
             assert!(rule_ids.contains(&"no-any"));
         }
+
+        // Verify JVM rules are present when their features are enabled
+        #[cfg(feature = "lang-java")]
+        {
+            let rule_ids: Vec<&str> = rules.iter().map(|(id, _)| id.as_str()).collect();
+            assert!(rule_ids.contains(&"java-no-system-out-println"));
+            assert!(rule_ids.contains(&"java-no-catch-exception"));
+        }
+        #[cfg(feature = "lang-kotlin")]
+        {
+            let rule_ids: Vec<&str> = rules.iter().map(|(id, _)| id.as_str()).collect();
+            assert!(rule_ids.contains(&"kotlin-no-println"));
+            assert!(rule_ids.contains(&"kotlin-no-catch-exception"));
+        }
+
+        // Verify C and C++ rules are present when their features are enabled
+        #[cfg(feature = "lang-c")]
+        {
+            let rule_ids: Vec<&str> = rules.iter().map(|(id, _)| id.as_str()).collect();
+            assert!(rule_ids.contains(&"c-no-unsafe-string-functions"));
+        }
+        #[cfg(feature = "lang-cpp")]
+        {
+            let rule_ids: Vec<&str> = rules.iter().map(|(id, _)| id.as_str()).collect();
+            assert!(rule_ids.contains(&"cpp-no-unsafe-string-functions"));
+            assert!(rule_ids.contains(&"cpp-no-c-style-casts"));
+        }
+
+        // Verify shell rules are present when lang-bash feature is enabled
+        #[cfg(feature = "lang-bash")]
+        {
+            let rule_ids: Vec<&str> = rules.iter().map(|(id, _)| id.as_str()).collect();
+            assert!(rule_ids.contains(&"bash-no-unquoted-rm-expansion"));
+            assert!(rule_ids.contains(&"bash-no-eval"));
+        }
         Ok(())
     }
 
@@ -0,0 +1,38 @@
+#![forbid(unsafe_code)]
+
+//! Rule documentation metadata
+//!
+//! The `[rule]` section of a rule file may explain the rule for
+//! `ratchets explain`:
+//!
+//! ```toml
+//! [rule]
+//! id = "no-eval"
+//! description = "Disallow eval()"
+//! severity = "error"
+//! rationale = "eval runs arbitrary code and defeats static analysis."
+//! help_url = "https://docs.python.org/3/library/functions.html#eval"
+//! bad = "value = eval(text)"
+//! good = "value = ast.literal_eval(text)"
+//! ```
+//!
+//! Every field is optional and none of them affect matching.
+
+use serde::Deserialize;
+
+/// Explanatory metadata of a rule
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct RuleDocs {
+    /// Why the rule exists
+    #[serde(default)]
+    pub rationale: Option<String>,
+    /// Link to further reading
+    #[serde(default)]
+    pub help_url: Option<String>,
+    /// Example code the rule accepts
+    #[serde(default)]
+    pub good: Option<String>,
+    /// Example code the rule reports
+    #[serde(default)]
+    pub bad: Option<String>,
+}
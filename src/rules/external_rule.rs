@@ -0,0 +1,580 @@
+#![forbid(unsafe_code)]
+
+//! External (executable-backed) rule implementation
+//!
+//! This module provides ExternalRule, which runs a local command over the
+//! files it applies to and turns the violations the command reports into
+//! ratchets violations. Checks that are easier to write as a script than as a
+//! regex or tree-sitter query can then be budgeted like any other rule.
+
+use crate::error::RuleError;
+use crate::rules::regex_rule::{GlobPatternList, build_globset_with_context};
+use crate::rules::rule::normalize_for_glob_match;
+use crate::rules::{ExecutionContext, Rule, RuleContext, RuleDocs, RuleKind, Violation};
+use crate::types::{Language, RegionPath, RuleId, Severity};
+use globset::GlobSet;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Seconds a command may run when the rule sets no `timeout`
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// How often a running command is polled for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Bytes of file arguments passed to one run of the command
+///
+/// Longer file lists are split across several runs, as `xargs` does, so the
+/// command line stays well under the operating system's limit.
+const MAX_ARG_BYTES: usize = 128 * 1024;
+
+/// TOML structure for external rule definitions
+///
+/// This structure is deserialized from TOML files in ratchets/external/.
+#[derive(Debug, Deserialize)]
+struct ExternalRuleDefinition {
+    rule: RuleSection,
+    command: CommandSection,
+    #[serde(default, rename = "match")]
+    match_section: MatchSection,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleSection {
+    id: String,
+    description: String,
+    severity: Severity,
+    #[serde(flatten)]
+    docs: RuleDocs,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommandSection {
+    run: Vec<String>,
+    #[serde(default)]
+    input: FileInput,
+    #[serde(default)]
+    timeout: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MatchSection {
+    #[serde(default)]
+    languages: Option<Vec<Language>>,
+    #[serde(default)]
+    include: Option<GlobPatternList>,
+    #[serde(default)]
+    exclude: Option<GlobPatternList>,
+}
+
+/// How the command receives the files to check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FileInput {
+    /// Appended to the command's arguments, split across runs if there are many
+    #[default]
+    Args,
+    /// Written to the command's stdin, one path per line
+    Stdin,
+}
+
+/// A violation record in the command's JSONL output
+///
+/// Uses the fields of the `violation` records of `ratchets check --format
+/// jsonl`; fields only ratchets can fill in, such as `rule` and `region`, are
+/// ignored.
+#[derive(Debug, Deserialize)]
+struct ViolationRecord {
+    #[serde(default, rename = "type")]
+    record_type: Option<String>,
+    file: PathBuf,
+    line: u32,
+    #[serde(default)]
+    column: Option<u32>,
+    #[serde(default)]
+    end_line: Option<u32>,
+    #[serde(default)]
+    end_column: Option<u32>,
+    #[serde(default)]
+    snippet: Option<String>,
+    message: String,
+}
+
+/// A rule whose violations come from an external command
+///
+/// ExternalRule runs its command once per check with every file it applies
+/// to, as arguments or on stdin; file lists too long for one command line
+/// are passed to several runs. The command prints one JSON object per
+/// violation (`file`, `line` and `message`, optionally `column`, `end_line`,
+/// `end_column` and `snippet`) and exits with status 0, whether or not it
+/// found violations. A command that fails to start, exits with another status,
+/// prints invalid records or outlives its timeout fails the check.
+///
+/// Results depend on the command, so they are never cached.
+pub struct ExternalRule {
+    id: RuleId,
+    description: String,
+    severity: Severity,
+    command: Vec<String>,
+    input: FileInput,
+    timeout: Duration,
+    languages: Vec<Language>,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    docs: RuleDocs,
+}
+
+impl std::fmt::Debug for ExternalRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalRule")
+            .field("id", &self.id)
+            .field("description", &self.description)
+            .field("severity", &self.severity)
+            .field("command", &self.command)
+            .field("input", &self.input)
+            .field("timeout", &self.timeout)
+            .field("languages", &self.languages)
+            .field("include", &"<GlobSet>")
+            .field("exclude", &"<GlobSet>")
+            .field("docs", &self.docs)
+            .finish()
+    }
+}
+
+impl ExternalRule {
+    /// Parse an ExternalRule from TOML content
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if:
+    /// - TOML syntax is invalid
+    /// - Required fields are missing, or `run` is empty
+    /// - Rule ID is invalid
+    /// - Glob patterns are invalid
+    pub fn from_toml(content: &str) -> Result<Self, RuleError> {
+        Self::from_toml_with_context(content, None)
+    }
+
+    /// Parse an ExternalRule from TOML content with pattern context
+    ///
+    /// This method allows resolving pattern references (e.g., @python_tests) in
+    /// include and exclude using the provided RuleContext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if:
+    /// - TOML syntax is invalid
+    /// - Required fields are missing, or `run` is empty
+    /// - Rule ID is invalid
+    /// - Glob patterns are invalid
+    /// - A pattern reference is not found in the context
+    pub fn from_toml_with_context(
+        content: &str,
+        ctx: Option<&RuleContext>,
+    ) -> Result<Self, RuleError> {
+        let def: ExternalRuleDefinition = toml::from_str(content)
+            .map_err(|e| RuleError::InvalidDefinition(format!("Failed to parse TOML: {}", e)))?;
+
+        let id = RuleId::new(def.rule.id.clone()).ok_or_else(|| {
+            RuleError::InvalidDefinition(format!("Invalid rule ID: {}", def.rule.id))
+        })?;
+
+        if def.command.run.is_empty() {
+            return Err(RuleError::InvalidDefinition(format!(
+                "External rule '{}' has an empty run command",
+                id
+            )));
+        }
+
+        let globset = |patterns: Option<GlobPatternList>| {
+            patterns
+                .map(|patterns| build_globset_with_context(&patterns, ctx))
+                .transpose()
+        };
+
+        Ok(ExternalRule {
+            id,
+            description: def.rule.description,
+            severity: def.rule.severity,
+            command: def.command.run,
+            input: def.command.input,
+            timeout: Duration::from_secs(def.command.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS)),
+            languages: def.match_section.languages.unwrap_or_default(),
+            include: globset(def.match_section.include)?,
+            exclude: globset(def.match_section.exclude)?,
+            docs: def.rule.docs,
+        })
+    }
+
+    /// Parse an ExternalRule from a TOML file path
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if the file cannot be read or parsed.
+    pub fn from_path(path: &Path) -> Result<Self, RuleError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RuleError::InvalidDefinition(format!("Failed to read file {:?}: {}", path, e))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    /// Check if this rule applies to the given file path
+    fn applies_to_file(&self, file_path: &Path) -> bool {
+        let normalized = normalize_for_glob_match(file_path);
+
+        if let Some(ref exclude) = self.exclude
+            && exclude.is_match(normalized.as_ref())
+        {
+            return false;
+        }
+
+        match self.include {
+            Some(ref include) => include.is_match(normalized.as_ref()),
+            None => true,
+        }
+    }
+
+    /// Build the error for a failed run of the command
+    fn failure(&self, message: String) -> RuleError {
+        RuleError::Execution {
+            rule: self.id.to_string(),
+            message,
+        }
+    }
+
+    /// Run the command once over `paths` and return its stdout
+    ///
+    /// The timeout applies to this run alone.
+    fn run(&self, paths: &[&str]) -> Result<String, RuleError> {
+        let mut command = Command::new(&self.command[0]);
+        command
+            .args(&self.command[1..])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        match self.input {
+            FileInput::Args => {
+                command.args(paths).stdin(Stdio::null());
+            }
+            FileInput::Stdin => {
+                command.stdin(Stdio::piped());
+            }
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| self.failure(format!("failed to run '{}': {}", self.command[0], e)))?;
+
+        // Feed stdin and drain both pipes on their own threads, so a command
+        // that writes a lot before reading its input cannot deadlock
+        if let Some(mut stdin) = child.stdin.take() {
+            let input: String = paths.iter().map(|path| format!("{}\n", path)).collect();
+            thread::spawn(move || stdin.write_all(input.as_bytes()));
+        }
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            let polled = child
+                .try_wait()
+                .map_err(|e| self.failure(format!("failed to wait for command: {}", e)))?;
+            if let Some(status) = polled {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                // Killing the command may leave children of it holding the
+                // pipes open, so their reader threads are not joined
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(self.failure(format!(
+                    "timed out after {} seconds",
+                    self.timeout.as_secs()
+                )));
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        if !status.success() {
+            let stderr = stderr.join().unwrap_or_default();
+            let detail = stderr.trim();
+            return Err(self.failure(if detail.is_empty() {
+                format!("command exited with {}", status)
+            } else {
+                format!("command exited with {}: {}", status, detail)
+            }));
+        }
+        Ok(stdout)
+    }
+
+    /// Split `paths` into the lists passed to each run of the command
+    ///
+    /// Paths read from stdin all go to one run; paths passed as arguments are
+    /// split so each run's arguments take at most `MAX_ARG_BYTES`.
+    fn batches<'a>(&self, paths: &'a [String]) -> Vec<Vec<&'a str>> {
+        if self.input == FileInput::Stdin {
+            return vec![paths.iter().map(String::as_str).collect()];
+        }
+
+        let mut batches: Vec<Vec<&str>> = Vec::new();
+        let mut batch = Vec::new();
+        let mut bytes = 0;
+        for path in paths {
+            // Each argument also takes a terminating NUL
+            let size = path.len() + 1;
+            if !batch.is_empty() && bytes + size > MAX_ARG_BYTES {
+                batches.push(std::mem::take(&mut batch));
+                bytes = 0;
+            }
+            batch.push(path.as_str());
+            bytes += size;
+        }
+        batches.push(batch);
+        batches
+    }
+
+    /// Parse the command's output into violations of the given files
+    ///
+    /// `files` maps each path the command was given to the file it names.
+    /// Records for other files are rejected, so every violation is budgeted
+    /// under a file ratchets knows about.
+    fn parse_output(
+        &self,
+        output: &str,
+        files: &HashMap<&str, &Path>,
+    ) -> Result<Vec<Violation>, RuleError> {
+        let mut violations = Vec::new();
+        for (index, line) in output.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: ViolationRecord = serde_json::from_str(line)
+                .map_err(|e| self.failure(format!("invalid output line {}: {}", index + 1, e)))?;
+            if record
+                .record_type
+                .as_deref()
+                .is_some_and(|record_type| record_type != "violation")
+            {
+                continue;
+            }
+
+            let reported = normalize_for_glob_match(&record.file);
+            let file = reported
+                .to_str()
+                .and_then(|reported| files.get(reported))
+                .ok_or_else(|| {
+                    self.failure(format!(
+                        "reported a violation in {}, which it was not asked to check",
+                        record.file.display()
+                    ))
+                })?;
+            let line = record.line.max(1);
+            let column = record.column.unwrap_or(1).max(1);
+
+            violations.push(Violation {
+                rule_id: self.id.clone(),
+                file: file.to_path_buf(),
+                line,
+                column,
+                end_line: record.end_line.unwrap_or(line),
+                end_column: record.end_column.unwrap_or(column),
+                snippet: record.snippet.unwrap_or_default(),
+                message: record.message,
+                region: RegionPath::new("."),
+            });
+        }
+        Ok(violations)
+    }
+}
+
+/// Read a child's pipe to the end on a separate thread
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+impl Rule for ExternalRule {
+    fn id(&self) -> &RuleId {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn kind(&self) -> RuleKind {
+        RuleKind::External
+    }
+
+    /// External rules report nothing file by file; see `execute_batch`
+    fn execute(&self, _ctx: &ExecutionContext) -> Vec<Violation> {
+        Vec::new()
+    }
+
+    fn is_batch(&self) -> bool {
+        true
+    }
+
+    fn execute_batch(&self, files: &[&Path]) -> Result<Vec<Violation>, RuleError> {
+        let files: Vec<&Path> = files
+            .iter()
+            .copied()
+            .filter(|file| self.applies_to_file(file))
+            .collect();
+        if files.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let paths: Vec<String> = files
+            .iter()
+            .map(|file| {
+                normalize_for_glob_match(file)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        let by_path: HashMap<&str, &Path> = paths
+            .iter()
+            .map(String::as_str)
+            .zip(files.iter().copied())
+            .collect();
+
+        let mut violations = Vec::new();
+        for batch in self.batches(&paths) {
+            let output = self.run(&batch)?;
+            violations.extend(self.parse_output(&output, &by_path)?);
+        }
+        Ok(violations)
+    }
+
+    fn docs(&self) -> Option<&RuleDocs> {
+        Some(&self.docs)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn external_rule(command: &str, extra: &str) -> Result<ExternalRule, RuleError> {
+        ExternalRule::from_toml(&format!(
+            r#"
+[rule]
+id = "no-prints"
+description = "No print calls"
+severity = "warning"
+
+[command]
+run = ["sh", "-c", '''{}''', "sh"]
+{}
+
+[match]
+include = ["src/**"]
+"#,
+            command, extra
+        ))
+    }
+
+    #[test]
+    fn test_violations_are_read_from_the_command() -> Result<(), Box<dyn std::error::Error>> {
+        // Report line 2 of every file given as an argument
+        let script = r#"for f in "$@"; do
+  printf '{"type":"violation","file":"%s","line":2,"column":3,"message":"print in %s"}\n' "$f" "$f"
+done"#;
+        let rule = external_rule(script, "")?;
+        let files = [Path::new("./src/a.py"), Path::new("docs/b.py")];
+        let violations = rule.execute_batch(&files)?;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].file, PathBuf::from("./src/a.py"));
+        assert_eq!((violations[0].line, violations[0].column), (2, 3));
+        assert_eq!(violations[0].message, "print in src/a.py");
+
+        // The file list may be read from stdin instead
+        let script =
+            r#"while read f; do echo "{\"file\":\"$f\",\"line\":1,\"message\":\"m\"}"; done"#;
+        let rule = external_rule(script, "input = \"stdin\"")?;
+        assert_eq!(rule.execute_batch(&files)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_file_lists_are_split_across_runs() -> Result<(), Box<dyn std::error::Error>> {
+        let script = r#"for f in "$@"; do
+  printf '{"file":"%s","line":1,"message":"m"}\n' "$f"
+done"#;
+        let rule = external_rule(script, "")?;
+        let paths: Vec<PathBuf> = (0..20_000)
+            .map(|i| PathBuf::from(format!("src/module_{:05}.py", i)))
+            .collect();
+        let files: Vec<&Path> = paths.iter().map(PathBuf::as_path).collect();
+
+        let names: Vec<String> = paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let batches = rule.batches(&names);
+        assert!(batches.len() > 1);
+        assert!(batches.iter().all(|batch| {
+            batch.iter().map(|path| path.len() + 1).sum::<usize>() <= MAX_ARG_BYTES
+        }));
+        assert_eq!(batches.concat(), names);
+
+        let violations = rule.execute_batch(&files)?;
+        assert_eq!(violations.len(), files.len());
+        assert_eq!(violations[19_999].file, paths[19_999]);
+
+        // Paths read from stdin are not split
+        let rule = external_rule("cat >/dev/null", "input = \"stdin\"")?;
+        assert_eq!(rule.batches(&names).len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_failures_are_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let files = [Path::new("src/a.py")];
+        let failing = external_rule("echo broken >&2; exit 3", "")?;
+        let err = failing
+            .execute_batch(&files)
+            .err()
+            .ok_or("expected error")?;
+        assert!(err.to_string().contains("broken"));
+
+        let slow = external_rule("sleep 5", "timeout = 0")?;
+        let err = slow.execute_batch(&files).err().ok_or("expected error")?;
+        assert!(err.to_string().contains("timed out"));
+
+        let garbage = external_rule("echo not json", "")?;
+        assert!(garbage.execute_batch(&files).is_err());
+        let elsewhere = external_rule(r#"echo '{"file":"other.py","line":1,"message":"m"}'"#, "")?;
+        assert!(elsewhere.execute_batch(&files).is_err());
+
+        assert!(
+            ExternalRule::from_toml(&format!(
+                "{}\n[command]\nrun = []\n",
+                "[rule]\nid = \"x\"\ndescription = \"d\"\nseverity = \"error\""
+            ))
+            .is_err()
+        );
+        Ok(())
+    }
+}
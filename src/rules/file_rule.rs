@@ -0,0 +1,601 @@
+#![forbid(unsafe_code)]
+
+//! File-level rule implementation
+//!
+//! This module provides FileRule, which checks properties of whole files
+//! rather than their contents: their length, their name and path, and the
+//! presence of companion files next to them.
+
+use crate::error::RuleError;
+use crate::rules::message::MessageTemplate;
+use crate::rules::regex_rule::{GlobPatternList, build_globset_with_context};
+use crate::rules::rule::{definition_hash, normalize_for_glob_match};
+use crate::rules::self_test::TestSection;
+use crate::rules::{ExecutionContext, Rule, RuleContext, RuleDocs, RuleKind, RuleTest, Violation};
+use crate::types::{Language, RuleId, Severity};
+use globset::GlobSet;
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Placeholders available to the message template of a file rule
+const PLACEHOLDERS: [&str; 4] = ["name", "path", "lines", "sibling"];
+
+/// Placeholders available to the `require_sibling` template
+const SIBLING_PLACEHOLDERS: [&str; 3] = ["name", "stem", "ext"];
+
+/// TOML structure for file rule definitions
+///
+/// This structure is deserialized from TOML files in ratchets/files/.
+#[derive(Debug, Deserialize)]
+struct FileRuleDefinition {
+    rule: RuleSection,
+    #[serde(rename = "match")]
+    match_section: MatchSection,
+    #[serde(default, rename = "test")]
+    tests: Vec<TestSection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleSection {
+    id: String,
+    description: String,
+    severity: Severity,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(flatten)]
+    docs: RuleDocs,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MatchSection {
+    #[serde(default)]
+    max_lines: Option<usize>,
+    #[serde(default)]
+    forbid_name: Option<String>,
+    #[serde(default)]
+    require_name: Option<String>,
+    #[serde(default)]
+    forbid_path: Option<String>,
+    #[serde(default)]
+    require_sibling: Option<String>,
+    #[serde(default)]
+    languages: Option<Vec<Language>>,
+    #[serde(default)]
+    include: Option<GlobPatternList>,
+    #[serde(default)]
+    exclude: Option<GlobPatternList>,
+}
+
+/// A rule that checks files as a whole
+///
+/// FileRule reports, at line 1 of each file it applies to, one violation
+/// per check the file fails:
+/// - `max_lines`: the file has more lines than this
+/// - `forbid_name` / `require_name`: the file name matches / does not match a regex
+/// - `forbid_path`: the file's path matches a regex
+/// - `require_sibling`: no file named by this template was walked in the
+///   file's directory; `{stem}`, `{ext}` and `{name}` interpolate the file's
+///   name, e.g. `test_{stem}.py`
+///
+/// Violation messages may interpolate `{name}`, `{path}`, `{lines}` and
+/// `{sibling}` (the required sibling's path, if any), and are followed by
+/// the check that failed.
+pub struct FileRule {
+    id: RuleId,
+    description: String,
+    severity: Severity,
+    message: Option<MessageTemplate>,
+    max_lines: Option<usize>,
+    forbid_name: Option<Regex>,
+    require_name: Option<Regex>,
+    forbid_path: Option<Regex>,
+    require_sibling: Option<MessageTemplate>,
+    languages: Vec<Language>,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    tests: Vec<RuleTest>,
+    docs: RuleDocs,
+    definition_hash: u64,
+}
+
+impl std::fmt::Debug for FileRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileRule")
+            .field("id", &self.id)
+            .field("description", &self.description)
+            .field("severity", &self.severity)
+            .field("message", &self.message)
+            .field("max_lines", &self.max_lines)
+            .field("forbid_name", &self.forbid_name)
+            .field("require_name", &self.require_name)
+            .field("forbid_path", &self.forbid_path)
+            .field("require_sibling", &self.require_sibling)
+            .field("languages", &self.languages)
+            .field("include", &"<GlobSet>")
+            .field("exclude", &"<GlobSet>")
+            .field("tests", &self.tests)
+            .field("docs", &self.docs)
+            .finish()
+    }
+}
+
+/// Compile an optional regex from a file rule's `[match]` section
+fn compile_regex(field: &str, pattern: Option<String>) -> Result<Option<Regex>, RuleError> {
+    pattern
+        .map(|pattern| {
+            Regex::new(&pattern).map_err(|e| {
+                RuleError::InvalidRegex(format!("Failed to compile {} '{}': {}", field, pattern, e))
+            })
+        })
+        .transpose()
+}
+
+impl FileRule {
+    /// Parse a FileRule from TOML content
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if:
+    /// - TOML syntax is invalid
+    /// - Required fields are missing, or `[match]` sets no check
+    /// - Rule ID is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message or sibling template is invalid or names an unknown placeholder
+    /// - Glob patterns are invalid
+    ///
+    /// Returns `RuleError::InvalidRegex` if a name or path regex is invalid.
+    pub fn from_toml(content: &str) -> Result<Self, RuleError> {
+        Self::from_toml_with_context(content, None)
+    }
+
+    /// Parse a FileRule from TOML content with pattern context
+    ///
+    /// This method allows resolving pattern references (e.g., @python_tests) in
+    /// include and exclude using the provided RuleContext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if:
+    /// - TOML syntax is invalid
+    /// - Required fields are missing, or `[match]` sets no check
+    /// - Rule ID is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message or sibling template is invalid or names an unknown placeholder
+    /// - Glob patterns are invalid
+    /// - A pattern reference is not found in the context
+    ///
+    /// Returns `RuleError::InvalidRegex` if a name or path regex is invalid.
+    pub fn from_toml_with_context(
+        content: &str,
+        ctx: Option<&RuleContext>,
+    ) -> Result<Self, RuleError> {
+        let def: FileRuleDefinition = toml::from_str(content)
+            .map_err(|e| RuleError::InvalidDefinition(format!("Failed to parse TOML: {}", e)))?;
+
+        let id = RuleId::new(def.rule.id.clone()).ok_or_else(|| {
+            RuleError::InvalidDefinition(format!("Invalid rule ID: {}", def.rule.id))
+        })?;
+
+        let m = def.match_section;
+        if m.max_lines.is_none()
+            && m.forbid_name.is_none()
+            && m.require_name.is_none()
+            && m.forbid_path.is_none()
+            && m.require_sibling.is_none()
+        {
+            return Err(RuleError::InvalidDefinition(format!(
+                "File rule '{}' sets none of max_lines, forbid_name, require_name, \
+                 forbid_path and require_sibling",
+                id
+            )));
+        }
+
+        let message = def
+            .rule
+            .message
+            .map(|template| MessageTemplate::parse(&template, |name| PLACEHOLDERS.contains(&name)))
+            .transpose()?;
+        let require_sibling = m
+            .require_sibling
+            .map(|template| {
+                MessageTemplate::parse(&template, |name| SIBLING_PLACEHOLDERS.contains(&name))
+            })
+            .transpose()?;
+
+        // Process languages (empty means all languages)
+        let languages = m.languages.unwrap_or_default();
+        let test_language = languages.first().copied();
+        let tests = RuleTest::compile_all(def.tests, test_language)?;
+
+        let globset = |patterns: Option<GlobPatternList>| {
+            patterns
+                .map(|patterns| build_globset_with_context(&patterns, ctx))
+                .transpose()
+        };
+
+        Ok(FileRule {
+            id,
+            description: def.rule.description,
+            severity: def.rule.severity,
+            message,
+            max_lines: m.max_lines,
+            forbid_name: compile_regex("forbid_name", m.forbid_name)?,
+            require_name: compile_regex("require_name", m.require_name)?,
+            forbid_path: compile_regex("forbid_path", m.forbid_path)?,
+            require_sibling,
+            languages,
+            include: globset(m.include)?,
+            exclude: globset(m.exclude)?,
+            tests,
+            docs: def.rule.docs,
+            definition_hash: definition_hash(content, ctx),
+        })
+    }
+
+    /// Parse a FileRule from a TOML file path
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if the file cannot be read or parsed.
+    pub fn from_path(path: &Path) -> Result<Self, RuleError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RuleError::InvalidDefinition(format!("Failed to read file {:?}: {}", path, e))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    /// Check if this rule applies to the given file path
+    fn applies_to_file(&self, file_path: &Path) -> bool {
+        let normalized = normalize_for_glob_match(file_path);
+
+        if let Some(ref exclude) = self.exclude
+            && exclude.is_match(normalized.as_ref())
+        {
+            return false;
+        }
+
+        match self.include {
+            Some(ref include) => include.is_match(normalized.as_ref()),
+            None => true,
+        }
+    }
+
+    /// Path of the sibling file the checked file requires, if any, without a leading `./`
+    fn sibling(&self, file_path: &Path) -> Option<PathBuf> {
+        let template = self.require_sibling.as_ref()?;
+        let name = file_path.file_name()?.to_str()?;
+        let stem = file_path.file_stem()?.to_str()?;
+        let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let sibling = template.render(|placeholder| match placeholder {
+            "name" => Some(name),
+            "stem" => Some(stem),
+            "ext" => Some(ext),
+            _ => None,
+        });
+        Some(normalize_for_glob_match(&file_path.with_file_name(sibling)).into_owned())
+    }
+
+    /// The checks the file fails, in the order they are declared
+    fn failures(&self, ctx: &ExecutionContext, sibling: Option<&Path>) -> Vec<Failure<'_>> {
+        let normalized = normalize_for_glob_match(ctx.file_path);
+        let path = normalized.to_string_lossy();
+        let name = ctx
+            .file_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        let mut failures = Vec::new();
+        if let Some(max) = self.max_lines {
+            let lines = ctx.content.lines().count();
+            if lines > max {
+                failures.push(Failure::TooLong { lines, max });
+            }
+        }
+        if let Some(ref re) = self.forbid_name
+            && re.is_match(&name)
+        {
+            failures.push(Failure::ForbiddenName(re));
+        }
+        if let Some(ref re) = self.require_name
+            && !re.is_match(&name)
+        {
+            failures.push(Failure::UnmatchedName(re));
+        }
+        if let Some(ref re) = self.forbid_path
+            && re.is_match(&path)
+        {
+            failures.push(Failure::ForbiddenPath(re));
+        }
+        // Siblings are looked up among the walked files, not on disk
+        if let Some(sibling) = sibling
+            && !ctx
+                .walked_files
+                .is_some_and(|files| files.contains(sibling))
+        {
+            failures.push(Failure::MissingSibling);
+        }
+        failures
+    }
+
+    /// Build the violation message for a failed check
+    ///
+    /// The rule's message, or its description, followed by what failed.
+    fn message(&self, ctx: &ExecutionContext, sibling: Option<&Path>, failure: &Failure) -> String {
+        format!(
+            "{} ({})",
+            self.base_message(ctx, sibling),
+            failure.describe(sibling)
+        )
+    }
+
+    /// Build the rule's message for a file, before naming the failed check
+    fn base_message(&self, ctx: &ExecutionContext, sibling: Option<&Path>) -> String {
+        let Some(ref template) = self.message else {
+            return self.description.clone();
+        };
+        let normalized = normalize_for_glob_match(ctx.file_path);
+        let path = normalized.to_string_lossy();
+        let name = ctx
+            .file_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let lines = ctx.content.lines().count().to_string();
+        let sibling = sibling
+            .map(|sibling| {
+                normalize_for_glob_match(sibling)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .unwrap_or_default();
+        template.render(|placeholder| match placeholder {
+            "name" => Some(name.as_ref()),
+            "path" => Some(path.as_ref()),
+            "lines" => Some(lines.as_str()),
+            "sibling" => Some(sibling.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// A check of a file rule that a file fails
+enum Failure<'r> {
+    /// The file has more than `max` lines
+    TooLong { lines: usize, max: usize },
+    /// The file name matches `forbid_name`
+    ForbiddenName(&'r Regex),
+    /// The file name does not match `require_name`
+    UnmatchedName(&'r Regex),
+    /// The file's path matches `forbid_path`
+    ForbiddenPath(&'r Regex),
+    /// The file named by `require_sibling` was not walked
+    MissingSibling,
+}
+
+impl Failure<'_> {
+    /// Describe the failure, given the path of the required sibling
+    fn describe(&self, sibling: Option<&Path>) -> String {
+        match self {
+            Failure::TooLong { lines, max } => format!("{} lines, more than {}", lines, max),
+            Failure::ForbiddenName(re) => format!("name matches forbid_name '{}'", re),
+            Failure::UnmatchedName(re) => format!("name does not match require_name '{}'", re),
+            Failure::ForbiddenPath(re) => format!("path matches forbid_path '{}'", re),
+            Failure::MissingSibling => format!(
+                "no {}",
+                sibling
+                    .map(|sibling| normalize_for_glob_match(sibling)
+                        .to_string_lossy()
+                        .into_owned())
+                    .unwrap_or_default()
+            ),
+        }
+    }
+}
+
+impl Rule for FileRule {
+    fn id(&self) -> &RuleId {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn kind(&self) -> RuleKind {
+        RuleKind::File
+    }
+
+    fn definition_hash(&self) -> Option<u64> {
+        // Sibling checks depend on other files, which the cache does not track
+        if self.require_sibling.is_some() {
+            return None;
+        }
+        Some(self.definition_hash)
+    }
+
+    fn reads_directory(&self) -> bool {
+        self.require_sibling.is_some()
+    }
+
+    fn execute(&self, ctx: &ExecutionContext) -> Vec<Violation> {
+        if !self.applies_to_file(ctx.file_path) {
+            return vec![];
+        }
+        let sibling = self.sibling(ctx.file_path);
+        let first_line = ctx.content.lines().next().unwrap_or_default();
+        self.failures(ctx, sibling.as_deref())
+            .iter()
+            .map(|failure| Violation {
+                rule_id: self.id.clone(),
+                file: ctx.file_path.to_path_buf(),
+                line: 1,
+                column: 1,
+                end_line: 1,
+                // Columns count bytes
+                end_column: first_line.len() as u32 + 1,
+                snippet: first_line.to_string(),
+                message: self.message(ctx, sibling.as_deref(), failure),
+                region: ctx.resolve_region(&self.id),
+            })
+            .collect()
+    }
+
+    fn count(&self, ctx: &ExecutionContext) -> usize {
+        if !self.applies_to_file(ctx.file_path) {
+            return 0;
+        }
+        let sibling = self.sibling(ctx.file_path);
+        self.failures(ctx, sibling.as_deref()).len()
+    }
+
+    fn tests(&self) -> &[RuleTest] {
+        &self.tests
+    }
+
+    fn docs(&self) -> Option<&RuleDocs> {
+        Some(&self.docs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn check(rule: &FileRule, file: &Path, content: &str) -> Vec<Violation> {
+        check_walked(rule, file, content, None)
+    }
+
+    fn check_walked(
+        rule: &FileRule,
+        file: &Path,
+        content: &str,
+        walked_files: Option<&HashSet<PathBuf>>,
+    ) -> Vec<Violation> {
+        rule.execute(&ExecutionContext {
+            file_path: file,
+            content,
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files,
+        })
+    }
+
+    #[test]
+    fn test_length_and_name_checks() -> Result<(), Box<dyn std::error::Error>> {
+        let rule = FileRule::from_toml(
+            r#"
+[rule]
+id = "small-snake-case-modules"
+description = "Modules are short and snake_case"
+severity = "warning"
+message = "{name} has {lines} lines"
+
+[match]
+languages = ["python"]
+max_lines = 2
+forbid_name = "^utils\\.py$"
+require_name = "^[a-z0-9_]+\\.py$"
+exclude = ["tests/**"]
+"#,
+        )?;
+
+        assert!(check(&rule, Path::new("./src/orders.py"), "a = 1\nb = 2\n").is_empty());
+
+        let violations = check(
+            &rule,
+            Path::new("./src/BigFile.py"),
+            "a = 1\nb = 2\nc = 3\n",
+        );
+        assert_eq!(violations.len(), 2);
+        assert_eq!((violations[0].line, violations[0].column), (1, 1));
+        assert_eq!(violations[0].end_column, 6);
+        assert_eq!(violations[0].snippet, "a = 1");
+        assert_eq!(
+            violations[0].message,
+            "BigFile.py has 3 lines (3 lines, more than 2)"
+        );
+        assert_eq!(
+            violations[1].message,
+            "BigFile.py has 3 lines (name does not match require_name '^[a-z0-9_]+\\.py$')"
+        );
+        assert_eq!(violations[0].region.as_str(), "src");
+
+        // End columns count bytes, like other rules' columns
+        let violations = check(&rule, Path::new("src/Café.py"), "café = 1\n");
+        assert_eq!(violations[0].end_column, 10);
+
+        assert_eq!(check(&rule, Path::new("src/utils.py"), "").len(), 1);
+        assert!(check(&rule, Path::new("tests/utils.py"), "").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_required_sibling() -> Result<(), Box<dyn std::error::Error>> {
+        let rule = FileRule::from_toml(
+            r#"
+[rule]
+id = "modules-have-tests"
+description = "Every module has a test file"
+severity = "error"
+message = "Missing {sibling}"
+
+[match]
+require_sibling = "test_{stem}.{ext}"
+exclude = ["**/test_*"]
+"#,
+        )?;
+        assert_eq!(rule.definition_hash(), None);
+        assert!(rule.reads_directory());
+
+        // Siblings are looked up among the walked files, not on disk; the
+        // engine drops the walk's leading `./` from both
+        let walked: HashSet<PathBuf> = ["src/orders.py", "src/test_orders.py", "src/billing.py"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        let check = |file: &str| check_walked(&rule, Path::new(file), "", Some(&walked));
+        assert!(check("./src/orders.py").is_empty());
+        assert!(check("./src/test_orders.py").is_empty());
+        let violations = check("./src/billing.py");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].message,
+            "Missing src/test_billing.py (no src/test_billing.py)"
+        );
+
+        // Without walked files, no sibling exists
+        assert_eq!(
+            check_walked(&rule, Path::new("./src/orders.py"), "", None).len(),
+            1
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        let rule = |match_section: &str| {
+            FileRule::from_toml(&format!(
+                "[rule]\nid = \"r\"\ndescription = \"d\"\nseverity = \"error\"\n\n[match]\n{}\n",
+                match_section
+            ))
+        };
+        assert!(rule("max_lines = 10").is_ok());
+        assert!(rule("languages = [\"python\"]").is_err());
+        assert!(rule("forbid_name = \"(\"").is_err());
+        assert!(rule("require_sibling = \"test_{module}.py\"").is_err());
+        assert!(rule("max_line = 10").is_err());
+    }
+}
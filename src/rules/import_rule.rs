@@ -0,0 +1,457 @@
+#![forbid(unsafe_code)]
+
+//! Import-based layering rule implementation
+//!
+//! This module provides ImportRule, which reports imports from one part of a
+//! project into another, e.g. domain code importing infrastructure code.
+
+use crate::error::RuleError;
+use crate::rules::ast::{Import, extract_imports};
+use crate::rules::message::MessageTemplate;
+use crate::rules::regex_rule::{GlobPatternList, build_globset_with_context};
+use crate::rules::rule::{definition_hash, normalize_for_glob_match};
+use crate::rules::self_test::TestSection;
+use crate::rules::{
+    ExecutionContext, ParserCache, Rule, RuleContext, RuleDocs, RuleKind, RuleTest, Violation,
+};
+use crate::types::{Language, RuleId, Severity};
+use globset::GlobSet;
+use serde::Deserialize;
+use std::path::Path;
+use tree_sitter::Tree;
+
+/// Placeholders available to the message template of an import rule
+const PLACEHOLDERS: [&str; 2] = ["module", "target"];
+
+/// Source roots used when a rule sets no `roots`
+const DEFAULT_ROOTS: [&str; 1] = ["src"];
+
+/// TOML structure for import rule definitions
+///
+/// This structure is deserialized from TOML files in ratchets/imports/.
+#[derive(Debug, Deserialize)]
+struct ImportRuleDefinition {
+    rule: RuleSection,
+    #[serde(rename = "match")]
+    match_section: MatchSection,
+    #[serde(default, rename = "test")]
+    tests: Vec<TestSection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleSection {
+    id: String,
+    description: String,
+    severity: Severity,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(flatten)]
+    docs: RuleDocs,
+}
+
+#[derive(Debug, Deserialize)]
+struct MatchSection {
+    forbid: GlobPatternList,
+    #[serde(default)]
+    allow: Option<GlobPatternList>,
+    #[serde(default)]
+    roots: Option<Vec<String>>,
+    #[serde(default)]
+    languages: Option<Vec<Language>>,
+    #[serde(default)]
+    include: Option<GlobPatternList>,
+    #[serde(default)]
+    exclude: Option<GlobPatternList>,
+}
+
+/// A rule that forbids imports of some modules from some files
+///
+/// ImportRule extracts the imports of each file it applies to from the file's
+/// syntax tree and reports every import whose target matches a `forbid`
+/// pattern and no `allow` pattern, at the importing statement. `include` and
+/// `exclude` select the importing files, so a rule expresses one layering
+/// constraint, e.g. "`src/domain/**` may not import `infra/**`".
+///
+/// Relative imports, and Rust paths within the crate, resolve against the
+/// importing file and drop the longest of the rule's `roots` (default `src`)
+/// they fall under, so they match the same patterns as absolute imports of
+/// the same module in every language. Violation messages
+/// may interpolate `{module}` (the import as written) and `{target}` (its
+/// resolved path).
+pub struct ImportRule {
+    id: RuleId,
+    description: String,
+    severity: Severity,
+    message: Option<MessageTemplate>,
+    forbid: GlobSet,
+    allow: Option<GlobSet>,
+    roots: Vec<String>,
+    languages: Vec<Language>,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    tests: Vec<RuleTest>,
+    docs: RuleDocs,
+    definition_hash: u64,
+}
+
+impl std::fmt::Debug for ImportRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImportRule")
+            .field("id", &self.id)
+            .field("description", &self.description)
+            .field("severity", &self.severity)
+            .field("message", &self.message)
+            .field("forbid", &"<GlobSet>")
+            .field("allow", &"<GlobSet>")
+            .field("roots", &self.roots)
+            .field("languages", &self.languages)
+            .field("include", &"<GlobSet>")
+            .field("exclude", &"<GlobSet>")
+            .field("tests", &self.tests)
+            .field("docs", &self.docs)
+            .finish()
+    }
+}
+
+impl ImportRule {
+    /// Parse an ImportRule from TOML content
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if:
+    /// - TOML syntax is invalid
+    /// - Required fields are missing
+    /// - Rule ID is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message template is invalid or names an unknown placeholder
+    /// - Glob patterns are invalid
+    pub fn from_toml(content: &str) -> Result<Self, RuleError> {
+        Self::from_toml_with_context(content, None)
+    }
+
+    /// Parse an ImportRule from TOML content with pattern context
+    ///
+    /// This method allows resolving pattern references (e.g., @python_tests) in
+    /// any of the rule's glob lists using the provided RuleContext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if:
+    /// - TOML syntax is invalid
+    /// - Required fields are missing
+    /// - Rule ID is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message template is invalid or names an unknown placeholder
+    /// - Glob patterns are invalid
+    /// - A pattern reference is not found in the context
+    pub fn from_toml_with_context(
+        content: &str,
+        ctx: Option<&RuleContext>,
+    ) -> Result<Self, RuleError> {
+        let def: ImportRuleDefinition = toml::from_str(content)
+            .map_err(|e| RuleError::InvalidDefinition(format!("Failed to parse TOML: {}", e)))?;
+
+        let id = RuleId::new(def.rule.id.clone()).ok_or_else(|| {
+            RuleError::InvalidDefinition(format!("Invalid rule ID: {}", def.rule.id))
+        })?;
+
+        let message = def
+            .rule
+            .message
+            .map(|template| MessageTemplate::parse(&template, |name| PLACEHOLDERS.contains(&name)))
+            .transpose()?;
+
+        // Process languages (empty means all languages)
+        let languages = def.match_section.languages.unwrap_or_default();
+        let test_language = languages.first().copied();
+        let tests = RuleTest::compile_all(def.tests, test_language)?;
+
+        let globset = |patterns: Option<GlobPatternList>| {
+            patterns
+                .map(|patterns| build_globset_with_context(&patterns, ctx))
+                .transpose()
+        };
+
+        Ok(ImportRule {
+            id,
+            description: def.rule.description,
+            severity: def.rule.severity,
+            message,
+            forbid: build_globset_with_context(&def.match_section.forbid, ctx)?,
+            allow: globset(def.match_section.allow)?,
+            roots: def
+                .match_section
+                .roots
+                .unwrap_or_else(|| DEFAULT_ROOTS.map(String::from).to_vec()),
+            languages,
+            include: globset(def.match_section.include)?,
+            exclude: globset(def.match_section.exclude)?,
+            tests,
+            docs: def.rule.docs,
+            definition_hash: definition_hash(content, ctx),
+        })
+    }
+
+    /// Parse an ImportRule from a TOML file path
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if the file cannot be read or parsed.
+    pub fn from_path(path: &Path) -> Result<Self, RuleError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RuleError::InvalidDefinition(format!("Failed to read file {:?}: {}", path, e))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    /// Check if this rule applies to the given (importing) file path
+    fn applies_to_file(&self, file_path: &Path) -> bool {
+        let normalized = normalize_for_glob_match(file_path);
+
+        if let Some(ref exclude) = self.exclude
+            && exclude.is_match(normalized.as_ref())
+        {
+            return false;
+        }
+
+        match self.include {
+            Some(ref include) => include.is_match(normalized.as_ref()),
+            None => true,
+        }
+    }
+
+    /// Returns true if importing `import` is forbidden
+    fn forbids(&self, import: &Import) -> bool {
+        self.forbid.is_match(&import.target)
+            && !self
+                .allow
+                .as_ref()
+                .is_some_and(|allow| allow.is_match(&import.target))
+    }
+
+    /// Get the file's syntax tree
+    ///
+    /// Uses the engine's shared tree, or parses the content if the rule
+    /// applies to a single language.
+    fn tree(&self, ctx: &ExecutionContext) -> Option<Tree> {
+        if let Some(tree) = ctx.ast {
+            return Some(tree.clone());
+        }
+
+        // No shared tree (e.g. direct callers); parse the content here
+        let [language] = self.languages[..] else {
+            return None;
+        };
+        let mut parser = ParserCache::new().get_parser(language).ok()?;
+        parser.parse(ctx.content, None)
+    }
+
+    /// Build the violation message for a forbidden import
+    fn message(&self, import: &Import) -> String {
+        let Some(ref template) = self.message else {
+            return self.description.clone();
+        };
+        template.render(|name| match name {
+            "module" => Some(import.module.as_str()),
+            "target" => Some(import.target.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl Rule for ImportRule {
+    fn id(&self) -> &RuleId {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn kind(&self) -> RuleKind {
+        RuleKind::Imports
+    }
+
+    fn definition_hash(&self) -> Option<u64> {
+        Some(self.definition_hash)
+    }
+
+    fn uses_syntax_tree(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, ctx: &ExecutionContext) -> Vec<Violation> {
+        if !self.applies_to_file(ctx.file_path) {
+            return vec![];
+        }
+        let Some(tree) = self.tree(ctx) else {
+            return vec![];
+        };
+        let Some(language) = ParserCache::language_of(&tree) else {
+            return vec![];
+        };
+
+        let file_path = normalize_for_glob_match(ctx.file_path);
+        extract_imports(&tree, ctx.content, language, &file_path, &self.roots)
+            .into_iter()
+            .filter(|import| self.forbids(import))
+            .map(|import| Violation {
+                rule_id: self.id.clone(),
+                file: ctx.file_path.to_path_buf(),
+                line: import.start.row as u32 + 1,
+                column: import.start.column as u32 + 1,
+                end_line: import.end.row as u32 + 1,
+                end_column: import.end.column as u32 + 1,
+                snippet: ctx.content[import.byte_range.clone()].to_string(),
+                message: self.message(&import),
+                region: ctx.resolve_region(&self.id),
+            })
+            .collect()
+    }
+
+    fn tests(&self) -> &[RuleTest] {
+        &self.tests
+    }
+
+    fn docs(&self) -> Option<&RuleDocs> {
+        Some(&self.docs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAIN_NO_INFRA: &str = r#"
+[rule]
+id = "domain-no-infra"
+description = "Domain code must not import infrastructure"
+severity = "error"
+message = "Domain code imports {target} via {module}"
+
+[match]
+languages = ["python"]
+include = ["src/domain/**"]
+forbid = ["**/infra", "**/infra/**"]
+allow = ["**/infra/types"]
+"#;
+
+    fn check(rule: &ImportRule, file: &str, content: &str) -> Vec<Violation> {
+        rule.execute(&ExecutionContext {
+            file_path: Path::new(file),
+            content,
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        })
+    }
+
+    #[cfg(feature = "lang-python")]
+    #[test]
+    fn test_forbidden_imports_are_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let rule = ImportRule::from_toml(DOMAIN_NO_INFRA)?;
+        let content = "\
+import json
+from app.infra.db import Pool
+from ..infra import types
+from ..infra import cache
+";
+        let violations = check(&rule, "./src/domain/order.py", content);
+        assert_eq!(violations.len(), 2);
+        assert_eq!((violations[0].line, violations[0].column), (2, 1));
+        assert_eq!(violations[0].snippet, "from app.infra.db import Pool");
+        assert_eq!(
+            violations[0].message,
+            "Domain code imports app/infra/db/Pool via app.infra.db"
+        );
+        assert_eq!(violations[1].line, 4);
+        assert_eq!(violations[1].region.as_str(), "src/domain");
+
+        // Files outside `include` may import anything
+        assert!(check(&rule, "src/infra/db.py", content).is_empty());
+        Ok(())
+    }
+
+    #[cfg(all(
+        feature = "lang-python",
+        feature = "lang-typescript",
+        feature = "lang-rust"
+    ))]
+    #[test]
+    fn test_relative_and_absolute_imports_match_one_pattern()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let rule = ImportRule::from_toml(
+            r#"
+[rule]
+id = "domain-no-infra"
+description = "Domain code must not import infrastructure"
+severity = "error"
+
+[match]
+include = ["src/domain/**"]
+forbid = ["infra/**"]
+"#,
+        )?;
+
+        let reported = |file: &str, language: Language, content: &str| {
+            let mut parser = ParserCache::new().get_parser(language)?;
+            let tree = parser.parse(content, None).ok_or("parse failed")?;
+            let violations = rule.execute(&ExecutionContext {
+                file_path: Path::new(file),
+                content,
+                ast: Some(&tree),
+                line_offsets: None,
+                region_resolver: None,
+                walked_files: None,
+            });
+            Ok::<_, Box<dyn std::error::Error>>(violations.len())
+        };
+
+        let python = "import infra.db\nfrom ..infra import cache\nfrom . import order\n";
+        assert_eq!(
+            reported("src/domain/invoice.py", Language::Python, python)?,
+            2
+        );
+        let typescript = "import { db } from 'infra/db';\nimport { c } from '../infra/cache';\n";
+        assert_eq!(
+            reported("src/domain/invoice.ts", Language::TypeScript, typescript)?,
+            2
+        );
+        let rust = "use crate::infra::db;\nuse super::super::infra::cache;\nuse super::order;\n";
+        assert_eq!(reported("src/domain/invoice.rs", Language::Rust, rust)?, 2);
+
+        // Roots can be set per rule
+        let rooted = ImportRule::from_toml(&format!(
+            "{}roots = [\"packages/app/src\"]\n",
+            DOMAIN_NO_INFRA.replace("src/domain/**", "packages/app/src/domain/**")
+        ))?;
+        let violations = check(
+            &rooted,
+            "packages/app/src/domain/order.py",
+            "from ..infra import db\n",
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].message,
+            "Domain code imports infra/db via ..infra"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        let unknown_placeholder = DOMAIN_NO_INFRA.replace("{module}", "{name}");
+        assert!(ImportRule::from_toml(&unknown_placeholder).is_err());
+        let no_forbid = DOMAIN_NO_INFRA.replace("forbid = ", "allowed = ");
+        assert!(ImportRule::from_toml(&no_forbid).is_err());
+    }
+}
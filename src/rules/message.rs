@@ -0,0 +1,134 @@
+#![forbid(unsafe_code)]
+
+//! Violation message templates
+//!
+//! Rules may set `message` in their `[rule]` section to report a message built
+//! from each match instead of the static description, e.g.
+//! `message = "Use pathlib instead of {func}"`. Placeholders name a regex group
+//! (by name or number) for regex rules, or a query capture for AST rules.
+//! `{{` and `}}` produce literal braces. The `replacement` of a rule's `[fix]`
+//! section uses the same syntax.
+
+use crate::error::RuleError;
+
+/// A parsed message template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MessageTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder(String),
+}
+
+impl MessageTemplate {
+    /// Parse a template, checking every placeholder with `is_known`
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if a brace is unbalanced, a
+    /// placeholder is empty, or `is_known` rejects a placeholder name.
+    pub(crate) fn parse(
+        template: &str,
+        is_known: impl Fn(&str) -> bool,
+    ) -> Result<Self, RuleError> {
+        let invalid = |reason: &str| {
+            RuleError::InvalidDefinition(format!(
+                "Invalid message template '{}': {}",
+                template, reason
+            ))
+        };
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => return Err(invalid("unclosed '{'")),
+                            Some(c) => name.push(c),
+                        }
+                    }
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err(invalid("empty placeholder"));
+                    }
+                    if !is_known(name) {
+                        return Err(invalid(&format!("unknown placeholder '{{{}}}'", name)));
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(name.to_string()));
+                }
+                '}' => return Err(invalid("unmatched '}'")),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Render the template, looking up each placeholder with `lookup`
+    ///
+    /// Placeholders that did not participate in the match render as empty.
+    pub(crate) fn render<'a>(&self, lookup: impl Fn(&str) -> Option<&'a str>) -> String {
+        let mut message = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => message.push_str(text),
+                Segment::Placeholder(name) => message.push_str(lookup(name).unwrap_or_default()),
+            }
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_placeholders_and_escapes() -> Result<(), Box<dyn std::error::Error>> {
+        let template = MessageTemplate::parse("Use {new} instead of {{{ old }}}", |_| true)?;
+        let message = template.render(|name| match name {
+            "new" => Some("pathlib"),
+            "old" => Some("os.path"),
+            _ => None,
+        });
+        assert_eq!(message, "Use pathlib instead of {os.path}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_capture_renders_empty() -> Result<(), Box<dyn std::error::Error>> {
+        let template = MessageTemplate::parse("[{1}]", |_| true)?;
+        assert_eq!(template.render(|_| None), "[]");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(MessageTemplate::parse("Use {func", |_| true).is_err());
+        assert!(MessageTemplate::parse("Use func}", |_| true).is_err());
+        assert!(MessageTemplate::parse("Use {}", |_| true).is_err());
+        assert!(MessageTemplate::parse("Use {func}", |name| name == "other").is_err());
+    }
+}
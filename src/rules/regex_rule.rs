@@ -6,13 +6,20 @@
 //! using regular expressions.
 
 use crate::error::RuleError;
-use crate::rules::rule::normalize_for_glob_match;
-use crate::rules::{ExecutionContext, Rule, RuleContext, Violation};
+use crate::rules::message::MessageTemplate;
+use crate::rules::rule::{definition_hash, normalize_for_glob_match};
+use crate::rules::scope::MatchScope;
+use crate::rules::self_test::TestSection;
+use crate::rules::{
+    ExecutionContext, Fix, ParserCache, Rule, RuleContext, RuleDocs, RuleKind, RuleTest, Violation,
+};
 use crate::types::{GlobPattern, Language, RuleId, Severity};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use regex::Regex;
 use serde::Deserialize;
+use std::ops::Range;
 use std::path::Path;
+use tree_sitter::Tree;
 
 /// TOML structure for regex rule definitions
 ///
@@ -23,6 +30,10 @@ struct RegexRuleDefinition {
     rule: RuleSection,
     #[serde(rename = "match")]
     match_section: MatchSection,
+    #[serde(default)]
+    fix: Option<FixSection>,
+    #[serde(default, rename = "test")]
+    tests: Vec<TestSection>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,11 +41,15 @@ struct RuleSection {
     id: String,
     description: String,
     severity: Severity,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(flatten)]
+    docs: RuleDocs,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
-enum GlobPatternList {
+pub(crate) enum GlobPatternList {
     Single(String),
     Multiple(Vec<String>),
 }
@@ -48,20 +63,37 @@ struct MatchSection {
     include: Option<GlobPatternList>,
     #[serde(default)]
     exclude: Option<GlobPatternList>,
+    #[serde(default)]
+    scope: Option<MatchScope>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixSection {
+    replacement: String,
 }
 
 /// A rule that matches text patterns using regular expressions
 ///
 /// RegexRule compiles a regex pattern and executes it against file content,
-/// reporting all matches as violations.
+/// reporting all matches as violations. Violation messages interpolate the
+/// match's groups into the rule's message template, if it has one. A rule
+/// with a scope only reports matches inside comments, inside strings, or in
+/// code, using the file's syntax tree. A rule with a fix replaces each match
+/// with its replacement template, interpolating groups the same way.
 pub struct RegexRule {
     id: RuleId,
     description: String,
     severity: Severity,
+    message: Option<MessageTemplate>,
     pattern: Regex,
     languages: Vec<Language>,
     include: Option<GlobSet>,
     exclude: Option<GlobSet>,
+    scope: Option<MatchScope>,
+    fix: Option<MessageTemplate>,
+    tests: Vec<RuleTest>,
+    docs: RuleDocs,
+    definition_hash: u64,
 }
 
 impl std::fmt::Debug for RegexRule {
@@ -70,10 +102,15 @@ impl std::fmt::Debug for RegexRule {
             .field("id", &self.id)
             .field("description", &self.description)
             .field("severity", &self.severity)
+            .field("message", &self.message)
             .field("pattern", &self.pattern.as_str())
             .field("languages", &self.languages)
             .field("include", &"<GlobSet>")
             .field("exclude", &"<GlobSet>")
+            .field("scope", &self.scope)
+            .field("fix", &self.fix)
+            .field("tests", &self.tests)
+            .field("docs", &self.docs)
             .finish()
     }
 }
@@ -88,6 +125,8 @@ impl RegexRule {
     /// - Required fields are missing
     /// - Rule ID is invalid
     /// - Regex pattern is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message or fix template is invalid or names an unknown group
     /// - Glob patterns are invalid
     pub fn from_toml(content: &str) -> Result<Self, RuleError> {
         Self::from_toml_with_context(content, None)
@@ -105,6 +144,8 @@ impl RegexRule {
     /// - Required fields are missing
     /// - Rule ID is invalid
     /// - Regex pattern is invalid
+    /// - A `[[test]]` table is invalid
+    /// - Message or fix template is invalid or names an unknown group
     /// - Glob patterns are invalid
     /// - A pattern reference is not found in the context
     pub fn from_toml_with_context(
@@ -128,8 +169,28 @@ impl RegexRule {
             ))
         })?;
 
+        // Parse the message and fix templates; placeholders name or number a group
+        let is_group = |name: &str| match name.parse::<usize>() {
+            Ok(index) => index < pattern.captures_len(),
+            Err(_) => pattern.capture_names().any(|group| group == Some(name)),
+        };
+        let message = def
+            .rule
+            .message
+            .map(|template| MessageTemplate::parse(&template, is_group))
+            .transpose()?;
+        let fix = def
+            .fix
+            .map(|section| MessageTemplate::parse(&section.replacement, is_group))
+            .transpose()?;
+
         // Process languages (empty means all languages)
         let languages = def.match_section.languages.unwrap_or_default();
+        let test_language = match languages[..] {
+            [language, ..] => Some(language),
+            [] => None,
+        };
+        let tests = RuleTest::compile_all(def.tests, test_language)?;
 
         // Build include GlobSet if specified
         let include = if let Some(patterns) = def.match_section.include {
@@ -149,10 +210,16 @@ impl RegexRule {
             id,
             description: def.rule.description,
             severity: def.rule.severity,
+            message,
             pattern,
             languages,
             include,
             exclude,
+            scope: def.match_section.scope,
+            fix,
+            tests,
+            docs: def.rule.docs,
+            definition_hash: definition_hash(content, ctx),
         })
     }
 
@@ -191,10 +258,72 @@ impl RegexRule {
             true
         }
     }
+
+    /// Get the syntax tree used to apply this rule's scope
+    ///
+    /// Uses the engine's shared tree, or parses the content if the rule
+    /// applies to a single language. Returns None for unscoped rules, and for
+    /// scoped rules without a tree, which then report every match.
+    fn scope_tree(&self, ctx: &ExecutionContext) -> Option<Tree> {
+        self.scope?;
+        if let Some(tree) = ctx.ast {
+            return Some(tree.clone());
+        }
+
+        // No shared tree (e.g. direct callers); parse the content here
+        let [language] = self.languages[..] else {
+            return None;
+        };
+        let mut parser = ParserCache::new().get_parser(language).ok()?;
+        parser.parse(ctx.content, None)
+    }
+
+    /// Check whether a match lies within this rule's scope
+    fn in_scope(&self, tree: Option<&Tree>, range: &Range<usize>) -> bool {
+        match (self.scope, tree) {
+            (Some(scope), Some(tree)) => scope.contains(tree, range.clone()),
+            _ => true,
+        }
+    }
+
+    /// Iterate over the matches this rule reports in `content` with their messages
+    ///
+    /// Groups are only extracted when the message template needs them.
+    fn matches_with_messages<'c>(
+        &'c self,
+        content: &'c str,
+    ) -> Box<dyn Iterator<Item = (Range<usize>, String)> + 'c> {
+        let Some(ref template) = self.message else {
+            return Box::new(
+                self.matches(content)
+                    .map(|m| (m.range(), self.description.clone())),
+            );
+        };
+
+        Box::new(self.pattern.captures_iter(content).filter_map(|captures| {
+            let range = captures.get(0)?.range();
+            let message = template.render(|name| group_text(&captures, name));
+            Some((range, message))
+        }))
+    }
+
+    /// Iterate over the matches this rule reports in `content`
+    fn matches<'c>(&self, content: &'c str) -> regex::Matches<'_, 'c> {
+        self.pattern.find_iter(content)
+    }
+}
+
+/// Look up a group of a match by number or name
+fn group_text<'h>(captures: &regex::Captures<'h>, name: &str) -> Option<&'h str> {
+    let group = match name.parse::<usize>() {
+        Ok(index) => captures.get(index),
+        Err(_) => captures.name(name),
+    };
+    group.map(|m| m.as_str())
 }
 
 /// Build a GlobSet from a list of glob patterns or references
-fn build_globset_with_context(
+pub(crate) fn build_globset_with_context(
     pattern_list: &GlobPatternList,
     ctx: Option<&RuleContext>,
 ) -> Result<GlobSet, RuleError> {
@@ -289,7 +418,7 @@ fn resolve_pattern_reference<'a>(
 ///
 /// Returns a vector where each element is the byte offset of the start of a line.
 /// Line 0 starts at offset 0.
-fn compute_line_offsets(content: &str) -> Vec<usize> {
+pub(crate) fn compute_line_offsets(content: &str) -> Vec<usize> {
     let mut offsets = vec![0];
     for (i, c) in content.char_indices() {
         if c == '\n' {
@@ -331,28 +460,55 @@ impl Rule for RegexRule {
         self.severity
     }
 
+    fn kind(&self) -> RuleKind {
+        RuleKind::Regex
+    }
+
+    fn definition_hash(&self) -> Option<u64> {
+        Some(self.definition_hash)
+    }
+
+    fn regex(&self) -> Option<&Regex> {
+        Some(&self.pattern)
+    }
+
+    fn uses_syntax_tree(&self) -> bool {
+        self.scope.is_some()
+    }
+
     fn execute(&self, ctx: &ExecutionContext) -> Vec<Violation> {
         // Check if this rule applies to this file
         if !self.applies_to_file(ctx.file_path) {
             return vec![];
         }
 
-        // Precompute line offsets for efficient position calculation
-        let line_offsets = compute_line_offsets(ctx.content);
+        // Use the engine's shared line offsets, or compute them here
+        let computed;
+        let line_offsets = match ctx.line_offsets {
+            Some(offsets) => offsets,
+            None => {
+                computed = compute_line_offsets(ctx.content);
+                &computed
+            }
+        };
 
         // Find all matches
         let mut violations = Vec::new();
 
-        for match_result in self.pattern.find_iter(ctx.content) {
-            let match_start = match_result.start();
-            let match_end = match_result.end();
+        let tree = self.scope_tree(ctx);
+        for (range, message) in self.matches_with_messages(ctx.content) {
+            if !self.in_scope(tree.as_ref(), &range) {
+                continue;
+            }
+            let match_start = range.start;
+            let match_end = range.end;
 
             // Extract snippet
             let snippet = ctx.content[match_start..match_end].to_string();
 
             // Calculate line/column positions
-            let (line, column) = offset_to_line_col(match_start, &line_offsets);
-            let (end_line, end_column) = offset_to_line_col(match_end, &line_offsets);
+            let (line, column) = offset_to_line_col(match_start, line_offsets);
+            let (end_line, end_column) = offset_to_line_col(match_end, line_offsets);
 
             // Determine region using resolver if available, else fall back to parent directory
             let region = ctx.resolve_region(&self.id);
@@ -365,13 +521,59 @@ impl Rule for RegexRule {
                 end_line,
                 end_column,
                 snippet,
-                message: self.description.clone(),
+                message,
                 region,
             });
         }
 
         violations
     }
+
+    fn count(&self, ctx: &ExecutionContext) -> usize {
+        if !self.applies_to_file(ctx.file_path) {
+            return 0;
+        }
+
+        let tree = self.scope_tree(ctx);
+        self.matches(ctx.content)
+            .filter(|m| self.in_scope(tree.as_ref(), &m.range()))
+            .count()
+    }
+
+    fn has_fix(&self) -> bool {
+        self.fix.is_some()
+    }
+
+    fn fixes(&self, ctx: &ExecutionContext) -> Vec<Fix> {
+        let Some(ref template) = self.fix else {
+            return vec![];
+        };
+        if !self.applies_to_file(ctx.file_path) {
+            return vec![];
+        }
+
+        // Each in-scope match is replaced with the rendered template
+        let tree = self.scope_tree(ctx);
+        self.pattern
+            .captures_iter(ctx.content)
+            .filter_map(|captures| {
+                let range = captures.get(0)?.range();
+                if !self.in_scope(tree.as_ref(), &range) {
+                    return None;
+                }
+                let replacement = template.render(|name| group_text(&captures, name));
+                Some(Fix { range, replacement })
+            })
+            .collect()
+    }
+
+    fn tests(&self) -> &[RuleTest] {
+        &self.tests
+    }
+
+    fn docs(&self) -> Option<&RuleDocs> {
+        Some(&self.docs)
+    }
 }
 
 #[cfg(test)]
@@ -552,7 +754,9 @@ pattern = "TODO"
             file_path: Path::new("test.rs"),
             content: "// TODO: fix this\nfn main() {}",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -562,6 +766,171 @@ pattern = "TODO"
         Ok(())
     }
 
+    #[test]
+    fn test_execute_message_template() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "no-os-path"
+description = "Use pathlib"
+message = "Use pathlib instead of os.path.{func} (via {1})"
+severity = "warning"
+
+[match]
+pattern = "(os\\.path)\\.(?P<func>\\w+)"
+"#;
+
+        let rule = RegexRule::from_toml(toml)?;
+
+        let ctx = ExecutionContext {
+            file_path: Path::new("test.py"),
+            content: "os.path.join(a, b)\nos.path.exists(c)",
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        };
+
+        let violations = rule.execute(&ctx);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].message,
+            "Use pathlib instead of os.path.join (via os.path)"
+        );
+        assert_eq!(
+            violations[1].message,
+            "Use pathlib instead of os.path.exists (via os.path)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_toml_message_unknown_group() {
+        let toml = r#"
+[rule]
+id = "test-rule"
+description = "Test"
+message = "Found {name} and {2}"
+severity = "warning"
+
+[match]
+pattern = "(?P<name>unsafe)"
+"#;
+
+        let result = RegexRule::from_toml(toml);
+        assert!(matches!(result, Err(RuleError::InvalidDefinition(_))));
+    }
+
+    #[test]
+    fn test_fixes_interpolate_groups() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "no-os-path-join"
+description = "Use pathlib"
+severity = "warning"
+
+[match]
+pattern = "os\\.path\\.join\\((?P<base>\\w+), (\\w+)\\)"
+
+[fix]
+replacement = "Path({base}) / {2}"
+"#;
+
+        let rule = RegexRule::from_toml(toml)?;
+        assert!(rule.has_fix());
+
+        let content = "a = os.path.join(root, name)\nb = os.path.join(x, y)\n";
+        let ctx = ExecutionContext {
+            file_path: Path::new("test.py"),
+            content,
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        };
+
+        let fixes = rule.fixes(&ctx);
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(&content[fixes[0].range.clone()], "os.path.join(root, name)");
+        assert_eq!(fixes[0].replacement, "Path(root) / name");
+        assert_eq!(fixes[1].replacement, "Path(x) / y");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_toml_fix_unknown_group() {
+        let toml = r#"
+[rule]
+id = "test-rule"
+description = "Test"
+severity = "warning"
+
+[match]
+pattern = "(unsafe)"
+
+[fix]
+replacement = "{2}"
+"#;
+
+        let result = RegexRule::from_toml(toml);
+        assert!(matches!(result, Err(RuleError::InvalidDefinition(_))));
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_execute_scope() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "// unwrap here\nlet s = \"unwrap\";\nx.unwrap();\n/* unwrap */\n";
+        let lines = |scope: &str| -> Result<Vec<u32>, Box<dyn std::error::Error>> {
+            let rule = RegexRule::from_toml(&format!(
+                r#"
+[rule]
+id = "no-unwrap-text"
+description = "Find unwrap"
+severity = "warning"
+
+[match]
+pattern = "unwrap"
+languages = ["rust"]
+scope = "{}"
+"#,
+                scope
+            ))?;
+
+            // No shared tree: single-language rules parse the content themselves
+            let ctx = ExecutionContext {
+                file_path: Path::new("test.rs"),
+                content,
+                ast: None,
+                line_offsets: None,
+                region_resolver: None,
+                walked_files: None,
+            };
+            assert_eq!(rule.count(&ctx), rule.execute(&ctx).len());
+            Ok(rule.execute(&ctx).iter().map(|v| v.line).collect())
+        };
+
+        assert_eq!(lines("comments")?, [1, 4]);
+        assert_eq!(lines("strings")?, [2]);
+        assert_eq!(lines("code")?, [3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_toml_invalid_scope() {
+        let toml = r#"
+[rule]
+id = "test-rule"
+description = "Test"
+severity = "warning"
+
+[match]
+pattern = "unsafe"
+scope = "docstrings"
+"#;
+
+        let result = RegexRule::from_toml(toml);
+        assert!(matches!(result, Err(RuleError::InvalidDefinition(_))));
+    }
+
     #[test]
     fn test_execute_multiple_matches() -> Result<(), Box<dyn std::error::Error>> {
         let toml = r#"
@@ -580,7 +949,9 @@ pattern = "TODO"
             file_path: Path::new("test.rs"),
             content: "// TODO: fix\n// TODO: also fix\nfn main() {}",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -608,7 +979,9 @@ pattern = "TODO"
             file_path: Path::new("test.rs"),
             content: "fn main() { println!(\"Hello\"); }",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -636,7 +1009,9 @@ include = ["src/**"]
             file_path: Path::new("src/main.rs"),
             content: "// TODO: fix",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 1);
@@ -646,7 +1021,9 @@ include = ["src/**"]
             file_path: Path::new("tests/test.rs"),
             content: "// TODO: fix",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0);
@@ -673,7 +1050,9 @@ exclude = ["tests/**"]
             file_path: Path::new("tests/test.rs"),
             content: "// TODO: fix",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0);
@@ -683,7 +1062,9 @@ exclude = ["tests/**"]
             file_path: Path::new("src/main.rs"),
             content: "// TODO: fix",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 1);
@@ -708,7 +1089,9 @@ pattern = "(?i)\\bTODO\\b"
             file_path: Path::new("test.rs"),
             content: "// TODO: fix\n// todo: also\n// Todo: and this",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -735,7 +1118,9 @@ pattern = "FIXME"
             file_path: Path::new("test.rs"),
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -780,7 +1165,9 @@ exclude = "@test_files"
             file_path: Path::new("test_foo.py"),
             content: "// TODO: fix this",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx_test);
         assert_eq!(violations.len(), 0);
@@ -790,7 +1177,9 @@ exclude = "@test_files"
             file_path: Path::new("main.py"),
             content: "// TODO: fix this",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx_normal);
         assert_eq!(violations.len(), 1);
@@ -828,7 +1217,9 @@ exclude = ["@test_files", "build/**"]
             file_path: Path::new("test_foo.py"),
             content: "// TODO: fix this",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         assert_eq!(rule.execute(&ctx1).len(), 0);
 
@@ -837,7 +1228,9 @@ exclude = ["@test_files", "build/**"]
             file_path: Path::new("build/main.py"),
             content: "// TODO: fix this",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         assert_eq!(rule.execute(&ctx2).len(), 0);
 
@@ -846,7 +1239,9 @@ exclude = ["@test_files", "build/**"]
             file_path: Path::new("src/main.py"),
             content: "// TODO: fix this",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         assert_eq!(rule.execute(&ctx3).len(), 1);
         Ok(())
@@ -948,7 +1343,9 @@ languages = ["rust"]
             file_path: Path::new("src/deep/nested/file.rs"),
             content: "// TODO: test",
             ast: None,
+            line_offsets: None,
             region_resolver: Some(resolver),
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -979,7 +1376,9 @@ include = ["example_app/frontend/**/*.tsx"]
             file_path: Path::new("./example_app/frontend/App.tsx"),
             content: "<button>X</button>",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(
@@ -993,7 +1392,9 @@ include = ["example_app/frontend/**/*.tsx"]
             file_path: Path::new("example_app/frontend/App.tsx"),
             content: "<button>X</button>",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(
@@ -1007,7 +1408,9 @@ include = ["example_app/frontend/**/*.tsx"]
             file_path: Path::new("./other/dir/App.tsx"),
             content: "<button>X</button>",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0, "non-matching paths still excluded");
@@ -1034,7 +1437,9 @@ exclude = ["**/tests/**"]
             file_path: Path::new("./src/tests/foo.rs"),
             content: "// TODO: fix",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 0, "**/tests/** must still exclude");
@@ -1044,10 +1449,77 @@ exclude = ["**/tests/**"]
             file_path: Path::new("./src/main.rs"),
             content: "// TODO: fix",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
         let violations = rule.execute(&ctx);
         assert_eq!(violations.len(), 1);
         Ok(())
     }
+
+    #[test]
+    fn test_count_matches_execute() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+[rule]
+id = "test-rule"
+description = "Find HACK"
+severity = "warning"
+
+[match]
+pattern = "HACK"
+include = ["src/**"]
+"#;
+
+        let rule = RegexRule::from_toml(toml)?;
+
+        for path in ["src/main.rs", "tests/test.rs"] {
+            let ctx = ExecutionContext {
+                file_path: Path::new(path),
+                content: "// HACK: fix\n// HACK: also fix\nfn main() {}",
+                ast: None,
+                line_offsets: None,
+                region_resolver: None,
+                walked_files: None,
+            };
+            assert_eq!(rule.count(&ctx), rule.execute(&ctx).len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_definition_hash_tracks_definition_and_context() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use std::collections::HashMap;
+
+        let toml = r#"
+[rule]
+id = "no-hack"
+description = "No HACK"
+severity = "warning"
+
+[match]
+pattern = "HACK"
+include = "@src"
+"#;
+        let context = |pattern: &str| {
+            let mut patterns = HashMap::new();
+            patterns.insert("src".to_string(), vec![GlobPattern::new(pattern)]);
+            RuleContext::new(patterns)
+        };
+
+        let hash = |content: &str, ctx: &RuleContext| -> Result<Option<u64>, RuleError> {
+            Ok(RegexRule::from_toml_with_context(content, Some(ctx))?.definition_hash())
+        };
+
+        let base = hash(toml, &context("src/**"))?;
+        assert!(base.is_some());
+        assert_eq!(base, hash(toml, &context("src/**"))?);
+        assert_ne!(base, hash(toml, &context("lib/**"))?);
+        assert_ne!(
+            base,
+            hash(&toml.replace("\"HACK\"", "\"XXX\""), &context("src/**"))?
+        );
+        Ok(())
+    }
 }
@@ -4,13 +4,22 @@
 //!
 //! The RuleRegistry is responsible for:
 //! - Loading built-in regex rules from builtin-ratchets/
+//! - Registering the built-in `unjustified-suppression` rule
 //! - Loading custom regex rules from ratchets/regex/
+//! - Loading custom import rules from ratchets/imports/
+//! - Loading custom file rules from ratchets/files/
+//! - Loading custom external rules from ratchets/external/
+//! - Instantiating rule templates from `[[rules.instances]]` in ratchets.toml
 //! - Filtering rules based on configuration
 //! - Providing access to rules by ID
 
+use crate::config::ratchet_toml::RuleInstance;
 use crate::config::sets::SetRegistry;
 use crate::error::RuleError;
-use crate::rules::{AstRule, RegexRule, Rule, RuleContext};
+use crate::rules::{
+    AstRule, ExternalRule, FileRule, ImportRule, RegexRule, Rule, RuleContext, TemplateRegistry,
+    UnjustifiedSuppressionRule,
+};
 use crate::types::{GlobPattern, RuleId};
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -60,6 +69,16 @@ impl RuleRegistry {
         Ok(())
     }
 
+    /// Register the built-in rule reporting suppression comments without a reason
+    ///
+    /// The rule has no definition file; the engine reports its violations
+    /// while parsing suppression comments.
+    pub fn load_builtin_suppression_rule(&mut self) {
+        if let Some(rule) = UnjustifiedSuppressionRule::new() {
+            self.rules.insert(rule.id().clone(), Box::new(rule));
+        }
+    }
+
     /// Load built-in regex rules from a directory
     ///
     /// This method scans the specified directory for `.toml` files and attempts
@@ -402,6 +421,259 @@ impl RuleRegistry {
         Ok(())
     }
 
+    /// Load custom import rules from a directory
+    ///
+    /// This method scans the specified directory for `.toml` files and loads
+    /// each as an ImportRule, replacing any rule with the same ID. If the
+    /// directory doesn't exist, a warning is logged but the operation succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `custom_dir` - Path to the ratchets/imports/ directory
+    /// * `ctx` - Optional pattern context for resolving pattern references
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if:
+    /// - A TOML file cannot be parsed
+    /// - A rule definition is invalid
+    /// - There is an I/O error reading a file
+    pub fn load_custom_import_rules(
+        &mut self,
+        custom_dir: &Path,
+        ctx: Option<&RuleContext>,
+    ) -> Result<(), RuleError> {
+        if !custom_dir.exists() {
+            eprintln!(
+                "Warning: Import rule directory does not exist: {}",
+                custom_dir.display()
+            );
+            return Ok(());
+        }
+
+        if !custom_dir.is_dir() {
+            return Err(RuleError::InvalidDefinition(format!(
+                "Path is not a directory: {}",
+                custom_dir.display()
+            )));
+        }
+
+        let entries = fs::read_dir(custom_dir).map_err(|e| {
+            RuleError::InvalidDefinition(format!(
+                "Failed to read directory {}: {}",
+                custom_dir.display(),
+                e
+            ))
+        })?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| {
+                RuleError::InvalidDefinition(format!(
+                    "Failed to read directory entry in {}: {}",
+                    custom_dir.display(),
+                    e
+                ))
+            })?;
+
+            let path = entry.path();
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("toml") {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                RuleError::InvalidDefinition(format!("Failed to read file {:?}: {}", path, e))
+            })?;
+            let rule = ImportRule::from_toml_with_context(&content, ctx)?;
+            self.rules.insert(rule.id().clone(), Box::new(rule));
+        }
+
+        Ok(())
+    }
+
+    /// Load custom file rules from a directory
+    ///
+    /// This method scans the specified directory for `.toml` files and loads
+    /// each as a FileRule, replacing any rule with the same ID. If the
+    /// directory doesn't exist, a warning is logged but the operation succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `custom_dir` - Path to the ratchets/files/ directory
+    /// * `ctx` - Optional pattern context for resolving pattern references
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if:
+    /// - A TOML file cannot be parsed
+    /// - A rule definition is invalid
+    /// - There is an I/O error reading a file
+    pub fn load_custom_file_rules(
+        &mut self,
+        custom_dir: &Path,
+        ctx: Option<&RuleContext>,
+    ) -> Result<(), RuleError> {
+        if !custom_dir.exists() {
+            eprintln!(
+                "Warning: File rule directory does not exist: {}",
+                custom_dir.display()
+            );
+            return Ok(());
+        }
+
+        if !custom_dir.is_dir() {
+            return Err(RuleError::InvalidDefinition(format!(
+                "Path is not a directory: {}",
+                custom_dir.display()
+            )));
+        }
+
+        let entries = fs::read_dir(custom_dir).map_err(|e| {
+            RuleError::InvalidDefinition(format!(
+                "Failed to read directory {}: {}",
+                custom_dir.display(),
+                e
+            ))
+        })?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| {
+                RuleError::InvalidDefinition(format!(
+                    "Failed to read directory entry in {}: {}",
+                    custom_dir.display(),
+                    e
+                ))
+            })?;
+
+            let path = entry.path();
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("toml") {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                RuleError::InvalidDefinition(format!("Failed to read file {:?}: {}", path, e))
+            })?;
+            let rule = FileRule::from_toml_with_context(&content, ctx)?;
+            self.rules.insert(rule.id().clone(), Box::new(rule));
+        }
+
+        Ok(())
+    }
+
+    /// Load custom external rules from a directory
+    ///
+    /// This method scans the specified directory for `.toml` files and loads
+    /// each as an ExternalRule, replacing any rule with the same ID. If the
+    /// directory doesn't exist, a warning is logged but the operation succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `custom_dir` - Path to the ratchets/external/ directory
+    /// * `ctx` - Optional pattern context for resolving pattern references
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if:
+    /// - A TOML file cannot be parsed
+    /// - A rule definition is invalid
+    /// - There is an I/O error reading a file
+    pub fn load_custom_external_rules(
+        &mut self,
+        custom_dir: &Path,
+        ctx: Option<&RuleContext>,
+    ) -> Result<(), RuleError> {
+        if !custom_dir.exists() {
+            eprintln!(
+                "Warning: External rule directory does not exist: {}",
+                custom_dir.display()
+            );
+            return Ok(());
+        }
+
+        if !custom_dir.is_dir() {
+            return Err(RuleError::InvalidDefinition(format!(
+                "Path is not a directory: {}",
+                custom_dir.display()
+            )));
+        }
+
+        let entries = fs::read_dir(custom_dir).map_err(|e| {
+            RuleError::InvalidDefinition(format!(
+                "Failed to read directory {}: {}",
+                custom_dir.display(),
+                e
+            ))
+        })?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| {
+                RuleError::InvalidDefinition(format!(
+                    "Failed to read directory entry in {}: {}",
+                    custom_dir.display(),
+                    e
+                ))
+            })?;
+
+            let path = entry.path();
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("toml") {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                RuleError::InvalidDefinition(format!("Failed to read file {:?}: {}", path, e))
+            })?;
+            let rule = ExternalRule::from_toml_with_context(&content, ctx)?;
+            self.rules.insert(rule.id().clone(), Box::new(rule));
+        }
+
+        Ok(())
+    }
+
+    /// Instantiate rule templates for each `[[rules.instances]]` entry
+    ///
+    /// Templates are looked up in a [`TemplateRegistry`] (embedded →
+    /// filesystem builtin → user-defined templates). Each instance is added
+    /// under its own ID, silently overriding a loaded rule with the same ID,
+    /// like custom rules override builtin ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if:
+    /// - A template fails to load
+    /// - An instance names an unknown template
+    /// - Two instances have the same ID
+    /// - An instance's parameters do not match its template's
+    /// - The instantiated definition is not a valid rule
+    pub fn load_rule_instances(
+        &mut self,
+        instances: &[RuleInstance],
+        ctx: Option<&RuleContext>,
+    ) -> Result<(), RuleError> {
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        let templates = TemplateRegistry::load_all()?;
+        let mut seen = HashSet::new();
+        for instance in instances {
+            if !seen.insert(&instance.id) {
+                return Err(RuleError::InvalidDefinition(format!(
+                    "Duplicate rule instance ID: {}",
+                    instance.id
+                )));
+            }
+            let template = templates.get(&instance.template).ok_or_else(|| {
+                RuleError::InvalidDefinition(format!(
+                    "Rule instance '{}' refers to unknown template '{}'",
+                    instance.id, instance.template
+                ))
+            })?;
+            let rule = template.instantiate(instance, ctx)?;
+            self.rules.insert(rule.id().clone(), rule);
+        }
+
+        Ok(())
+    }
+
     /// Filter rules to only those whose ID appears in `enabled`.
     ///
     /// The `enabled` set is produced by [`SetRegistry::resolve`] in
@@ -475,7 +747,10 @@ impl RuleRegistry {
                     return None;
                 }
                 // Keep if any of the rule's languages are in the config
-                if rule_langs.iter().any(|l| languages.contains(l)) {
+                if rule_langs
+                    .iter()
+                    .any(|l| languages.iter().any(|config| config.includes(*l)))
+                {
                     return None;
                 }
                 // Otherwise, remove
@@ -489,41 +764,21 @@ impl RuleRegistry {
         }
     }
 
-    /// Build a fully configured rule registry from the given config.
+    /// Load every builtin and custom rule, without filtering by config
     ///
-    /// This is the ONLY function that should be used to create a rule registry
-    /// for normal operation. It loads rules in the correct order:
-    /// 1. Embedded builtin rules (compiled into binary)
-    /// 2. Filesystem builtin rules (from builtin-ratchets/ - for overrides/development)
-    /// 3. Custom rules (from ratchets/ - user-defined rules)
-    /// 4. Resolve `enabled_ratchets` / `disabled_ratchets` via a
-    ///    [`SetRegistry`] (embedded → filesystem builtin → user-defined sets)
-    ///    and drop any rule whose ID is not in the resolved enabled set.
-    /// 5. Filter by language (removes rules for unconfigured languages).
-    ///
-    /// Unknown rule IDs that appear in `[rules]` produce a stderr warning
-    /// (not a hard error) — they may simply have been disabled by
-    /// `disabled_ratchets`, or refer to rules that aren't loaded for the
-    /// configured languages.
-    ///
-    /// # Arguments
-    ///
-    /// * `config` - The configuration containing patterns and rule settings
+    /// Performs steps 1-3 of [`RuleRegistry::build_from_config`]: embedded
+    /// builtin rules, then filesystem builtin rules, then custom rules, each
+    /// overriding earlier rules with the same ID. Used directly by commands
+    /// that work on every known rule, such as `ratchets test-rules`.
     ///
     /// # Errors
     ///
-    /// Returns `RuleError` if any rule loading step fails, or if the
-    /// ratchet-set resolver rejects the config (cycle in user-defined sets or
-    /// reference to an unknown set).
-    pub fn build_from_config(
-        config: &crate::config::ratchet_toml::Config,
-    ) -> Result<Self, RuleError> {
+    /// Returns `RuleError` if any rule fails to load.
+    pub fn load_all(rule_context: &RuleContext) -> Result<Self, RuleError> {
         let mut registry = Self::new();
 
-        // Create RuleContext from config patterns
-        let rule_context = RuleContext::new(config.patterns.clone());
-
         // Step 1: Load embedded builtin rules (always available)
+        registry.load_builtin_suppression_rule();
         registry.load_embedded_builtin_regex_rules()?;
         registry.load_embedded_builtin_ast_rules()?;
 
@@ -545,28 +800,76 @@ impl RuleRegistry {
         // These silently override builtin rules if they have the same ID
         let custom_regex_dir = std::path::PathBuf::from("ratchets").join("regex");
         if custom_regex_dir.exists() {
-            registry.load_custom_regex_rules(&custom_regex_dir, Some(&rule_context))?;
+            registry.load_custom_regex_rules(&custom_regex_dir, Some(rule_context))?;
         }
 
         let custom_ast_dir = std::path::PathBuf::from("ratchets").join("ast");
         if custom_ast_dir.exists() {
-            registry.load_custom_ast_rules(&custom_ast_dir, Some(&rule_context))?;
+            registry.load_custom_ast_rules(&custom_ast_dir, Some(rule_context))?;
+        }
+
+        let custom_imports_dir = std::path::PathBuf::from("ratchets").join("imports");
+        if custom_imports_dir.exists() {
+            registry.load_custom_import_rules(&custom_imports_dir, Some(rule_context))?;
         }
 
+        let custom_files_dir = std::path::PathBuf::from("ratchets").join("files");
+        if custom_files_dir.exists() {
+            registry.load_custom_file_rules(&custom_files_dir, Some(rule_context))?;
+        }
+
+        let custom_external_dir = std::path::PathBuf::from("ratchets").join("external");
+        if custom_external_dir.exists() {
+            registry.load_custom_external_rules(&custom_external_dir, Some(rule_context))?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Build a fully configured rule registry from the given config.
+    ///
+    /// This is the ONLY function that should be used to create a rule registry
+    /// for normal operation. It loads rules in the correct order:
+    /// 1. Embedded builtin rules (compiled into binary)
+    /// 2. Filesystem builtin rules (from builtin-ratchets/ - for overrides/development)
+    /// 3. Custom rules (from ratchets/ - user-defined rules), then rule
+    ///    template instances (from `[[rules.instances]]` in ratchets.toml)
+    /// 4. Resolve `enabled_ratchets` / `disabled_ratchets` via a
+    ///    [`SetRegistry`] (embedded → filesystem builtin → user-defined sets)
+    ///    and drop any rule whose ID is not in the resolved enabled set.
+    /// 5. Filter by language (removes rules for unconfigured languages).
+    ///
+    /// Unknown rule IDs that appear in `[rules]` produce a stderr warning
+    /// (not a hard error) — they may simply have been disabled by
+    /// `disabled_ratchets`, or refer to rules that aren't loaded for the
+    /// configured languages.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration containing patterns and rule settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if any rule loading step fails, or if the
+    /// ratchet-set resolver rejects the config (cycle in user-defined sets or
+    /// reference to an unknown set).
+    pub fn build_from_config(
+        config: &crate::config::ratchet_toml::Config,
+    ) -> Result<Self, RuleError> {
+        // Create RuleContext from config patterns
+        let rule_context = RuleContext::new(config.patterns.clone());
+
+        // Steps 1-3: Load every builtin and custom rule
+        let mut registry = Self::load_all(&rule_context)?;
+        registry.load_rule_instances(&config.rules.instances, Some(&rule_context))?;
+
         // Step 4: Resolve `enabled_ratchets` / `disabled_ratchets` via the
         // SetRegistry and filter the rule set down to the resolved IDs.
         //
         // Loading order for the SetRegistry mirrors the rule loaders:
         // embedded → filesystem-builtin → user-defined. Later sets override
         // earlier ones with the same ID.
-        let mut set_registry = SetRegistry::new();
-        set_registry.load_embedded_builtin_sets()?;
-
-        let builtin_sets_dir = std::path::PathBuf::from("builtin-ratchets").join("sets");
-        set_registry.load_builtin_sets(&builtin_sets_dir)?;
-
-        let user_sets_dir = std::path::PathBuf::from("ratchets").join("sets");
-        set_registry.load_custom_sets(&user_sets_dir)?;
+        let set_registry = SetRegistry::load_all()?;
 
         let resolved = set_registry.resolve(&config.enabled_ratchets, &config.disabled_ratchets)?;
 
@@ -658,6 +961,8 @@ fn warn_orphan_rule_settings(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ratchet_toml::RatchetRef;
+    use crate::rules::UNJUSTIFIED_SUPPRESSION;
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -1461,7 +1766,9 @@ query = "(unclosed_paren"
     #[cfg(feature = "lang-rust")]
     fn test_build_from_config_loads_embedded_rules_when_enabled()
     -> Result<(), Box<dyn std::error::Error>> {
-        use crate::config::ratchet_toml::{Config, OutputConfig, RatchetsMeta, RulesConfig};
+        use crate::config::ratchet_toml::{
+            Config, LanguagesConfig, OutputConfig, RatchetsMeta, RulesConfig,
+        };
         use crate::types::GlobPattern;
         use std::collections::HashMap;
 
@@ -1473,12 +1780,15 @@ query = "(unclosed_paren"
                 languages: vec![crate::types::Language::Rust],
                 include: vec![GlobPattern::new("**/*.rs".to_string())],
                 exclude: vec![],
+                header_language: None,
             },
             rules: RulesConfig {
                 builtin: HashMap::new(),
                 custom: HashMap::new(),
+                instances: Vec::new(),
             },
             output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
             patterns: HashMap::new(),
             enabled_ratchets: vec![
                 crate::config::ratchet_toml::RatchetRef::Rule(
@@ -1520,7 +1830,9 @@ query = "(unclosed_paren"
     -> Result<(), Box<dyn std::error::Error>> {
         // With no enabled refs, the registry resolves to zero rules even
         // though embedded rules loaded.
-        use crate::config::ratchet_toml::{Config, OutputConfig, RatchetsMeta, RulesConfig};
+        use crate::config::ratchet_toml::{
+            Config, LanguagesConfig, OutputConfig, RatchetsMeta, RulesConfig,
+        };
         use crate::types::GlobPattern;
         use std::collections::HashMap;
 
@@ -1530,12 +1842,15 @@ query = "(unclosed_paren"
                 languages: vec![crate::types::Language::Rust],
                 include: vec![GlobPattern::new("**/*.rs".to_string())],
                 exclude: vec![],
+                header_language: None,
             },
             rules: RulesConfig {
                 builtin: HashMap::new(),
                 custom: HashMap::new(),
+                instances: Vec::new(),
             },
             output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
             patterns: HashMap::new(),
             enabled_ratchets: Vec::new(),
             disabled_ratchets: Vec::new(),
@@ -1543,6 +1858,17 @@ query = "(unclosed_paren"
 
         let registry = RuleRegistry::build_from_config(&config)?;
         assert!(registry.is_empty());
+
+        // The suppression rule is enabled like any other rule
+        let config = Config {
+            enabled_ratchets: vec![RatchetRef::Rule(
+                RuleId::new(UNJUSTIFIED_SUPPRESSION).ok_or("invalid rule id")?,
+            )],
+            ..config
+        };
+        let registry = RuleRegistry::build_from_config(&config)?;
+        let ids: Vec<&str> = registry.iter_rules().map(|r| r.id().as_str()).collect();
+        assert_eq!(ids, vec![UNJUSTIFIED_SUPPRESSION]);
         Ok(())
     }
 
@@ -1551,7 +1877,7 @@ query = "(unclosed_paren"
     -> Result<(), Box<dyn std::error::Error>> {
         // Disabled wins over enabled.
         use crate::config::ratchet_toml::{
-            Config, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
+            Config, LanguagesConfig, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
         };
         use crate::types::GlobPattern;
         use std::collections::HashMap;
@@ -1562,12 +1888,15 @@ query = "(unclosed_paren"
                 languages: vec![crate::types::Language::Rust],
                 include: vec![GlobPattern::new("**/*".to_string())],
                 exclude: vec![],
+                header_language: None,
             },
             rules: RulesConfig {
                 builtin: HashMap::new(),
                 custom: HashMap::new(),
+                instances: Vec::new(),
             },
             output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
             patterns: HashMap::new(),
             enabled_ratchets: vec![
                 RatchetRef::Rule(RuleId::new("no-todo-comments").ok_or("invalid rule id")?),
@@ -1592,6 +1921,71 @@ query = "(unclosed_paren"
         Ok(())
     }
 
+    #[cfg(feature = "lang-python")]
+    #[test]
+    fn test_build_from_config_expands_rule_instances() -> Result<(), Box<dyn std::error::Error>> {
+        // Instances become rules before set resolution, so they are enabled
+        // and disabled by ID like any other rule.
+        use crate::config::ratchet_toml::{
+            Config, LanguagesConfig, OutputConfig, RatchetRef, RatchetsMeta, RuleInstance,
+            RulesConfig,
+        };
+        use crate::types::GlobPattern;
+        use std::collections::HashMap;
+
+        let instance = |id: &str, module: &str| -> Result<RuleInstance, &str> {
+            Ok(RuleInstance {
+                template: "python-no-module-import".to_string(),
+                id: RuleId::new(id).ok_or("invalid rule id")?,
+                description: None,
+                params: HashMap::from([("module".to_string(), module.to_string())]),
+            })
+        };
+        let mut config = Config {
+            ratchets: RatchetsMeta {
+                version: "2".to_string(),
+                languages: vec![crate::types::Language::Python],
+                include: vec![GlobPattern::new("**/*".to_string())],
+                exclude: vec![],
+                header_language: None,
+            },
+            rules: RulesConfig {
+                builtin: HashMap::new(),
+                custom: HashMap::new(),
+                instances: vec![
+                    instance("no-pickle", "pickle")?,
+                    instance("no-shelve", "shelve")?,
+                ],
+            },
+            output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
+            patterns: HashMap::new(),
+            enabled_ratchets: vec![
+                RatchetRef::Rule(RuleId::new("no-pickle").ok_or("invalid rule id")?),
+                RatchetRef::Rule(RuleId::new("no-shelve").ok_or("invalid rule id")?),
+            ],
+            disabled_ratchets: vec![RatchetRef::Rule(
+                RuleId::new("no-shelve").ok_or("invalid rule id")?,
+            )],
+        };
+
+        let registry = RuleRegistry::build_from_config(&config)?;
+        let mut ids: Vec<&str> = registry.iter_rules().map(|r| r.id().as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["no-pickle"]);
+        let rule = registry
+            .get_rule(&RuleId::new("no-pickle").ok_or("invalid rule id")?)
+            .ok_or("missing instance")?;
+        assert_eq!(rule.description(), "Do not import pickle");
+
+        // Unknown templates and duplicate instance IDs are errors
+        config.rules.instances[1].template = "no-such-template".to_string();
+        assert!(RuleRegistry::build_from_config(&config).is_err());
+        config.rules.instances[1] = instance("no-pickle", "shelve")?;
+        assert!(RuleRegistry::build_from_config(&config).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_build_from_config_keeps_settings_record_for_enabled_rule()
     -> Result<(), Box<dyn std::error::Error>> {
@@ -1599,7 +1993,8 @@ query = "(unclosed_paren"
         // record without a matching enabled_ratchet entry is reported as a
         // warning (see `warn_orphan_rule_settings`).
         use crate::config::ratchet_toml::{
-            Config, OutputConfig, RatchetRef, RatchetsMeta, RuleSettings, RulesConfig,
+            Config, LanguagesConfig, OutputConfig, RatchetRef, RatchetsMeta, RuleSettings,
+            RulesConfig,
         };
         use crate::types::GlobPattern;
         use std::collections::HashMap;
@@ -1619,12 +2014,15 @@ query = "(unclosed_paren"
                 languages: vec![crate::types::Language::Rust],
                 include: vec![GlobPattern::new("**/*".to_string())],
                 exclude: vec![],
+                header_language: None,
             },
             rules: RulesConfig {
                 builtin: builtin_rules,
                 custom: HashMap::new(),
+                instances: Vec::new(),
             },
             output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
             patterns: HashMap::new(),
             enabled_ratchets: vec![RatchetRef::Rule(
                 RuleId::new("no-todo-comments").ok_or("invalid rule id")?,
@@ -1662,7 +2060,7 @@ query = "(unclosed_paren"
     fn test_filter_by_languages_removes_non_matching_rules()
     -> Result<(), Box<dyn std::error::Error>> {
         use crate::config::ratchet_toml::{
-            Config, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
+            Config, LanguagesConfig, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
         };
         use crate::types::{GlobPattern, Language};
         use std::collections::HashMap;
@@ -1688,12 +2086,15 @@ query = "(unclosed_paren"
                 languages: vec![Language::Rust],
                 include: vec![GlobPattern::new("**/*".to_string())],
                 exclude: vec![],
+                header_language: None,
             },
             rules: RulesConfig {
                 builtin: HashMap::new(),
                 custom: HashMap::new(),
+                instances: Vec::new(),
             },
             output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
             patterns: HashMap::new(),
             enabled_ratchets,
             disabled_ratchets: Vec::new(),
@@ -1767,7 +2168,7 @@ query = "(unclosed_paren"
     fn test_filter_by_languages_keeps_multiple_languages() -> Result<(), Box<dyn std::error::Error>>
     {
         use crate::config::ratchet_toml::{
-            Config, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
+            Config, LanguagesConfig, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
         };
         use crate::types::{GlobPattern, Language};
         use std::collections::HashMap;
@@ -1784,12 +2185,15 @@ query = "(unclosed_paren"
                 languages: vec![Language::Rust, Language::Python],
                 include: vec![GlobPattern::new("**/*".to_string())],
                 exclude: vec![],
+                header_language: None,
             },
             rules: RulesConfig {
                 builtin: HashMap::new(),
                 custom: HashMap::new(),
+                instances: Vec::new(),
             },
             output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
             patterns: HashMap::new(),
             enabled_ratchets,
             disabled_ratchets: Vec::new(),
@@ -1826,7 +2230,7 @@ query = "(unclosed_paren"
     fn test_filter_by_languages_keeps_language_agnostic_rules()
     -> Result<(), Box<dyn std::error::Error>> {
         use crate::config::ratchet_toml::{
-            Config, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
+            Config, LanguagesConfig, OutputConfig, RatchetRef, RatchetsMeta, RulesConfig,
         };
         use crate::types::{GlobPattern, Language};
         use std::collections::HashMap;
@@ -1842,12 +2246,15 @@ query = "(unclosed_paren"
                 languages: vec![Language::Rust],
                 include: vec![GlobPattern::new("**/*".to_string())],
                 exclude: vec![],
+                header_language: None,
             },
             rules: RulesConfig {
                 builtin: HashMap::new(),
                 custom: HashMap::new(),
+                instances: Vec::new(),
             },
             output: OutputConfig::default(),
+            languages: LanguagesConfig::default(),
             patterns: HashMap::new(),
             enabled_ratchets,
             disabled_ratchets: Vec::new(),
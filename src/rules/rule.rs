@@ -2,10 +2,14 @@
 
 //! Core Rule trait and related types for defining and executing rules
 
+use crate::error::RuleError;
+use crate::rules::{RuleDocs, RuleTest};
 use crate::types::{GlobPattern, Language, RegionPath, RuleId, Severity};
+use regex::Regex;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -35,6 +39,55 @@ pub(crate) fn normalize_for_glob_match(path: &Path) -> Cow<'_, Path> {
     Cow::Borrowed(path)
 }
 
+/// Hasher for keys that outlive the process, such as cached results
+///
+/// `std::hash::Hash` output may change between Rust releases and platforms, so
+/// cache keys are BLAKE3 digests of explicitly length-prefixed fields instead.
+pub(crate) struct StableHasher(blake3::Hasher);
+
+impl StableHasher {
+    /// Creates a hasher with no fields written
+    pub(crate) fn new() -> Self {
+        Self(blake3::Hasher::new())
+    }
+
+    /// Adds a field, prefixed by its length so adjacent fields cannot run together
+    pub(crate) fn write_str(&mut self, field: &str) {
+        self.0.update(&(field.len() as u64).to_le_bytes());
+        self.0.update(field.as_bytes());
+    }
+
+    /// Returns the first 64 bits of the digest
+    pub(crate) fn finish(&self) -> u64 {
+        let digest = self.0.finalize();
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&digest.as_bytes()[..8]);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// Hash a rule definition for keying cached results.
+///
+/// Covers the rule's TOML source and every pattern in the context used to
+/// resolve `@name` references, so editing either invalidates cached results.
+pub(crate) fn definition_hash(content: &str, ctx: Option<&RuleContext>) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_str(content);
+    if let Some(ctx) = ctx {
+        let mut names: Vec<&String> = ctx.patterns.keys().collect();
+        names.sort();
+        for name in names {
+            hasher.write_str(name);
+            let patterns = &ctx.patterns[name];
+            hasher.write_str(&patterns.len().to_string());
+            for pattern in patterns {
+                hasher.write_str(pattern.as_str());
+            }
+        }
+    }
+    hasher.finish()
+}
+
 /// Type alias for the region resolver function
 ///
 /// Given a file path and rule ID, returns the configured region for that file.
@@ -42,13 +95,6 @@ pub(crate) fn normalize_for_glob_match(path: &Path) -> Cow<'_, Path> {
 /// when executing.
 pub type RegionResolver = Arc<dyn Fn(&Path, &RuleId) -> RegionPath + Send + Sync>;
 
-/// A placeholder for AST types until tree-sitter is added
-///
-/// This allows the API to be defined without adding the tree-sitter dependency yet.
-/// When tree-sitter is added, this will be replaced with `tree_sitter::Tree`.
-#[derive(Debug)]
-pub struct AstPlaceholder;
-
 /// Context for resolving pattern references in rule definitions
 ///
 /// This context contains pattern definitions from ratchets.toml that can be
@@ -83,14 +129,30 @@ pub struct ExecutionContext<'a> {
 
     /// Optional parsed AST (for AST-based rules)
     ///
-    /// Currently uses a placeholder type. Will use `tree_sitter::Tree` when available.
-    pub ast: Option<&'a AstPlaceholder>,
+    /// The execution engine parses each file once and shares the tree with every
+    /// applicable AST rule, and with rules whose `uses_syntax_tree` returns true.
+    /// When None, rules parse the content themselves as needed.
+    pub ast: Option<&'a tree_sitter::Tree>,
+
+    /// Optional precomputed line start offsets (for text-based rules)
+    ///
+    /// Each element is the byte offset where a line starts; line 1 starts at
+    /// offset 0. The execution engine computes these once per file. When None,
+    /// rules compute them from the content as needed.
+    pub line_offsets: Option<&'a [usize]>,
 
     /// Optional region resolver for mapping files to configured regions
     ///
     /// When Some, rules should use this to determine the region for violations.
     /// When None, rules should fall back to using the file's parent directory.
     pub region_resolver: Option<RegionResolver>,
+
+    /// Optional paths of every file being checked
+    ///
+    /// Rules whose `reads_directory` returns true look up other files here,
+    /// so they see the same files as the rest of the check. When None, no
+    /// other file is known to exist.
+    pub walked_files: Option<&'a HashSet<PathBuf>>,
 }
 
 impl fmt::Debug for ExecutionContext<'_> {
@@ -98,11 +160,19 @@ impl fmt::Debug for ExecutionContext<'_> {
         f.debug_struct("ExecutionContext")
             .field("file_path", &self.file_path)
             .field("content", &format!("<{} bytes>", self.content.len()))
-            .field("ast", &self.ast.map(|_| "<AstPlaceholder>"))
+            .field("ast", &self.ast.map(|_| "<Tree>"))
+            .field(
+                "line_offsets",
+                &self.line_offsets.map(|o| format!("<{} lines>", o.len())),
+            )
             .field(
                 "region_resolver",
                 &self.region_resolver.as_ref().map(|_| "<RegionResolver>"),
             )
+            .field(
+                "walked_files",
+                &self.walked_files.map(|f| format!("<{} files>", f.len())),
+            )
             .finish()
     }
 }
@@ -158,6 +228,33 @@ pub struct Violation {
     pub region: RegionPath,
 }
 
+/// A text edit that fixes a single violation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Byte range of the file content to replace
+    pub range: Range<usize>,
+
+    /// Text that replaces the range
+    pub replacement: String,
+}
+
+/// How a rule finds violations, which decides how the engine runs it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    /// Searches the file's text with a regex
+    Regex,
+    /// Runs a tree-sitter query against the file's syntax tree
+    Ast,
+    /// Checks the imports read from the file's syntax tree
+    Imports,
+    /// Checks the file's path, name and length
+    File,
+    /// Runs an external command over all files at once
+    External,
+    /// Reports the inline suppression comments the engine parses from each file
+    Suppression,
+}
+
 /// Trait that all rules must implement
 ///
 /// Rules are responsible for analyzing source code and detecting violations.
@@ -175,11 +272,107 @@ pub trait Rule: Send + Sync {
     /// Returns the severity level of violations from this rule
     fn severity(&self) -> Severity;
 
+    /// Returns how this rule finds violations
+    ///
+    /// The engine runs `Ast` rules only against a parsed syntax tree, and
+    /// shares each file's tree with them.
+    fn kind(&self) -> RuleKind;
+
     /// Executes the rule against the provided context
     ///
     /// Returns a vector of all violations found in the file.
     /// Returns an empty vector if no violations are found.
     fn execute(&self, ctx: &ExecutionContext) -> Vec<Violation>;
+
+    /// Returns a hash of the rule definition used to key cached results
+    ///
+    /// Rules returning None are never cached and always execute.
+    fn definition_hash(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the regex this rule searches for, if it is regex-driven
+    ///
+    /// The engine combines these patterns into one `RegexSet` per language and
+    /// skips rules whose pattern does not occur in a file. Only return Some if
+    /// the rule reports no violations for content the regex does not match.
+    fn regex(&self) -> Option<&Regex> {
+        None
+    }
+
+    /// Returns the source of the tree-sitter query this rule runs, if it is query-driven
+    fn query(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns true if the rule reads the file's syntax tree from `ExecutionContext::ast`
+    ///
+    /// The engine parses each file at most once and shares the tree with AST
+    /// rules and with any other rule returning true here.
+    fn uses_syntax_tree(&self) -> bool {
+        false
+    }
+
+    /// Count the violations this rule would report for the given context
+    ///
+    /// Used by count-only runs, which need per-region totals but not the
+    /// violations themselves. Rules should override this to avoid building
+    /// snippets; the result must equal `execute(ctx).len()`.
+    fn count(&self, ctx: &ExecutionContext) -> usize {
+        self.execute(ctx).len()
+    }
+
+    /// Returns true if the rule's result for a file depends on which other
+    /// files exist in its directory
+    ///
+    /// Files such rules apply to are re-checked when a file is added to or
+    /// removed from their directory, even if their own content is unchanged.
+    fn reads_directory(&self) -> bool {
+        false
+    }
+
+    /// Returns true if the rule checks all files at once with `execute_batch`
+    ///
+    /// The engine skips batch rules when checking files one by one, and runs
+    /// them once per check over every file they apply to by language.
+    fn is_batch(&self) -> bool {
+        false
+    }
+
+    /// Executes a batch rule against every file it applies to by language
+    ///
+    /// Violations are returned in the root region; the engine assigns their
+    /// configured regions and drops those suppressed by inline comments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::Execution` if the files could not be checked.
+    fn execute_batch(&self, _files: &[&Path]) -> Result<Vec<Violation>, RuleError> {
+        Ok(Vec::new())
+    }
+
+    /// Returns true if the rule can compute fixes for its violations
+    fn has_fix(&self) -> bool {
+        false
+    }
+
+    /// Computes edits that fix the violations found in the given context
+    ///
+    /// Returns one fix per fixable violation, in any order; fixes may overlap.
+    /// Rules without a fix return an empty vector.
+    fn fixes(&self, _ctx: &ExecutionContext) -> Vec<Fix> {
+        Vec::new()
+    }
+
+    /// Returns the examples the rule is checked against by `ratchets test-rules`
+    fn tests(&self) -> &[RuleTest] {
+        &[]
+    }
+
+    /// Returns the rationale, help link and examples shown by `ratchets explain`
+    fn docs(&self) -> Option<&RuleDocs> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -232,23 +425,28 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "lang-rust")]
     #[test]
-    fn test_execution_context_construction() {
+    fn test_execution_context_construction() -> Result<(), Box<dyn std::error::Error>> {
         let path = Path::new("test.rs");
         let content = "fn main() {}";
-        let ast_placeholder = AstPlaceholder;
+        let mut parser = crate::rules::ParserCache::new().get_parser(Language::Rust)?;
+        let tree = parser.parse(content, None).ok_or("parse failed")?;
 
         let ctx = ExecutionContext {
             file_path: path,
             content,
-            ast: Some(&ast_placeholder),
+            ast: Some(&tree),
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         assert_eq!(ctx.file_path, path);
         assert_eq!(ctx.content, content);
         assert!(ctx.ast.is_some());
         assert!(ctx.region_resolver.is_none());
+        Ok(())
     }
 
     #[test]
@@ -260,7 +458,9 @@ mod tests {
             file_path: path,
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         assert_eq!(ctx.file_path, path);
@@ -277,7 +477,9 @@ mod tests {
             file_path: path,
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let rule_id = RuleId::new("test-rule").ok_or("invalid rule id")?;
@@ -300,7 +502,9 @@ mod tests {
             file_path: path,
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: Some(resolver),
+            walked_files: None,
         };
 
         let rule_id = RuleId::new("test-rule").ok_or("invalid rule id")?;
@@ -335,7 +539,9 @@ mod tests {
             file_path: path,
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: Some(resolver),
+            walked_files: None,
         };
 
         let rule_id = RuleId::new("my-rule").ok_or("invalid rule id")?;
@@ -355,7 +561,9 @@ mod tests {
             file_path: path,
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let rule_id = RuleId::new("test-rule").ok_or("invalid rule id")?;
@@ -375,7 +583,9 @@ mod tests {
             file_path: path,
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         // Should not panic and should produce reasonable output
@@ -395,7 +605,9 @@ mod tests {
             file_path: path,
             content,
             ast: None,
+            line_offsets: None,
             region_resolver: Some(resolver),
+            walked_files: None,
         };
 
         // Should not panic and should produce reasonable output
@@ -429,6 +641,10 @@ mod tests {
             self.severity
         }
 
+        fn kind(&self) -> RuleKind {
+            RuleKind::Regex
+        }
+
         fn execute(&self, _ctx: &ExecutionContext) -> Vec<Violation> {
             vec![]
         }
@@ -452,7 +668,9 @@ mod tests {
             file_path: Path::new("test.rs"),
             content: "fn main() {}",
             ast: None,
+            line_offsets: None,
             region_resolver: None,
+            walked_files: None,
         };
 
         let violations = rule.execute(&ctx);
@@ -0,0 +1,158 @@
+#![forbid(unsafe_code)]
+
+//! Syntactic scopes for regex rules
+//!
+//! A regex rule with `scope = "comments" | "strings" | "code"` in its `[match]`
+//! section only reports matches that lie inside comment nodes, inside string
+//! literal nodes, or outside both, according to the file's tree-sitter parse.
+
+use serde::Deserialize;
+use std::ops::Range;
+use tree_sitter::{Node, Tree};
+
+/// Node kinds that are comments in the supported grammars
+const COMMENT_KINDS: [&str; 4] = [
+    "comment",
+    "line_comment",
+    "block_comment",
+    "multiline_comment",
+];
+
+/// Node kinds that are string or character literals in the supported grammars
+const STRING_KINDS: [&str; 11] = [
+    "string",
+    "string_literal",
+    "raw_string_literal",
+    "interpreted_string_literal",
+    "template_string",
+    "char_literal",
+    "rune_literal",
+    "raw_string",
+    "ansi_c_string",
+    "translated_string",
+    "heredoc_body",
+];
+
+/// Node kinds that embed code inside a string literal (f-strings, template
+/// literals, shell expansions)
+const INTERPOLATION_KINDS: [&str; 5] = [
+    "interpolation",
+    "template_substitution",
+    "simple_expansion",
+    "expansion",
+    "command_substitution",
+];
+
+/// Part of the source a regex rule's matches must lie in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum MatchScope {
+    /// Inside comments
+    Comments,
+    /// Inside string and character literals
+    Strings,
+    /// Outside comments and string literals
+    Code,
+}
+
+impl MatchScope {
+    /// Whether the bytes in `range` lie within this scope
+    ///
+    /// The range is classified by the innermost comment, string or
+    /// interpolation node that contains all of it; interpolations inside a
+    /// string (e.g. `{x}` in a Python f-string) count as code.
+    pub(crate) fn contains(self, tree: &Tree, range: Range<usize>) -> bool {
+        let node = tree
+            .root_node()
+            .descendant_for_byte_range(range.start, range.end);
+        self == node.map_or(MatchScope::Code, classify)
+    }
+}
+
+/// Classify a node by its innermost comment, string or interpolation ancestor
+fn classify(node: Node) -> MatchScope {
+    let mut current = Some(node);
+    while let Some(node) = current {
+        let kind = node.kind();
+        if COMMENT_KINDS.contains(&kind) {
+            return MatchScope::Comments;
+        }
+        if STRING_KINDS.contains(&kind) {
+            return MatchScope::Strings;
+        }
+        if INTERPOLATION_KINDS.contains(&kind) {
+            return MatchScope::Code;
+        }
+        current = node.parent();
+    }
+    MatchScope::Code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::ParserCache;
+    use crate::types::Language;
+
+    /// The scope of the first occurrence of `needle` in `content`
+    fn scope_of(
+        language: Language,
+        content: &str,
+        needle: &str,
+    ) -> Result<MatchScope, Box<dyn std::error::Error>> {
+        let mut parser = ParserCache::new().get_parser(language)?;
+        let tree = parser.parse(content, None).ok_or("parse failed")?;
+        let start = content.find(needle).ok_or("needle not found")?;
+        let range = start..start + needle.len();
+        [MatchScope::Comments, MatchScope::Strings, MatchScope::Code]
+            .into_iter()
+            .find(|scope| scope.contains(&tree, range.clone()))
+            .ok_or_else(|| "no scope".into())
+    }
+
+    #[cfg(feature = "lang-kotlin")]
+    #[test]
+    fn test_kotlin_scopes() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "// line\n/* block\n   more */\nval s = \"text\"\nfun code() {}\n";
+        assert_eq!(
+            scope_of(Language::Kotlin, content, "line")?,
+            MatchScope::Comments
+        );
+        assert_eq!(
+            scope_of(Language::Kotlin, content, "more")?,
+            MatchScope::Comments
+        );
+        assert_eq!(
+            scope_of(Language::Kotlin, content, "text")?,
+            MatchScope::Strings
+        );
+        assert_eq!(
+            scope_of(Language::Kotlin, content, "code")?,
+            MatchScope::Code
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lang-bash")]
+    #[test]
+    fn test_bash_scopes() -> Result<(), Box<dyn std::error::Error>> {
+        let content = "\
+# note
+echo 'raw' $'ansi' \"quoted $home\"
+cat <<EOF
+heredoc $(whoami)
+EOF
+run
+";
+        let scope = |needle: &str| scope_of(Language::Bash, content, needle);
+        assert_eq!(scope("note")?, MatchScope::Comments);
+        assert_eq!(scope("raw")?, MatchScope::Strings);
+        assert_eq!(scope("ansi")?, MatchScope::Strings);
+        assert_eq!(scope("quoted")?, MatchScope::Strings);
+        assert_eq!(scope("heredoc")?, MatchScope::Strings);
+        assert_eq!(scope("home")?, MatchScope::Code);
+        assert_eq!(scope("whoami")?, MatchScope::Code);
+        assert_eq!(scope("run")?, MatchScope::Code);
+        Ok(())
+    }
+}
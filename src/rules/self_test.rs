@@ -0,0 +1,202 @@
+#![forbid(unsafe_code)]
+
+//! Embedded rule self-tests
+//!
+//! Rule files may carry examples that `ratchets test-rules` checks the rule
+//! against:
+//!
+//! ```toml
+//! [[test]]
+//! code = "x = eval(source)\n"
+//! expect = 1                 # number of violations
+//!
+//! [[test]]
+//! code = "ok()\nbad()\n"
+//! filename = "src/app.py"    # optional, defaults to example.<ext>
+//! lines = [2]                # lines the violations start on
+//! ```
+//!
+//! The default file name uses the extension of the rule's (first) language.
+//! Each test sets exactly one of `expect` and `lines`. Rules that apply to
+//! every language have no default file name, so their tests must set one.
+
+use crate::error::RuleError;
+use crate::rules::Violation;
+use crate::types::Language;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// TOML structure of one `[[test]]` table
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TestSection {
+    code: String,
+    #[serde(default)]
+    filename: Option<String>,
+    #[serde(default)]
+    expect: Option<usize>,
+    #[serde(default)]
+    lines: Option<Vec<u32>>,
+}
+
+/// What a rule test expects the rule to report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expectation {
+    /// This many violations
+    Count(usize),
+    /// Violations starting on exactly these lines (1-indexed, sorted)
+    Lines(Vec<u32>),
+}
+
+/// An example the rule is checked against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTest {
+    /// Source code to check
+    pub code: String,
+    /// Path the code is checked as, which selects the language and include globs
+    pub filename: PathBuf,
+    /// What the rule should report
+    pub expected: Expectation,
+}
+
+impl RuleTest {
+    /// Compile a `[[test]]` table
+    ///
+    /// `language` is the rule's first language, if it has any, and supplies
+    /// the default file name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if the table sets both or
+    /// neither of `expect` and `lines`, or has no file name and no default.
+    pub(crate) fn compile(
+        section: TestSection,
+        language: Option<Language>,
+    ) -> Result<Self, RuleError> {
+        let expected = match (section.expect, section.lines) {
+            (Some(count), None) => Expectation::Count(count),
+            (None, Some(mut lines)) => {
+                lines.sort_unstable();
+                Expectation::Lines(lines)
+            }
+            _ => {
+                return Err(RuleError::InvalidDefinition(
+                    "[[test]] must set exactly one of expect and lines".to_string(),
+                ));
+            }
+        };
+
+        let filename = match (section.filename, language) {
+            (Some(filename), _) => PathBuf::from(filename),
+            (None, Some(language)) => PathBuf::from(format!("example.{}", language.extension())),
+            (None, None) => {
+                return Err(RuleError::InvalidDefinition(
+                    "[[test]] needs a filename for rules that apply to every language".to_string(),
+                ));
+            }
+        };
+
+        Ok(Self {
+            code: section.code,
+            filename,
+            expected,
+        })
+    }
+
+    /// Compile every `[[test]]` table of a rule
+    ///
+    /// # Errors
+    ///
+    /// Returns the first table's error, see [`RuleTest::compile`].
+    pub(crate) fn compile_all(
+        sections: Vec<TestSection>,
+        language: Option<Language>,
+    ) -> Result<Vec<Self>, RuleError> {
+        sections
+            .into_iter()
+            .map(|section| Self::compile(section, language))
+            .collect()
+    }
+
+    /// Compare the violations the rule reported against the expectation
+    ///
+    /// Returns a description of the mismatch if they differ.
+    pub fn verify(&self, violations: &[Violation]) -> Result<(), String> {
+        let mut found: Vec<u32> = violations.iter().map(|v| v.line).collect();
+        found.sort_unstable();
+
+        match &self.expected {
+            Expectation::Count(count) if *count == found.len() => Ok(()),
+            Expectation::Count(count) => Err(format!(
+                "expected {} violations, found {} on lines {:?}",
+                count,
+                found.len(),
+                found
+            )),
+            Expectation::Lines(lines) if *lines == found => Ok(()),
+            Expectation::Lines(lines) => Err(format!(
+                "expected violations on lines {:?}, found {:?}",
+                lines, found
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{RegionPath, RuleId};
+
+    fn section(toml: &str) -> Result<TestSection, Box<dyn std::error::Error>> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    fn violation_on(line: u32) -> Result<Violation, Box<dyn std::error::Error>> {
+        Ok(Violation {
+            rule_id: RuleId::new("no-eval").ok_or("invalid rule id")?,
+            file: PathBuf::from("example.py"),
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 5,
+            snippet: String::new(),
+            message: String::new(),
+            region: RegionPath::new("."),
+        })
+    }
+
+    #[test]
+    fn test_verify_count_and_lines() -> Result<(), Box<dyn std::error::Error>> {
+        let test = RuleTest::compile(
+            section("code = \"eval(x)\"\nexpect = 1")?,
+            Some(Language::Python),
+        )?;
+        assert_eq!(test.filename, PathBuf::from("example.py"));
+        assert!(test.verify(&[violation_on(1)?]).is_ok());
+        assert!(test.verify(&[]).is_err());
+
+        let test = RuleTest::compile(
+            section("code = \"\"\nfilename = \"src/a.rs\"\nlines = [3, 1]")?,
+            None,
+        )?;
+        assert_eq!(test.filename, PathBuf::from("src/a.rs"));
+        assert!(test.verify(&[violation_on(3)?, violation_on(1)?]).is_ok());
+        assert_eq!(
+            test.verify(&[violation_on(1)?]),
+            Err("expected violations on lines [1, 3], found [1]".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_compile_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let both = section("code = \"\"\nexpect = 1\nlines = [1]")?;
+        assert!(RuleTest::compile(both, Some(Language::Rust)).is_err());
+        let neither = section("code = \"\"")?;
+        assert!(RuleTest::compile(neither, Some(Language::Rust)).is_err());
+        let no_filename = section("code = \"\"\nexpect = 0")?;
+        assert!(RuleTest::compile(no_filename, None).is_err());
+        assert!(section("code = \"\"\nexpected = 0").is_err());
+        Ok(())
+    }
+}
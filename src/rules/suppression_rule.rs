@@ -0,0 +1,85 @@
+#![forbid(unsafe_code)]
+
+//! The built-in rule reporting suppression comments without a reason
+//!
+//! Suppression directives are parsed by the engine, which knows each file's
+//! language and so its comment syntax. This rule gives the directives it
+//! reports a rule of their own: projects enable it by ID or through
+//! `$common-starter`, and it is budgeted in ratchet-counts.toml, bumped and
+//! explained like any other rule.
+
+use crate::rules::{ExecutionContext, Rule, RuleDocs, RuleKind, Violation};
+use crate::types::{Language, RuleId, Severity};
+
+/// Rule ID reported for suppression directives without a reason
+pub const UNJUSTIFIED_SUPPRESSION: &str = "unjustified-suppression";
+
+/// Reports `ratchets-ignore` directives that give no reason
+pub struct UnjustifiedSuppressionRule {
+    id: RuleId,
+    docs: RuleDocs,
+}
+
+impl UnjustifiedSuppressionRule {
+    /// Creates the rule
+    pub fn new() -> Option<Self> {
+        Some(Self {
+            id: RuleId::new(UNJUSTIFIED_SUPPRESSION)?,
+            docs: RuleDocs {
+                rationale: Some(
+                    "A suppression hides a violation from its budget; the reason tells \
+                     reviewers why that is acceptable."
+                        .to_string(),
+                ),
+                help_url: None,
+                // Split so that this file does not carry the directives itself
+                good: Some(
+                    concat!(
+                        "let port = env.get(\"PORT\").unwrap(); // ",
+                        "ratchets-ignore[no-unwrap]: set by the launcher",
+                    )
+                    .to_string(),
+                ),
+                bad: Some(
+                    concat!(
+                        "let port = env.get(\"PORT\").unwrap(); // ",
+                        "ratchets-ignore[no-unwrap]",
+                    )
+                    .to_string(),
+                ),
+            },
+        })
+    }
+}
+
+impl Rule for UnjustifiedSuppressionRule {
+    fn id(&self) -> &RuleId {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        "Suppression comments must give a reason after a colon"
+    }
+
+    fn languages(&self) -> &[Language] {
+        &[]
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn kind(&self) -> RuleKind {
+        RuleKind::Suppression
+    }
+
+    /// Suppression rules report nothing on their own; the engine reports the
+    /// directives it parses from each file
+    fn execute(&self, _ctx: &ExecutionContext) -> Vec<Violation> {
+        Vec::new()
+    }
+
+    fn docs(&self) -> Option<&RuleDocs> {
+        Some(&self.docs)
+    }
+}
@@ -0,0 +1,508 @@
+#![forbid(unsafe_code)]
+
+//! Parameterized rule templates
+//!
+//! A rule template is a rule definition of any kind with a `[template]` table
+//! that names it and declares its parameters:
+//!
+//! ```toml
+//! [template]
+//! id = "python-no-module-import"
+//! kind = "regex"                 # regex, ast, imports or files
+//! params = ["module"]
+//!
+//! [rule]
+//! description = "Do not import {{module}}"
+//! severity = "error"
+//!
+//! [match]
+//! pattern = "\\bimport\\s+{{module}}\\b|\\bfrom\\s+{{module}}\\b"
+//! languages = ["python"]
+//! ```
+//!
+//! Each `[[rules.instances]]` entry of ratchets.toml instantiates a template
+//! as a rule with its own ID: `{{name}}` in every string of the definition,
+//! including `[[test]]` examples, is replaced by the instance's value for
+//! parameter `name`. In the `[match]` fields that hold regexes (`pattern` of
+//! regex rules; `forbid_name`, `require_name` and `forbid_path` of file rules)
+//! regex metacharacters in the value are escaped, so `os.path` matches only
+//! itself; `{{name:raw}}` inserts the value as a regex instead. Everywhere
+//! else values are inserted verbatim. Inserted values are never searched for
+//! further placeholders.
+//!
+//! Templates load like ratchet-sets: embedded builtin templates, then
+//! `builtin-ratchets/templates/*.toml`, then `ratchets/templates/*.toml`,
+//! later ones overriding earlier ones with the same ID.
+
+use crate::config::ratchet_toml::RuleInstance;
+use crate::error::RuleError;
+use crate::rules::{AstRule, FileRule, ImportRule, RegexRule, Rule, RuleContext};
+use crate::types::RuleId;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// TOML structure of the `[template]` table
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateSection {
+    id: String,
+    kind: TemplateKind,
+    #[serde(default)]
+    params: Vec<String>,
+}
+
+/// Kind of rule a template defines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateKind {
+    /// A regex rule, as in `ratchets/regex/`
+    Regex,
+    /// An AST rule, as in `ratchets/ast/`
+    Ast,
+    /// An import rule, as in `ratchets/imports/`
+    Imports,
+    /// A file rule, as in `ratchets/files/`
+    Files,
+}
+
+impl TemplateKind {
+    /// The `[match]` fields of this kind of rule that hold regexes
+    fn regex_fields(self) -> &'static [&'static str] {
+        match self {
+            TemplateKind::Regex => &["pattern"],
+            TemplateKind::Files => &["forbid_name", "require_name", "forbid_path"],
+            TemplateKind::Ast | TemplateKind::Imports => &[],
+        }
+    }
+}
+
+/// A rule definition with parameters, instantiated by `[[rules.instances]]`
+#[derive(Debug, Clone)]
+pub struct RuleTemplate {
+    id: String,
+    kind: TemplateKind,
+    params: Vec<String>,
+    /// The rule definition, without the `[template]` table
+    definition: toml::Table,
+}
+
+impl RuleTemplate {
+    /// Parse a RuleTemplate from TOML content
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if the TOML is invalid, or the
+    /// `[template]` table is missing or invalid.
+    pub fn from_toml(content: &str) -> Result<Self, RuleError> {
+        let mut definition: toml::Table = toml::from_str(content)
+            .map_err(|e| RuleError::InvalidDefinition(format!("Failed to parse TOML: {}", e)))?;
+
+        let section: TemplateSection = definition
+            .remove("template")
+            .ok_or_else(|| {
+                RuleError::InvalidDefinition("Rule template has no [template] table".to_string())
+            })?
+            .try_into()
+            .map_err(|e| {
+                RuleError::InvalidDefinition(format!("Invalid [template] table: {}", e))
+            })?;
+
+        if RuleId::new(section.id.as_str()).is_none() {
+            return Err(RuleError::InvalidDefinition(format!(
+                "Invalid template ID: {}",
+                section.id
+            )));
+        }
+
+        Ok(Self {
+            id: section.id,
+            kind: section.kind,
+            params: section.params,
+            definition,
+        })
+    }
+
+    /// Parse a RuleTemplate from a TOML file path
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if the file cannot be read or parsed.
+    pub fn from_path(path: &Path) -> Result<Self, RuleError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            RuleError::InvalidDefinition(format!("Failed to read file {:?}: {}", path, e))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    /// The template's ID, referenced by `template` in `[[rules.instances]]`
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The kind of rule the template defines
+    pub fn kind(&self) -> TemplateKind {
+        self.kind
+    }
+
+    /// Instantiate the template as a rule
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError::InvalidDefinition` if the instance misses a
+    /// parameter or sets an undeclared one, and any error of parsing the
+    /// instantiated definition as a rule of the template's kind.
+    pub fn instantiate(
+        &self,
+        instance: &RuleInstance,
+        ctx: Option<&RuleContext>,
+    ) -> Result<Box<dyn Rule>, RuleError> {
+        let invalid = |reason: String| {
+            RuleError::InvalidDefinition(format!(
+                "Rule instance '{}' of template '{}': {}",
+                instance.id, self.id, reason
+            ))
+        };
+
+        if let Some(missing) = self
+            .params
+            .iter()
+            .find(|name| !instance.params.contains_key(*name))
+        {
+            return Err(invalid(format!("missing parameter '{}'", missing)));
+        }
+        let mut unknown: Vec<&String> = instance
+            .params
+            .keys()
+            .filter(|name| !self.params.contains(name))
+            .collect();
+        unknown.sort();
+        if let Some(unknown) = unknown.first() {
+            return Err(invalid(format!("unknown parameter '{}'", unknown)));
+        }
+
+        let mut definition = self.definition.clone();
+        let regex_fields = self.kind.regex_fields();
+        for (key, value) in definition.iter_mut() {
+            match value {
+                toml::Value::Table(section) if key == "match" => {
+                    for (field, value) in section.iter_mut() {
+                        let escape = regex_fields.contains(&field.as_str());
+                        substitute(value, &instance.params, escape);
+                    }
+                }
+                _ => substitute(value, &instance.params, false),
+            }
+        }
+
+        let rule_section = definition
+            .entry("rule")
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| invalid("[rule] is not a table".to_string()))?;
+        rule_section.insert(
+            "id".to_string(),
+            toml::Value::String(instance.id.as_str().to_string()),
+        );
+        if let Some(ref description) = instance.description {
+            rule_section.insert(
+                "description".to_string(),
+                toml::Value::String(description.clone()),
+            );
+        }
+
+        let content = toml::to_string(&definition).map_err(|e| invalid(e.to_string()))?;
+        let rule: Box<dyn Rule> = match self.kind {
+            TemplateKind::Regex => Box::new(RegexRule::from_toml_with_context(&content, ctx)?),
+            TemplateKind::Ast => Box::new(AstRule::from_toml_with_context(&content, ctx)?),
+            TemplateKind::Imports => Box::new(ImportRule::from_toml_with_context(&content, ctx)?),
+            TemplateKind::Files => Box::new(FileRule::from_toml_with_context(&content, ctx)?),
+        };
+        Ok(rule)
+    }
+}
+
+/// Replace `{{name}}` with the value of each parameter in every string of `value`
+///
+/// With `escape`, values are regex-escaped unless the placeholder is `{{name:raw}}`.
+fn substitute(value: &mut toml::Value, params: &HashMap<String, String>, escape: bool) {
+    match value {
+        toml::Value::String(text) if text.contains("{{") => {
+            *text = substitute_text(text, params, escape);
+        }
+        toml::Value::Array(items) => {
+            for item in items {
+                substitute(item, params, escape);
+            }
+        }
+        toml::Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                substitute(item, params, escape);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace the placeholders of `text` in a single left-to-right pass
+///
+/// Text that names no parameter, such as `{{` in a regex, is kept as is.
+fn substitute_text(text: &str, params: &HashMap<String, String>, escape: bool) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest[2..].find("}}").and_then(|end| {
+            let placeholder = &rest[2..2 + end];
+            let (name, raw) = match placeholder.strip_suffix(":raw") {
+                Some(name) => (name, true),
+                None => (placeholder, false),
+            };
+            let value = params.get(name)?;
+            Some((
+                end + 4,
+                if escape && !raw {
+                    regex::escape(value)
+                } else {
+                    value.clone()
+                },
+            ))
+        });
+        match value {
+            Some((len, value)) => {
+                result.push_str(&value);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Registry of rule templates, keyed by template ID
+#[derive(Debug, Default)]
+pub struct TemplateRegistry {
+    templates: HashMap<String, RuleTemplate>,
+}
+
+impl TemplateRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Insert (or replace) a template
+    pub fn insert(&mut self, template: RuleTemplate) {
+        self.templates.insert(template.id.clone(), template);
+    }
+
+    /// Look up a template by ID
+    pub fn get(&self, id: &str) -> Option<&RuleTemplate> {
+        self.templates.get(id)
+    }
+
+    /// Load every template: embedded, then filesystem builtin from
+    /// `builtin-ratchets/templates/`, then user-defined from `ratchets/templates/`
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if any template fails to read or parse.
+    pub fn load_all() -> Result<Self, RuleError> {
+        let mut registry = Self::new();
+        for template in crate::rules::load_builtin_templates()? {
+            registry.insert(template);
+        }
+        registry.load_templates_from_dir(&Path::new("builtin-ratchets").join("templates"))?;
+        registry.load_templates_from_dir(&Path::new("ratchets").join("templates"))?;
+        Ok(registry)
+    }
+
+    /// Load the `*.toml` templates of a directory; a missing directory has none
+    ///
+    /// # Errors
+    ///
+    /// Returns `RuleError` if a template fails to read or parse.
+    pub fn load_templates_from_dir(&mut self, dir: &Path) -> Result<(), RuleError> {
+        if !dir.exists() {
+            return Ok(());
+        }
+
+        let entries = fs::read_dir(dir).map_err(|e| {
+            RuleError::InvalidDefinition(format!(
+                "Failed to read template directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| {
+                RuleError::InvalidDefinition(format!(
+                    "Failed to read template directory entry in {}: {}",
+                    dir.display(),
+                    e
+                ))
+            })?;
+
+            let path = entry.path();
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("toml") {
+                continue;
+            }
+            self.insert(RuleTemplate::from_path(&path)?);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::ExecutionContext;
+
+    fn instance(
+        template: &str,
+        id: &str,
+        params: &[(&str, &str)],
+    ) -> Result<RuleInstance, Box<dyn std::error::Error>> {
+        Ok(RuleInstance {
+            template: template.to_string(),
+            id: RuleId::new(id).ok_or("invalid rule ID")?,
+            description: None,
+            params: params
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        })
+    }
+
+    #[cfg(feature = "lang-python")]
+    #[test]
+    fn test_builtin_template_instantiates_rules() -> Result<(), Box<dyn std::error::Error>> {
+        let templates = TemplateRegistry::load_all()?;
+        let template = templates
+            .get("python-no-module-import")
+            .ok_or("missing builtin template")?;
+        assert_eq!(template.kind(), TemplateKind::Regex);
+
+        let rule = template.instantiate(
+            &instance(
+                "python-no-module-import",
+                "no-pickle",
+                &[("module", "pickle")],
+            )?,
+            None,
+        )?;
+        assert_eq!(rule.id().as_str(), "no-pickle");
+        assert_eq!(rule.description(), "Do not import pickle");
+        assert_eq!(rule.tests().len(), 2);
+
+        let violations = rule.execute(&ExecutionContext {
+            file_path: Path::new("app.py"),
+            content: "import pickle\nfrom pickle import loads\nimport pickletools\n",
+            ast: None,
+            line_offsets: None,
+            region_resolver: None,
+            walked_files: None,
+        });
+        let lines: Vec<u32> = violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, vec![1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_instance_parameters_are_checked() -> Result<(), Box<dyn std::error::Error>> {
+        let template = RuleTemplate::from_toml(
+            r#"
+[template]
+id = "no-call"
+kind = "regex"
+params = ["func"]
+
+[rule]
+description = "Do not call {{func}}"
+severity = "warning"
+
+[match]
+pattern = "\\b{{func}}\\("
+"#,
+        )?;
+
+        let mut with_description = instance("no-call", "no-exit", &[("func", "exit")])?;
+        with_description.description = Some("exit() skips cleanup".to_string());
+        let rule = template.instantiate(&with_description, None)?;
+        assert_eq!(rule.description(), "exit() skips cleanup");
+        assert_eq!(rule.regex().map(|r| r.as_str()), Some(r"\bexit\("));
+
+        assert!(
+            template
+                .instantiate(&instance("no-call", "no-exit", &[])?, None)
+                .is_err()
+        );
+        let extra = instance("no-call", "no-exit", &[("func", "exit"), ("module", "sys")])?;
+        assert!(template.instantiate(&extra, None).is_err());
+        assert!(RuleTemplate::from_toml("[rule]\ndescription = \"d\"\n").is_err());
+        Ok(())
+    }
+    #[test]
+    fn test_values_are_escaped_in_regex_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let template = RuleTemplate::from_toml(
+            r#"
+[template]
+id = "no-call"
+kind = "regex"
+params = ["func", "args"]
+
+[rule]
+description = "Do not call {{func}}({{args}})"
+severity = "warning"
+
+[match]
+pattern = "{{func}}\\({{args:raw}}\\)"
+"#,
+        )?;
+
+        let rule = template.instantiate(
+            &instance(
+                "no-call",
+                "no-exit",
+                &[("func", "sys.exit"), ("args", "[0-9]+")],
+            )?,
+            None,
+        )?;
+        assert_eq!(rule.description(), "Do not call sys.exit([0-9]+)");
+        assert_eq!(
+            rule.regex().map(|r| r.as_str()),
+            Some(r"sys\.exit\([0-9]+\)")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_substitution_is_a_single_pass() {
+        let params: HashMap<String, String> = [
+            ("a".to_string(), "{{b}}".to_string()),
+            ("b".to_string(), "{{a}}".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            substitute_text("{{a}}-{{b}}", &params, false),
+            "{{b}}-{{a}}"
+        );
+        assert_eq!(substitute_text("{{{a}}}", &params, false), "{{{b}}}");
+        assert_eq!(
+            substitute_text("x{2}{{c}}{{", &params, false),
+            "x{2}{{c}}{{"
+        );
+    }
+}
@@ -13,9 +13,17 @@ use std::fmt;
 pub enum Language {
     Rust,
     TypeScript,
+    /// TypeScript with JSX (`.tsx` files), parsed with its own grammar
+    Tsx,
     JavaScript,
     Python,
     Go,
+    Java,
+    Kotlin,
+    C,
+    Cpp,
+    /// Bash and POSIX shell scripts
+    Bash,
 }
 
 /// Violation severity levels
@@ -245,9 +253,56 @@ impl Language {
         match self {
             Language::Rust => "rust",
             Language::TypeScript => "ts",
+            Language::Tsx => "tsx",
             Language::JavaScript => "js",
             Language::Python => "py",
             Language::Go => "go",
+            Language::Java => "java",
+            Language::Kotlin => "kotlin",
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::Bash => "sh",
+        }
+    }
+
+    /// Returns the usual file extension of this language, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Language::Rust => "rs",
+            Language::TypeScript => "ts",
+            Language::Tsx => "tsx",
+            Language::JavaScript => "js",
+            Language::Python => "py",
+            Language::Go => "go",
+            Language::Java => "java",
+            Language::Kotlin => "kt",
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::Bash => "sh",
+        }
+    }
+
+    /// Returns true if rules for this language apply to files of `other`
+    ///
+    /// TSX is a dialect of TypeScript, so TypeScript rules also apply to
+    /// `.tsx` files, while TSX rules apply only to them.
+    pub fn includes(&self, other: Language) -> bool {
+        *self == other || (*self == Language::TypeScript && other == Language::Tsx)
+    }
+
+    /// Returns the markers that start a comment in this language
+    pub fn comment_markers(&self) -> &'static [&'static str] {
+        match self {
+            Language::Rust
+            | Language::TypeScript
+            | Language::Tsx
+            | Language::JavaScript
+            | Language::Go
+            | Language::Java
+            | Language::Kotlin
+            | Language::C
+            | Language::Cpp => &["//", "/*"],
+            Language::Python | Language::Bash => &["#"],
         }
     }
 
@@ -256,9 +311,15 @@ impl Language {
         [
             Language::Rust,
             Language::TypeScript,
+            Language::Tsx,
             Language::JavaScript,
             Language::Python,
             Language::Go,
+            Language::Java,
+            Language::Kotlin,
+            Language::C,
+            Language::Cpp,
+            Language::Bash,
         ]
         .into_iter()
     }
@@ -304,6 +365,21 @@ mod tests {
             Language::Python
         );
         assert_eq!(serde_json::from_str::<Language>("\"go\"")?, Language::Go);
+        assert_eq!(serde_json::from_str::<Language>("\"tsx\"")?, Language::Tsx);
+        assert_eq!(
+            serde_json::from_str::<Language>("\"java\"")?,
+            Language::Java
+        );
+        assert_eq!(
+            serde_json::from_str::<Language>("\"kotlin\"")?,
+            Language::Kotlin
+        );
+        assert_eq!(serde_json::from_str::<Language>("\"c\"")?, Language::C);
+        assert_eq!(serde_json::from_str::<Language>("\"cpp\"")?, Language::Cpp);
+        assert_eq!(
+            serde_json::from_str::<Language>("\"bash\"")?,
+            Language::Bash
+        );
         Ok(())
     }
 
@@ -311,6 +387,7 @@ mod tests {
     fn test_language_all_variants_exist() {
         let _rust = Language::Rust;
         let _typescript = Language::TypeScript;
+        let _tsx = Language::Tsx;
         let _javascript = Language::JavaScript;
         let _python = Language::Python;
         let _go = Language::Go;
@@ -651,19 +728,48 @@ mod tests {
     fn test_language_ignore_type_name() {
         assert_eq!(Language::Rust.ignore_type_name(), "rust");
         assert_eq!(Language::TypeScript.ignore_type_name(), "ts");
+        assert_eq!(Language::Tsx.ignore_type_name(), "tsx");
         assert_eq!(Language::JavaScript.ignore_type_name(), "js");
         assert_eq!(Language::Python.ignore_type_name(), "py");
         assert_eq!(Language::Go.ignore_type_name(), "go");
+        assert_eq!(Language::Java.ignore_type_name(), "java");
+        assert_eq!(Language::Kotlin.ignore_type_name(), "kotlin");
+        assert_eq!(Language::C.ignore_type_name(), "c");
+        assert_eq!(Language::Cpp.ignore_type_name(), "cpp");
+        assert_eq!(Language::Bash.ignore_type_name(), "sh");
+    }
+
+    #[test]
+    fn test_language_includes_dialects() {
+        assert!(Language::TypeScript.includes(Language::TypeScript));
+        assert!(Language::TypeScript.includes(Language::Tsx));
+        assert!(Language::Tsx.includes(Language::Tsx));
+        assert!(!Language::Tsx.includes(Language::TypeScript));
+        assert!(!Language::JavaScript.includes(Language::Tsx));
+    }
+
+    #[test]
+    fn test_language_comment_markers() {
+        assert_eq!(Language::Rust.comment_markers(), ["//", "/*"]);
+        assert_eq!(Language::Go.comment_markers(), ["//", "/*"]);
+        assert_eq!(Language::Python.comment_markers(), ["#"]);
+        assert_eq!(Language::Bash.comment_markers(), ["#"]);
     }
 
     #[test]
     fn test_language_all() {
         let languages: Vec<_> = Language::all().collect();
-        assert_eq!(languages.len(), 5);
+        assert_eq!(languages.len(), 11);
         assert!(languages.contains(&Language::Rust));
         assert!(languages.contains(&Language::TypeScript));
+        assert!(languages.contains(&Language::Tsx));
         assert!(languages.contains(&Language::JavaScript));
         assert!(languages.contains(&Language::Python));
         assert!(languages.contains(&Language::Go));
+        assert!(languages.contains(&Language::Java));
+        assert!(languages.contains(&Language::Kotlin));
+        assert!(languages.contains(&Language::C));
+        assert!(languages.contains(&Language::Cpp));
+        assert!(languages.contains(&Language::Bash));
     }
 }
@@ -0,0 +1,105 @@
+#![forbid(unsafe_code)]
+
+//! Integration tests for the linter report adapters
+//!
+//! These tests parse the sample reports in tests/fixtures/reports, which
+//! follow the output of each tool.
+
+use ratchets::adapters::{ReportFormat, ReportedViolation};
+use std::path::PathBuf;
+
+/// Helper function to parse a sample report
+fn parse_fixture(format: ReportFormat, filename: &str) -> Vec<ReportedViolation> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("reports")
+        .join(filename);
+    let content = std::fs::read_to_string(&path).unwrap();
+    format.parse(&content).unwrap()
+}
+
+/// Helper function to list each violation as `file:line:column code`
+fn locations(violations: &[ReportedViolation]) -> Vec<String> {
+    violations
+        .iter()
+        .map(|v| format!("{}:{}:{} {}", v.file.display(), v.line, v.column, v.code))
+        .collect()
+}
+
+#[test]
+fn test_clippy_report() {
+    let violations = parse_fixture(ReportFormat::Clippy, "clippy.jsonl");
+
+    // Artifacts, summaries without a code and non-primary spans are skipped
+    assert_eq!(
+        locations(&violations),
+        vec![
+            "src/lib.rs:7:13 clippy::unwrap_used",
+            "src/legacy/mod.rs:3:5 clippy::unwrap_used",
+            "src/lib.rs:10:5 clippy::if_same_then_else",
+        ]
+    );
+    assert_eq!(violations[2].end_line, 12);
+    assert_eq!(violations[0].message, "used `unwrap()` on a `Result` value");
+}
+
+#[test]
+fn test_ruff_report() {
+    let violations = parse_fixture(ReportFormat::Ruff, "ruff.json");
+
+    assert_eq!(
+        locations(&violations),
+        vec![
+            "src/app.py:4:89 E501",
+            "src/legacy/old.py:1:8 F401",
+            "src/legacy/old.py:9:89 E501",
+            "src/legacy/old.py:12:1 syntax-error",
+        ]
+    );
+    assert_eq!(violations[0].end_column, 101);
+    assert_eq!(violations[1].message, "`os` imported but unused");
+}
+
+#[test]
+fn test_eslint_report() {
+    let violations = parse_fixture(ReportFormat::Eslint, "eslint.json");
+
+    assert_eq!(
+        locations(&violations),
+        vec![
+            "web/index.js:3:7 no-unused-vars",
+            "web/index.js:8:20 @typescript-eslint/no-explicit-any",
+            "web/broken.js:2:14 parse-error",
+        ]
+    );
+    // Messages without an end position end where they start
+    assert_eq!(violations[2].end_line, 2);
+    assert_eq!(violations[2].end_column, 14);
+}
+
+#[test]
+fn test_mypy_report() {
+    let violations = parse_fixture(ReportFormat::Mypy, "mypy.txt");
+
+    // Notes and the summary line are skipped
+    assert_eq!(
+        locations(&violations),
+        vec![
+            "src/app.py:5:12 arg-type",
+            "src/legacy/old.py:14:1 no-untyped-def",
+            "src/legacy/old.py:20:1 misc",
+        ]
+    );
+    assert_eq!(
+        violations[0].message,
+        "Argument 1 to \"greet\" has incompatible type \"int\"; expected \"str\""
+    );
+}
+
+#[test]
+fn test_malformed_reports_are_errors() {
+    assert!(ReportFormat::Ruff.parse("not json").is_err());
+    assert!(ReportFormat::Eslint.parse("{\"filePath\": 1}").is_err());
+    assert!(ReportFormat::Clippy.parse("{\"reason\":").is_err());
+}
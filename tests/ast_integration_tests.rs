@@ -24,6 +24,11 @@ fn read_fixture(name: &str) -> String {
         .unwrap_or_else(|e| panic!("Failed to read fixture {}: {}", path, e))
 }
 
+/// Helper function to build `lines` lines of filler statements
+fn filler_lines(lines: usize, statement: &str) -> String {
+    (0..lines).map(|_| format!("    {}\n", statement)).collect()
+}
+
 #[cfg(feature = "lang-rust")]
 mod rust_tests {
     use super::*;
@@ -368,6 +373,30 @@ fn clean_code() {
             );
         }
     }
+    #[test]
+    fn test_rust_max_function_length() {
+        let rule = load_builtin_rule("rust", "max-function-length");
+        let content = format!(
+            "fn short() {{\n{}}}\n\nfn long() {{\n{}}}\n",
+            filler_lines(198, "step();"),
+            filler_lines(199, "step();"),
+        );
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Rust).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        let violations = rule.execute_with_tree(&tree, &content, Path::new("lib.rs"), None);
+
+        // short() spans exactly 200 lines, long() spans 201
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, 202);
+        assert_eq!(violations[0].snippet, "long");
+        assert_eq!(
+            violations[0].message,
+            "Function long has 201 lines (max 200)"
+        );
+    }
 }
 
 #[cfg(feature = "lang-typescript")]
@@ -393,18 +422,7 @@ mod typescript_tests {
         let violations =
             rule.execute_with_tree(&tree, &content, Path::new("typescript_any.ts"), None);
 
-        // Debug: print what we found
-        if violations.is_empty() {
-            eprintln!("WARNING: No violations found. The TypeScript query may need adjustment.");
-            eprintln!("This could be due to:");
-            eprintln!("1. The predicate (#eq? @violation \"any\") not matching as expected");
-            eprintln!("2. TypeScript tree-sitter parsing differences");
-            eprintln!("For now, we'll just verify the test runs without panic.");
-            // Don't fail the test - this is a known issue we're documenting
-            return;
-        }
-
-        // Should find 'any' type annotations if the query works correctly
+        // Should find 'any' type annotations
         assert!(!violations.is_empty(), "Expected to find violations");
     }
 
@@ -420,12 +438,7 @@ mod typescript_tests {
         let violations =
             rule.execute_with_tree(&tree, &content, Path::new("typescript_any.ts"), None);
 
-        // The TypeScript query may not find violations due to predicate matching
-        // This is a known limitation we're documenting
-        if violations.is_empty() {
-            eprintln!("No TypeScript violations found - query may need adjustment");
-            return;
-        }
+        assert!(!violations.is_empty(), "Expected to find violations");
 
         // Verify all violations have valid positions
         for violation in &violations {
@@ -464,18 +477,130 @@ function cleanCode(param: string): number {
         let violations =
             rule.execute_with_tree(&tree, &content, Path::new("typescript_any.ts"), None);
 
-        // The TypeScript query may not find violations due to predicate matching
-        // This is a known limitation we're documenting
-        if violations.is_empty() {
-            eprintln!("No TypeScript violations found - query may need adjustment");
-            return;
-        }
+        assert!(!violations.is_empty(), "Expected to find violations");
 
         // If we found violations, verify they have reasonable snippets
         for violation in &violations {
             assert!(!violation.snippet.is_empty(), "Snippet should not be empty");
         }
     }
+    #[test]
+    fn test_typescript_max_function_length() {
+        let rule = load_builtin_rule("typescript", "max-function-length");
+        let body = filler_lines(250, "step();");
+        let content = format!(
+            "function a() {{\n{body}}}\n\
+             const b = () => {{\n{body}}};\n\
+             class C {{\n  d() {{\n{body}  }}\n}}\n\
+             function e() {{}}\n"
+        );
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::TypeScript).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        let violations = rule.execute_with_tree(&tree, &content, Path::new("app.ts"), None);
+
+        let names: Vec<&str> = violations.iter().map(|v| v.snippet.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "d"]);
+    }
+
+    #[test]
+    fn test_tsx_parses_without_errors() {
+        let content = read_fixture("tsx_component.tsx");
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Tsx).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        assert!(!tree.root_node().has_error());
+    }
+
+    #[test]
+    fn test_no_any_finds_violations_in_tsx() {
+        let rule = load_builtin_rule("typescript", "no-any");
+        let content = read_fixture("tsx_component.tsx");
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Tsx).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        let violations =
+            rule.execute_with_tree(&tree, &content, Path::new("tsx_component.tsx"), None);
+
+        let lines: Vec<u32> = violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, vec![7, 16]);
+    }
+}
+
+/// Queries over JSX elements, shared between JavaScript and TSX
+#[cfg(all(feature = "lang-javascript", feature = "lang-typescript"))]
+mod jsx_tests {
+    use super::*;
+
+    fn no_inline_styles() -> AstRule {
+        let toml = r#"
+[rule]
+id = "no-inline-styles"
+description = "Use class names instead of inline styles"
+severity = "warning"
+
+[match]
+language = ["javascript", "tsx"]
+query = """
+(jsx_attribute
+  (property_identifier) @name
+  (#eq? @name "style")) @violation
+"""
+"#;
+        AstRule::from_toml(toml).unwrap()
+    }
+
+    fn violation_lines(language: Language, fixture: &str) -> Vec<u32> {
+        let rule = no_inline_styles();
+        let content = read_fixture(fixture);
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(language).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        rule.execute_with_tree(&tree, &content, Path::new(fixture), None)
+            .iter()
+            .map(|v| v.line)
+            .collect()
+    }
+
+    #[test]
+    fn test_jsx_query_in_javascript() {
+        assert_eq!(
+            violation_lines(Language::JavaScript, "jsx_component.jsx"),
+            vec![5]
+        );
+    }
+
+    #[test]
+    fn test_jsx_query_in_tsx() {
+        assert_eq!(
+            violation_lines(Language::Tsx, "tsx_component.tsx"),
+            vec![14]
+        );
+    }
+
+    #[test]
+    fn test_jsx_query_rejected_for_typescript() {
+        // Plain TypeScript has no JSX nodes, so the query cannot compile for it
+        let toml = r#"
+[rule]
+id = "no-inline-styles"
+description = "Use class names instead of inline styles"
+severity = "warning"
+
+[match]
+language = "typescript"
+query = "(jsx_attribute) @violation"
+"#;
+        assert!(AstRule::from_toml(toml).is_err());
+    }
 }
 
 #[cfg(feature = "lang-python")]
@@ -1041,6 +1166,191 @@ def clean_code():
             );
         }
     }
+    #[test]
+    fn test_python_max_function_length() {
+        let rule = load_builtin_rule("python", "max-function-length");
+        let content = format!(
+            "def short():\n{}\n\nclass A:\n    def long(self):\n{}",
+            filler_lines(199, "step()"),
+            filler_lines(201, "    step()"),
+        );
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Python).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        let violations = rule.execute_with_tree(&tree, &content, Path::new("app.py"), None);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].snippet, "long");
+        assert_eq!(
+            violations[0].message,
+            "Function long has 202 lines (max 200)"
+        );
+    }
+}
+
+#[cfg(feature = "lang-java")]
+mod java_tests {
+    use super::*;
+
+    fn run_rule(rule_name: &str) -> Vec<u32> {
+        let rule = load_builtin_rule("java", rule_name);
+        let content = read_fixture("java_logging.java");
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Java).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        rule.execute_with_tree(&tree, &content, Path::new("OrderService.java"), None)
+            .iter()
+            .map(|v| v.line)
+            .collect()
+    }
+
+    #[test]
+    fn test_java_rules_load() {
+        let rule = load_builtin_rule("java", "no-system-out-println");
+        assert_eq!(rule.id().as_str(), "java-no-system-out-println");
+        assert_eq!(rule.languages(), &[Language::Java]);
+
+        let rule = load_builtin_rule("java", "no-catch-exception");
+        assert_eq!(rule.id().as_str(), "java-no-catch-exception");
+        assert_eq!(rule.languages(), &[Language::Java]);
+    }
+
+    #[test]
+    fn test_java_no_system_out_println_finds_violations() {
+        // Line 9: System.out.println, line 15: System.err.println
+        assert_eq!(run_rule("no-system-out-println"), vec![9, 15]);
+    }
+
+    #[test]
+    fn test_java_no_catch_exception_finds_violations() {
+        // Line 14: catch (Exception e), line 22: multi-catch with Throwable
+        assert_eq!(run_rule("no-catch-exception"), vec![14, 22]);
+    }
+}
+
+#[cfg(feature = "lang-kotlin")]
+mod kotlin_tests {
+    use super::*;
+
+    fn run_rule(rule_name: &str) -> Vec<u32> {
+        let rule = load_builtin_rule("kotlin", rule_name);
+        let content = read_fixture("kotlin_logging.kt");
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Kotlin).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        rule.execute_with_tree(&tree, &content, Path::new("OrderService.kt"), None)
+            .iter()
+            .map(|v| v.line)
+            .collect()
+    }
+
+    #[test]
+    fn test_kotlin_rules_load() {
+        let rule = load_builtin_rule("kotlin", "no-println");
+        assert_eq!(rule.id().as_str(), "kotlin-no-println");
+        assert_eq!(rule.languages(), &[Language::Kotlin]);
+
+        let rule = load_builtin_rule("kotlin", "no-catch-exception");
+        assert_eq!(rule.id().as_str(), "kotlin-no-catch-exception");
+        assert_eq!(rule.languages(), &[Language::Kotlin]);
+    }
+
+    #[test]
+    fn test_kotlin_no_println_finds_violations() {
+        // Line 7: println, line 13: print; logger.println on line 23 is a method
+        assert_eq!(run_rule("no-println"), vec![7, 13]);
+    }
+
+    #[test]
+    fn test_kotlin_no_catch_exception_finds_violations() {
+        // Line 12: Exception, line 20: qualified kotlin.Throwable
+        assert_eq!(run_rule("no-catch-exception"), vec![12, 20]);
+    }
+}
+
+#[cfg(feature = "lang-c")]
+mod c_tests {
+    use super::*;
+
+    #[test]
+    fn test_c_no_unsafe_string_functions_finds_violations() {
+        let rule = load_builtin_rule("c", "no-unsafe-string-functions");
+        assert_eq!(rule.languages(), &[Language::C]);
+        let content = read_fixture("c_strings.c");
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::C).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        let violations = rule.execute_with_tree(&tree, &content, Path::new("strings.c"), None);
+
+        // Line 6: strcpy, line 7: strcat, line 12: sprintf, line 17: gets
+        let lines: Vec<u32> = violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, vec![6, 7, 12, 17]);
+        assert_eq!(violations[0].snippet, "strcpy(dest, src)");
+    }
+}
+
+#[cfg(feature = "lang-cpp")]
+mod cpp_tests {
+    use super::*;
+
+    fn run_rule(rule_name: &str) -> Vec<u32> {
+        let rule = load_builtin_rule("cpp", rule_name);
+        let content = read_fixture("cpp_casts.cpp");
+
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Cpp).unwrap();
+        let tree = parser.parse(&content, None).unwrap();
+
+        rule.execute_with_tree(&tree, &content, Path::new("casts.cpp"), None)
+            .iter()
+            .map(|v| v.line)
+            .collect()
+    }
+
+    #[test]
+    fn test_cpp_no_unsafe_string_functions_finds_violations() {
+        // Line 7: strcpy, line 8: std::sprintf
+        assert_eq!(run_rule("no-unsafe-string-functions"), vec![7, 8]);
+    }
+
+    #[test]
+    fn test_cpp_no_c_style_casts_finds_violations() {
+        // Line 13: (int), line 14: (const char *); static_cast is fine
+        assert_eq!(run_rule("no-c-style-casts"), vec![13, 14]);
+    }
+}
+
+#[cfg(feature = "lang-bash")]
+mod bash_tests {
+    use super::*;
+
+    #[test]
+    fn test_bash_no_unquoted_rm_expansion_finds_violations() {
+        let rule = load_builtin_rule("bash", "no-unquoted-rm-expansion");
+        assert_eq!(rule.id().as_str(), "bash-no-unquoted-rm-expansion");
+        assert_eq!(rule.languages(), &[Language::Bash]);
+
+        let content =
+            "#!/bin/sh\nrm -rf $TMP ${CACHE}/old\nrm -rf \"$TMP\"\nrm -- \"${CACHE}\"/*\n";
+        let parser_cache = ParserCache::new();
+        let mut parser = parser_cache.get_parser(Language::Bash).unwrap();
+        let tree = parser.parse(content, None).unwrap();
+
+        let violations = rule.execute_with_tree(&tree, content, Path::new("bin/clean"), None);
+
+        // Both unquoted arguments on line 2; quoted ones are fine
+        let snippets: Vec<&str> = violations.iter().map(|v| v.snippet.as_str()).collect();
+        assert_eq!(snippets, vec!["$TMP", "${CACHE}/old"]);
+        assert!(violations.iter().all(|v| v.line == 2));
+    }
 }
 
 /// Tests for query validation and error handling
@@ -45,7 +45,9 @@ pub fn matches(rule: &AstRule, src: &str) -> usize {
         file_path: Path::new("t.py"),
         content: src,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
     rule.execute(&ctx).len()
 }
@@ -83,6 +83,9 @@ fn test_check_command_within_budget() {
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should pass because we have 1 TODO and budget is 2
@@ -157,6 +160,9 @@ pattern = "TODO"
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should fail because we have 2 TODOs and budget is 1
@@ -184,6 +190,9 @@ fn test_check_command_missing_config() {
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should return error code
@@ -219,6 +228,9 @@ include = ["**/*.rs"]
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should succeed with warning (no files to check)
@@ -242,6 +254,9 @@ fn test_check_command_jsonl_format() {
         ratchets::cli::OutputFormat::Jsonl,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should pass because we have 1 TODO and budget is 2
@@ -329,6 +344,9 @@ pattern = "TODO"
         ratchets::cli::OutputFormat::Human,
         true, // verbose = true
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should succeed - we have 1 TODO and budget is 10
@@ -353,6 +371,9 @@ fn test_check_verbose_short_flag_behavior() {
         ratchets::cli::OutputFormat::Human,
         true, // verbose = true (equivalent to -v)
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should pass because we have 1 TODO and budget is 2
@@ -423,6 +444,9 @@ pattern = "TODO"
         ratchets::cli::OutputFormat::Jsonl,
         true, // verbose = true
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should succeed
@@ -505,6 +529,9 @@ pattern = "TODO"
         ratchets::cli::OutputFormat::Human,
         false, // verbose = false
         None,
+        false,
+        false,
+        &[],
     );
 
     // Should pass because we have 2 TODOs and budget is 10
@@ -624,6 +651,9 @@ fn test_check_since_filters_to_changed_files_only() {
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
     assert_eq!(
         exit_code,
@@ -640,6 +670,9 @@ fn test_check_since_filters_to_changed_files_only() {
         ratchets::cli::OutputFormat::Human,
         false,
         Some(&baseline_sha),
+        false,
+        false,
+        &[],
     );
     assert_eq!(
         exit_code,
@@ -664,6 +697,9 @@ fn test_check_since_bad_ref_returns_error() {
         ratchets::cli::OutputFormat::Human,
         false,
         Some("this-ref-does-not-exist"),
+        false,
+        false,
+        &[],
     );
     assert_eq!(exit_code, ratchets::cli::common::EXIT_ERROR);
 
@@ -754,6 +790,9 @@ include = ["example_app/frontend/src/**/*.tsx"]
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
     assert_eq!(
         exit_code_dot,
@@ -768,6 +807,9 @@ include = ["example_app/frontend/src/**/*.tsx"]
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
     assert_eq!(
         exit_code_sub,
@@ -778,6 +820,254 @@ include = ["example_app/frontend/src/**/*.tsx"]
     std::env::set_current_dir(original_dir).unwrap();
 }
 
+#[cfg(feature = "lang-typescript")]
+#[test]
+#[serial]
+fn test_check_tsx_files_use_tsx_grammar() {
+    // `.tsx` files parse as TSX, so a JSX query in a `tsx` AST rule matches
+    // them, and configuring "typescript" keeps the rule since TSX is its dialect.
+    let temp_dir = TempDir::new().unwrap();
+    let config = r#"
+enabled_ratchets = ["no-raw-buttons"]
+
+[ratchets]
+version = "2"
+languages = ["typescript"]
+"#;
+    fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+
+    let ast_dir = temp_dir.path().join("ratchets").join("ast");
+    fs::create_dir_all(&ast_dir).unwrap();
+    let rule_toml = r#"
+[rule]
+id = "no-raw-buttons"
+description = "Use the Button component instead of <button>"
+severity = "warning"
+
+[match]
+language = "tsx"
+query = """
+(jsx_opening_element
+  name: (identifier) @name
+  (#eq? @name "button")) @violation
+"""
+"#;
+    fs::write(ast_dir.join("no-raw-buttons.toml"), rule_toml).unwrap();
+
+    let src_dir = temp_dir.path().join("src");
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::write(
+        src_dir.join("App.tsx"),
+        "export const App = (props: { label: string }) => <button>{props.label}</button>;\n",
+    )
+    .unwrap();
+    fs::write(src_dir.join("util.ts"), "export const button = 1;\n").unwrap();
+
+    let counts = r#"
+[no-raw-buttons]
+"." = 1
+"#;
+    fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(temp_dir.path()).unwrap();
+
+    let run_check = || {
+        ratchets::cli::check::run_check(
+            &[".".to_string()],
+            ratchets::cli::OutputFormat::Jsonl,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        )
+    };
+    assert_eq!(run_check(), ratchets::cli::common::EXIT_SUCCESS);
+
+    fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+    assert_eq!(run_check(), ratchets::cli::common::EXIT_EXCEEDED);
+
+    std::env::set_current_dir(original_dir).unwrap();
+}
+
+#[cfg(feature = "lang-cpp")]
+#[test]
+#[serial]
+fn test_check_header_language_selects_header_grammar() {
+    // `.h` headers are C by default; `header_language = "cpp"` makes C++
+    // rules check them.
+    let temp_dir = TempDir::new().unwrap();
+    let config = |header_language: &str| {
+        format!(
+            r#"
+enabled_ratchets = ["cpp-no-c-style-casts"]
+
+[ratchets]
+version = "2"
+languages = ["c", "cpp"]
+{}
+"#,
+            header_language
+        )
+    };
+    fs::write(temp_dir.path().join("ratchets.toml"), config("")).unwrap();
+
+    let src_dir = temp_dir.path().join("src");
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::write(
+        src_dir.join("ratio.h"),
+        "inline int percent(double ratio) { return (int)(ratio * 100); }\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(temp_dir.path()).unwrap();
+
+    let run_check = || {
+        ratchets::cli::check::run_check(
+            &[".".to_string()],
+            ratchets::cli::OutputFormat::Jsonl,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        )
+    };
+    assert_eq!(run_check(), ratchets::cli::common::EXIT_SUCCESS);
+
+    fs::write(
+        temp_dir.path().join("ratchets.toml"),
+        config("header_language = \"cpp\""),
+    )
+    .unwrap();
+    assert_eq!(run_check(), ratchets::cli::common::EXIT_EXCEEDED);
+
+    std::env::set_current_dir(original_dir).unwrap();
+}
+
+#[cfg(all(feature = "lang-bash", feature = "lang-python"))]
+#[test]
+#[serial]
+fn test_check_detects_extensionless_scripts_by_shebang() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = r#"
+enabled_ratchets = ["bash-no-unquoted-rm-expansion", "no-bare-print"]
+
+[ratchets]
+version = "2"
+languages = ["bash", "python"]
+"#;
+    fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+
+    let bin_dir = temp_dir.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    fs::write(
+        bin_dir.join("clean"),
+        "#!/usr/bin/env bash\nset -euo pipefail\nrm -rf $BUILD_DIR/cache\n",
+    )
+    .unwrap();
+    fs::write(
+        bin_dir.join("report"),
+        "#!/usr/bin/env python3\nprint(\"done\")\n",
+    )
+    .unwrap();
+    fs::write(bin_dir.join("README"), "print(\"not a script\")\n").unwrap();
+
+    let counts = r#"
+[bash-no-unquoted-rm-expansion]
+"." = 1
+
+[no-bare-print]
+"." = 1
+"#;
+    fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(temp_dir.path()).unwrap();
+
+    let run_check = || {
+        ratchets::cli::check::run_check(
+            &[".".to_string()],
+            ratchets::cli::OutputFormat::Jsonl,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        )
+    };
+    assert_eq!(run_check(), ratchets::cli::common::EXIT_SUCCESS);
+
+    // Each script's violation counts, so dropping either budget exceeds it
+    for budgeted in ["no-bare-print", "bash-no-unquoted-rm-expansion"] {
+        fs::write(
+            temp_dir.path().join("ratchet-counts.toml"),
+            format!("[{}]\n\".\" = 1\n", budgeted),
+        )
+        .unwrap();
+        assert_eq!(run_check(), ratchets::cli::common::EXIT_EXCEEDED);
+    }
+
+    std::env::set_current_dir(original_dir).unwrap();
+}
+
+#[cfg(feature = "lang-python")]
+#[test]
+#[serial]
+fn test_check_languages_map_assigns_languages() {
+    // `.bzl` files are unknown until `[languages.map]` maps them to Python
+    let temp_dir = TempDir::new().unwrap();
+    let config = |map: &str| {
+        format!(
+            r#"
+enabled_ratchets = ["no-bare-print"]
+
+[ratchets]
+version = "2"
+languages = ["python"]
+
+[languages.map]
+{}
+"#,
+            map
+        )
+    };
+    fs::write(temp_dir.path().join("ratchets.toml"), config("")).unwrap();
+
+    let tools_dir = temp_dir.path().join("tools");
+    fs::create_dir_all(&tools_dir).unwrap();
+    fs::write(tools_dir.join("defs.bzl"), "print(\"loading defs\")\n").unwrap();
+    fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(temp_dir.path()).unwrap();
+
+    let run_check = || {
+        ratchets::cli::check::run_check(
+            &[".".to_string()],
+            ratchets::cli::OutputFormat::Jsonl,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        )
+    };
+    assert_eq!(run_check(), ratchets::cli::common::EXIT_SUCCESS);
+
+    fs::write(
+        temp_dir.path().join("ratchets.toml"),
+        config("\"*.bzl\" = \"python\""),
+    )
+    .unwrap();
+    assert_eq!(run_check(), ratchets::cli::common::EXIT_EXCEEDED);
+
+    std::env::set_current_dir(original_dir).unwrap();
+}
+
 #[test]
 #[serial]
 fn test_check_since_outside_git_repo_returns_error() {
@@ -794,6 +1084,9 @@ fn test_check_since_outside_git_repo_returns_error() {
         ratchets::cli::OutputFormat::Human,
         false,
         Some("main"),
+        false,
+        false,
+        &[],
     );
     assert_eq!(exit_code, ratchets::cli::common::EXIT_ERROR);
 
@@ -896,6 +1189,9 @@ fn test_phase3_bare_rule_ids_in_enabled_ratchets_load_exactly_those_rules() {
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     std::env::set_current_dir(original_dir).unwrap();
@@ -938,6 +1234,9 @@ disabled_ratchets = ["phase3-no-todo"]
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     std::env::set_current_dir(original_dir).unwrap();
@@ -983,6 +1282,9 @@ rules = ["phase3-no-todo", "phase3-no-fixme"]
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     std::env::set_current_dir(original_dir).unwrap();
@@ -1036,6 +1338,9 @@ rules = ["$phase3-a"]
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     std::env::set_current_dir(original_dir).unwrap();
@@ -1114,6 +1419,9 @@ fn test_phase4_common_starter_finds_todo_violation_and_exits_exceeded() {
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     std::env::set_current_dir(original_dir).unwrap();
@@ -1142,6 +1450,9 @@ fn test_phase4_disabled_ratchets_overrides_common_starter_member() {
         ratchets::cli::OutputFormat::Human,
         false,
         None,
+        false,
+        false,
+        &[],
     );
 
     std::env::set_current_dir(original_dir).unwrap();
@@ -1175,6 +1486,10 @@ fn test_phase4_load_builtin_sets_returns_common_starter_only() {
         .collect();
     assert_eq!(
         member_rule_ids,
-        vec!["no-todo-comments", "no-fixme-comments"]
+        vec![
+            "no-todo-comments",
+            "no-fixme-comments",
+            "unjustified-suppression"
+        ]
     );
 }
@@ -2,15 +2,21 @@
 //!
 //! These tests verify all CLI commands and their behavior, including:
 //! - init: Creates files, --force behavior
-//! - check: Exit codes, output formats
+//! - check: Exit codes, output formats, suppression comments, import rules,
+//!   imported linter reports
 //! - bump: Updates counts, auto-detect
 //! - tighten: Reduces counts, fails on exceeded
 //! - list: Output formats
+//! - watch: Incremental re-checks and reloads
+//! - fix: Applies [fix] sections, --dry-run leaves files unchanged
+//! - test-rules: Checks rules against their [[test]] examples
+//! - explain: Output formats, disabled and unknown rules
 //! - merge-driver: Minimum wins
 //!
 //! NOTE: These tests change the current directory and use std::sync::Mutex
 //! to ensure they don't interfere with each other.
 
+use ratchets::adapters::ReportImport;
 use ratchets::cli;
 use std::fs;
 use std::path::Path;
@@ -90,6 +96,8 @@ fn test_init_creates_all_files() {
         assert!(result.created.contains(&"ratchet-counts.toml".to_string()));
         assert!(result.created.contains(&"ratchets/regex/".to_string()));
         assert!(result.created.contains(&"ratchets/ast/".to_string()));
+        assert!(result.created.contains(&"ratchets/imports/".to_string()));
+        assert!(result.created.contains(&"ratchets/files/".to_string()));
         assert!(result.skipped.is_empty());
         assert!(result.overwritten.is_empty());
 
@@ -150,7 +158,7 @@ fn test_init_is_idempotent() {
     with_temp_dir(|_temp_dir| {
         // First init
         let result1 = cli::init::run_init(false).expect("first init should succeed");
-        assert_eq!(result1.created.len(), 4); // 2 files + 2 directories
+        assert_eq!(result1.created.len(), 6); // 2 files + 4 directories
 
         // Second init should skip files
         let result2 = cli::init::run_init(false).expect("second init should succeed");
@@ -253,8 +261,15 @@ fn test_check_returns_success_when_within_budget() {
     with_temp_dir(|temp_dir| {
         setup_basic_project(temp_dir.path());
 
-        let exit_code =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
 
         // Should pass: 1 TODO with budget of 5
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
@@ -273,8 +288,15 @@ fn test_check_returns_exceeded_when_over_budget() {
 "#;
         fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
 
-        let exit_code =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
 
         // Should fail: 1 TODO with budget of 0
         assert_eq!(exit_code, cli::common::EXIT_EXCEEDED);
@@ -286,8 +308,15 @@ fn test_check_returns_error_when_config_missing() {
     with_temp_dir(|_temp_dir| {
         // Don't create any config files
 
-        let exit_code =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
 
         // Should return error
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -299,8 +328,15 @@ fn test_check_jsonl_format_returns_success() {
     with_temp_dir(|temp_dir| {
         setup_basic_project(temp_dir.path());
 
-        let exit_code =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Jsonl, false, None);
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Jsonl,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
 
         // Should pass with JSONL format
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
@@ -325,6 +361,9 @@ fn test_check_with_multiple_paths() {
             cli::OutputFormat::Human,
             false,
             None,
+            false,
+            false,
+            &[],
         );
 
         // Should still be within budget (2 TODOs, budget 5)
@@ -348,8 +387,15 @@ include = ["**/*.rs"]
 "#;
         fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
 
-        let exit_code =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
 
         // Should succeed with warning (no files to check)
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
@@ -362,7 +408,15 @@ fn test_bump_with_explicit_count() {
         setup_basic_project(temp_dir.path());
 
         // Bump to explicit count
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), ".", Some(10), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            Some(10),
+            false,
+            false,
+            false,
+            false,
+        );
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -379,7 +433,15 @@ fn test_bump_with_auto_detect() {
         setup_basic_project(temp_dir.path());
 
         // Bump with auto-detect (should set to current count of 1)
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), ".", None, false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            None,
+            false,
+            false,
+            false,
+            false,
+        );
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -397,7 +459,15 @@ fn test_bump_rejects_count_below_current() {
         setup_basic_project(temp_dir.path());
 
         // Try to bump to 0 (below current count of 1)
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), ".", Some(0), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            Some(0),
+            false,
+            false,
+            false,
+            false,
+        );
 
         // Should fail
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -427,7 +497,15 @@ fn test_bump_with_custom_region() {
         fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
 
         // Now bump the src region
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), "src", Some(5), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            "src",
+            Some(5),
+            false,
+            false,
+            false,
+            false,
+        );
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -445,7 +523,15 @@ fn test_bump_invalid_rule_id() {
         setup_basic_project(temp_dir.path());
 
         // Try to bump non-existent rule
-        let exit_code = cli::bump::run_bump(Some("nonexistent-rule"), ".", Some(10), false);
+        let exit_code = cli::bump::run_bump(
+            Some("nonexistent-rule"),
+            ".",
+            Some(10),
+            false,
+            false,
+            false,
+            false,
+        );
 
         // Should fail
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -457,7 +543,15 @@ fn test_bump_missing_config() {
     with_temp_dir(|_temp_dir| {
         // Don't create config
 
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), ".", Some(10), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            Some(10),
+            false,
+            false,
+            false,
+            false,
+        );
 
         // Should fail
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -481,7 +575,15 @@ fn test_bump_fails_for_unconfigured_region() {
         // for no-todo-comments. The "src" region is NOT configured.
 
         // Try to bump an unconfigured region - should fail
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), "src", Some(5), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            "src",
+            Some(5),
+            false,
+            false,
+            false,
+            false,
+        );
 
         // Should fail because "src" is not configured for this rule
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -508,7 +610,15 @@ fn test_bump_succeeds_for_root_region_even_when_not_explicitly_configured() {
         fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
 
         // Bumping the root region "." should always succeed
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), ".", Some(10), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            Some(10),
+            false,
+            false,
+            false,
+            false,
+        );
 
         // Should succeed because "." is always implicitly configured
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
@@ -545,7 +655,15 @@ fn test_bump_succeeds_for_explicitly_configured_region() {
         fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
 
         // Now bumping "src" should succeed
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), "src", Some(10), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            "src",
+            Some(10),
+            false,
+            false,
+            false,
+            false,
+        );
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -569,7 +687,7 @@ fn test_tighten_reduces_budget_to_current() {
         setup_basic_project(temp_dir.path());
 
         // Current count is 1, budget is 5 - should tighten to 1
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -587,7 +705,7 @@ fn test_tighten_specific_rule() {
     with_temp_dir(|temp_dir| {
         setup_basic_project(temp_dir.path());
 
-        let exit_code = cli::tighten::run_tighten(Some("no-todo-comments"), None);
+        let exit_code = cli::tighten::run_tighten(Some("no-todo-comments"), None, false, false);
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
     });
@@ -598,7 +716,7 @@ fn test_tighten_specific_region() {
     with_temp_dir(|temp_dir| {
         setup_basic_project(temp_dir.path());
 
-        let exit_code = cli::tighten::run_tighten(None, Some("."));
+        let exit_code = cli::tighten::run_tighten(None, Some("."), false, false);
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
     });
@@ -616,7 +734,7 @@ fn test_tighten_fails_when_violations_exceed_budget() {
 "#;
         fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
 
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
 
         // Should fail because violations exceed budget
         assert_eq!(exit_code, cli::common::EXIT_EXCEEDED);
@@ -629,11 +747,11 @@ fn test_tighten_no_changes_needed() {
         setup_basic_project(temp_dir.path());
 
         // First tighten to current
-        let exit_code1 = cli::tighten::run_tighten(None, None);
+        let exit_code1 = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code1, cli::common::EXIT_SUCCESS);
 
         // Second tighten should have no changes
-        let exit_code2 = cli::tighten::run_tighten(None, None);
+        let exit_code2 = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code2, cli::common::EXIT_SUCCESS);
     });
 }
@@ -643,7 +761,7 @@ fn test_tighten_invalid_rule_id() {
     with_temp_dir(|temp_dir| {
         setup_basic_project(temp_dir.path());
 
-        let exit_code = cli::tighten::run_tighten(Some("invalid rule!"), None);
+        let exit_code = cli::tighten::run_tighten(Some("invalid rule!"), None, false, false);
 
         // Should fail with invalid rule ID
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -653,7 +771,7 @@ fn test_tighten_invalid_rule_id() {
 #[test]
 fn test_tighten_missing_config() {
     with_temp_dir(|_temp_dir| {
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
 
         // Should fail
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -682,7 +800,7 @@ fn test_tighten_warns_on_orphan_count_and_tightens_surviving_rule() {
 "#;
         fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
 
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         // Surviving rule tightens from 5 to 1 (single TODO in test.rs).
@@ -763,150 +881,1306 @@ fn test_list_missing_config() {
 }
 
 #[test]
-fn test_merge_driver_minimum_wins() {
+fn test_check_writes_cache_and_cache_clean_removes_it() {
     with_temp_dir(|temp_dir| {
-        // Create three versions with different counts
-        let base = r#"
-[no-todo-comments]
-"." = 20
-"#;
-        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+        setup_basic_project(temp_dir.path());
+        let cache_dir = temp_dir.path().join(".ratchets-cache");
 
-        let ours = r#"
-[no-todo-comments]
-"." = 15
-"#;
-        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        assert!(cache_dir.join("results.json").exists());
+        assert!(cache_dir.join(".gitignore").exists());
 
-        let theirs = r#"
-[no-todo-comments]
-"." = 18
-"#;
-        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
+        assert_eq!(cli::cache::run_cache_clean(), cli::common::EXIT_SUCCESS);
+        assert!(!cache_dir.exists());
 
-        let exit_code =
-            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
+        // Cleaning again is not an error
+        assert_eq!(cli::cache::run_cache_clean(), cli::common::EXIT_SUCCESS);
+    });
+}
 
-        assert_eq!(exit_code, 0); // EXIT_SUCCESS
+#[test]
+fn test_check_no_cache_does_not_write_cache() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
 
-        // Verify merged result has minimum (15)
-        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
-        assert!(merged.contains("15"));
-        assert!(!merged.contains("18"));
-        assert!(!merged.contains("20"));
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            true,
+            false,
+            &[],
+        );
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        assert!(!temp_dir.path().join(".ratchets-cache").exists());
     });
 }
 
 #[test]
-fn test_merge_driver_new_rule_in_ours() {
+fn test_cached_check_sees_edited_files() {
     with_temp_dir(|temp_dir| {
-        let base = "";
-        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
-
-        let ours = r#"
+        setup_basic_project(temp_dir.path());
+        let counts = r#"
 [no-todo-comments]
-"." = 10
+"." = 1
 "#;
-        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
-
-        let theirs = "";
-        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
-
-        let exit_code =
-            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
 
-        assert_eq!(exit_code, 0);
+        let run = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Human,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+        assert_eq!(run(), cli::common::EXIT_SUCCESS);
+
+        // A second TODO exceeds the budget even though the file was cached
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// TODO: test\n// TODO: again\nfn main() {}\n",
+        )
+        .unwrap();
+        assert_eq!(run(), cli::common::EXIT_EXCEEDED);
 
-        // Verify new rule is preserved
-        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
-        assert!(merged.contains("no-todo-comments"));
-        assert!(merged.contains("10"));
+        // Raising the budget is honored for cached results
+        let counts = r#"
+[no-todo-comments]
+"." = 2
+"#;
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run(), cli::common::EXIT_SUCCESS);
     });
 }
 
 #[test]
-fn test_merge_driver_new_rule_in_theirs() {
+fn test_check_count_only_enforces_budgets() {
     with_temp_dir(|temp_dir| {
-        let base = "";
-        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+        setup_basic_project(temp_dir.path());
 
-        let ours = "";
-        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
+        let run = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Human,
+                false,
+                None,
+                false,
+                true,
+                &[],
+            )
+        };
+        assert_eq!(run(), cli::common::EXIT_SUCCESS);
+        // Counting only caches the counts, without snippets or messages
+        let results =
+            fs::read_to_string(temp_dir.path().join(".ratchets-cache/results.json")).unwrap();
+        assert!(results.contains("\"count\""));
+        assert!(!results.contains("snippet"));
+        assert_eq!(run(), cli::common::EXIT_SUCCESS);
 
-        let theirs = r#"
-[no-unwrap]
-"." = 5
+        let counts = r#"
+[no-todo-comments]
+"." = 0
 "#;
-        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run(), cli::common::EXIT_EXCEEDED);
+    });
+}
 
-        let exit_code =
-            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
+#[test]
+fn test_tighten_count_only() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
 
-        assert_eq!(exit_code, 0);
+        let exit_code = cli::tighten::run_tighten(None, None, false, true);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
-        // Verify new rule from theirs is merged
-        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
-        assert!(merged.contains("no-unwrap"));
-        assert!(merged.contains("5"));
+        let counts_content =
+            fs::read_to_string(temp_dir.path().join("ratchet-counts.toml")).unwrap();
+        assert!(counts_content.contains("\".\" = 1"));
     });
 }
 
 #[test]
-fn test_merge_driver_multiple_rules() {
+fn test_watch_rechecks_changed_files_and_reloads_config() {
+    use cli::watch::{WatchEvent, Watcher};
+
     with_temp_dir(|temp_dir| {
-        let base = r#"
-[no-unwrap]
-"." = 20
-[no-todo-comments]
-"." = 30
-"#;
-        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+        setup_basic_project(temp_dir.path());
+        let todo_status = |watcher: &Watcher| {
+            let result = watcher.aggregate();
+            assert!(result.statuses.len() <= 1);
+            result
+                .statuses
+                .first()
+                .map(|s| (s.actual_count, s.budget))
+                .unwrap_or((0, 0))
+        };
+
+        let mut watcher = Watcher::new(&[".".to_string()]).unwrap();
+        assert_eq!(watcher.file_count(), 1);
+        assert_eq!(todo_status(&watcher), (1, 5));
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Unchanged);
+
+        // Editing and adding source files re-checks only those files
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// TODO: test\n// TODO: again\nfn main() {}\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("other.rs"), "// TODO: other\n").unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::FilesChanged(2));
+        assert_eq!(watcher.file_count(), 2);
+        assert_eq!(todo_status(&watcher), (3, 5));
 
-        let ours = r#"
-[no-unwrap]
-"." = 15
-[no-todo-comments]
-"." = 30
-"#;
-        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
+        // Removing a file drops its violations
+        fs::remove_file(temp_dir.path().join("other.rs")).unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::FilesChanged(1));
+        assert_eq!(todo_status(&watcher), (2, 5));
 
-        let theirs = r#"
-[no-unwrap]
-"." = 18
+        // Budget and rule edits reload everything
+        let counts = r#"
 [no-todo-comments]
-"." = 25
+"." = 1
 "#;
-        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
-
-        let exit_code =
-            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
-
-        assert_eq!(exit_code, 0);
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Reloaded);
+        assert_eq!(todo_status(&watcher), (2, 1));
+        assert!(!watcher.aggregate().passed);
 
-        // Verify minimum for each rule
-        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
-        assert!(merged.contains("no-unwrap"));
-        assert!(merged.contains("no-todo-comments"));
-        // no-unwrap: min(15, 18) = 15
-        // no-todo-comments: min(30, 25) = 25
-        // Check that both values are in the file
-        let parsed = ratchets::config::counts::CountsManager::parse(&merged).unwrap();
-        let no_unwrap = ratchets::types::RuleId::new("no-unwrap").unwrap();
-        let no_todo = ratchets::types::RuleId::new("no-todo-comments").unwrap();
-        assert_eq!(parsed.get_budget(&no_unwrap, Path::new(".")), 15);
-        assert_eq!(parsed.get_budget(&no_todo, Path::new(".")), 25);
+        fs::create_dir_all(temp_dir.path().join("ratchets/regex")).unwrap();
+        fs::write(
+            temp_dir.path().join("ratchets/regex/no-fixme.toml"),
+            "[rule]\nid = \"no-fixme\"\ndescription = \"No FIXME\"\nseverity = \"warning\"\n\n[match]\npattern = \"FIXME\"\n",
+        )
+        .unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Reloaded);
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Unchanged);
     });
 }
 
 #[test]
-fn test_merge_driver_multiple_regions() {
+fn test_watch_reloads_custom_rules_and_sets() {
+    use cli::watch::{WatchEvent, Watcher};
+    use ratchets::types::{RegionPath, RuleId};
+
     with_temp_dir(|temp_dir| {
-        let base = r#"
-[no-todo-comments]
-"." = 20
-"src" = 15
-"#;
-        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+        setup_basic_project(temp_dir.path());
+        let config_path = temp_dir.path().join("ratchets.toml");
+        let config = fs::read_to_string(&config_path).unwrap().replace(
+            "enabled_ratchets = [\"no-todo-comments\"]",
+            "enabled_ratchets = [\"$team\"]",
+        );
+        fs::write(config_path, config).unwrap();
+
+        let sets_dir = temp_dir.path().join("ratchets/sets");
+        fs::create_dir_all(&sets_dir).unwrap();
+        let set = "[set]\nid = \"team\"\ndescription = \"Team rules\"\n\nrules = [\"no-todo-comments\"]\n";
+        fs::write(sets_dir.join("team.toml"), set).unwrap();
+        let regex_dir = temp_dir.path().join("ratchets/regex");
+        fs::create_dir_all(&regex_dir).unwrap();
+        let rule = "[rule]\nid = \"no-hack\"\ndescription = \"No HACK\"\nseverity = \"warning\"\n\n[match]\npattern = \"HACK\"\n";
+        fs::write(regex_dir.join("no-hack.toml"), rule).unwrap();
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// HACK: first\n// HACK: second\nfn main() {}\n",
+        )
+        .unwrap();
+
+        let root = RegionPath::new(".");
+        let no_hack = RuleId::new("no-hack").unwrap();
+        let mut watcher = Watcher::new(&[".".to_string()]).unwrap();
+        assert!(watcher.registry().get_rule(&no_hack).is_none());
+
+        // Adding a rule to a user set enables it
+        fs::write(
+            sets_dir.join("team.toml"),
+            set.replace(
+                "[\"no-todo-comments\"]",
+                "[\"no-todo-comments\", \"no-hack\"]",
+            ),
+        )
+        .unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Reloaded);
+        assert_eq!(watcher.region_count(&no_hack, &root), 2);
+
+        // Editing the custom rule re-checks with its new definition
+        fs::write(
+            regex_dir.join("no-hack.toml"),
+            rule.replace("HACK\"", "HACK: second\""),
+        )
+        .unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Reloaded);
+        assert_eq!(watcher.region_count(&no_hack, &root), 1);
+    });
+}
+
+#[test]
+fn test_watch_agrees_with_check_on_suppressed_budgets() {
+    use cli::watch::{WatchEvent, Watcher};
+
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+        let counts = r#"
+[no-todo-comments]
+"." = 5
+
+[no-todo-comments.suppressed]
+"." = 0
+"#;
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        let run_check = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+
+        let mut watcher = Watcher::new(&[".".to_string()]).unwrap();
+        assert!(watcher.aggregate().passed);
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        // A suppression over its budget fails both commands
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// TODO: test // ratchets-ignore[no-todo-comments]: upstream\nfn main() {}\n",
+        )
+        .unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::FilesChanged(1));
+        let result = watcher.aggregate();
+        assert!(!result.passed);
+        assert_eq!(result.total_violations, 0);
+        assert_eq!(result.suppressed.len(), 1);
+        assert_eq!(result.suppressed[0].count, 1);
+        assert_eq!(run_check(), cli::common::EXIT_EXCEEDED);
+    });
+}
+
+#[test]
+fn test_watch_keeps_state_when_reload_fails() {
+    use cli::watch::{WatchEvent, Watcher};
+
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+        let mut watcher = Watcher::new(&[".".to_string()]).unwrap();
+
+        let config_path = temp_dir.path().join("ratchets.toml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(&config_path, "not valid toml [").unwrap();
+        assert!(watcher.poll().is_err());
+        assert_eq!(watcher.aggregate().total_violations, 1);
+
+        // The broken config is not retried until it changes again
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Unchanged);
+
+        fs::write(&config_path, config).unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Reloaded);
+    });
+}
+
+#[test]
+fn test_watch_runs_batch_rules_and_rechecks_siblings() {
+    use cli::watch::{WatchEvent, Watcher};
+    use ratchets::types::{RegionPath, RuleId};
+
+    with_temp_dir(|temp_dir| {
+        let config = r#"
+enabled_ratchets = ["no-prints", "modules-have-tests"]
+
+[ratchets]
+version = "2"
+languages = ["python"]
+include = ["**/*.py"]
+"#;
+        fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+
+        fs::write(
+            temp_dir.path().join("no-prints.sh"),
+            r#"for f in "$@"; do
+  grep -n 'print(' "$f" | while IFS=: read -r line rest; do
+    printf '{"type":"violation","file":"%s","line":%s,"message":"print call"}\n' "$f" "$line"
+  done
+done
+"#,
+        )
+        .unwrap();
+        let external_dir = temp_dir.path().join("ratchets").join("external");
+        fs::create_dir_all(&external_dir).unwrap();
+        let external_rule = r#"
+[rule]
+id = "no-prints"
+description = "Use logging instead of print"
+severity = "warning"
+
+[command]
+run = ["sh", "no-prints.sh"]
+
+[match]
+languages = ["python"]
+"#;
+        fs::write(external_dir.join("no-prints.toml"), external_rule).unwrap();
+
+        let files_dir = temp_dir.path().join("ratchets").join("files");
+        fs::create_dir_all(&files_dir).unwrap();
+        fs::write(
+            files_dir.join("modules-have-tests.toml"),
+            r#"
+[rule]
+id = "modules-have-tests"
+description = "Every module has a test file"
+severity = "error"
+
+[match]
+exclude = ["**/test_*.py"]
+require_sibling = "test_{name}"
+"#,
+        )
+        .unwrap();
+
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("orders.py"), "print(1)\n").unwrap();
+
+        let root = RegionPath::new(".");
+        let prints = RuleId::new("no-prints").unwrap();
+        let missing_tests = RuleId::new("modules-have-tests").unwrap();
+
+        let mut watcher = Watcher::new(&[".".to_string()]).unwrap();
+        assert_eq!(watcher.region_count(&prints, &root), 1);
+        assert_eq!(watcher.region_count(&missing_tests, &root), 1);
+
+        // Adding the test file re-checks the unchanged module next to it
+        fs::write(src_dir.join("test_orders.py"), "a = 1\n").unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::FilesChanged(1));
+        assert_eq!(watcher.region_count(&missing_tests, &root), 0);
+
+        // Batch rules re-run after edits
+        fs::write(src_dir.join("test_orders.py"), "a = 1\nprint(a)\n").unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::FilesChanged(1));
+        assert_eq!(watcher.region_count(&prints, &root), 2);
+
+        // Removing the test file reports the module again
+        fs::remove_file(src_dir.join("test_orders.py")).unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::FilesChanged(1));
+        assert_eq!(watcher.region_count(&missing_tests, &root), 1);
+        assert_eq!(watcher.region_count(&prints, &root), 1);
+
+        // External rule edits reload everything
+        fs::write(
+            external_dir.join("no-prints.toml"),
+            external_rule.replace("warning", "error"),
+        )
+        .unwrap();
+        assert_eq!(watcher.poll().unwrap(), WatchEvent::Reloaded);
+    });
+}
+
+/// Also enable the rule reporting suppression comments without a reason
+fn enable_suppression_rule(temp_dir: &Path) {
+    let config_path = temp_dir.join("ratchets.toml");
+    let config = fs::read_to_string(&config_path).unwrap().replace(
+        "enabled_ratchets = [\"no-todo-comments\"]",
+        "enabled_ratchets = [\"no-todo-comments\", \"unjustified-suppression\"]",
+    );
+    fs::write(config_path, config).unwrap();
+}
+
+#[test]
+fn test_check_honors_suppression_comments_and_requires_reasons() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+        enable_suppression_rule(temp_dir.path());
+        let counts = r#"
+[no-todo-comments]
+"." = 0
+
+[no-todo-comments.suppressed]
+"." = 1
+"#;
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// ratchets-ignore-next-line[no-todo-comments]: tracked in the issue tracker\n// TODO: test\nfn main() {}\n",
+        )
+        .unwrap();
+
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Jsonl,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+
+        // Without a reason the suppression itself is a violation
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// ratchets-ignore-next-line[no-todo-comments]\n// TODO: test\nfn main() {}\n",
+        )
+        .unwrap();
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
+        assert_eq!(exit_code, cli::common::EXIT_EXCEEDED);
+    });
+}
+
+#[test]
+fn test_suppressed_counts_have_budgets_that_bump_and_tighten_update() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+        enable_suppression_rule(temp_dir.path());
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// TODO: a // ratchets-ignore[no-todo-comments]: upstream\n// TODO: b // ratchets-ignore[no-todo-comments]\n",
+        )
+        .unwrap();
+        let counts_path = temp_dir.path().join("ratchet-counts.toml");
+        let run_check = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+
+        // Suppressions are unlimited until the rule has a suppressed budget
+        fs::write(&counts_path, "[unjustified-suppression]\n\".\" = 1\n").unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            Some(1),
+            false,
+            false,
+            false,
+            true,
+        );
+        assert_eq!(exit_code, cli::common::EXIT_ERROR);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            None,
+            false,
+            false,
+            false,
+            true,
+        );
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        let counts = fs::read_to_string(&counts_path).unwrap();
+        assert!(
+            counts.contains("[no-todo-comments.suppressed]\n\".\" = 2\n"),
+            "{counts}"
+        );
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        // Once budgeted, a new suppression fails the check
+        fs::write(
+            temp_dir.path().join("lib.rs"),
+            "// TODO: c // ratchets-ignore[no-todo-comments]: upstream\n",
+        )
+        .unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_EXCEEDED);
+        fs::remove_file(temp_dir.path().join("lib.rs")).unwrap();
+
+        // The unjustified-suppression rule is a rule like any other
+        let exit_code =
+            cli::explain::run_explain("unjustified-suppression", cli::OutputFormat::Human);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+
+        // Removing a suppression lets tighten lower its budget
+        fs::write(
+            temp_dir.path().join("test.rs"),
+            "// TODO: a // ratchets-ignore[no-todo-comments]: upstream\n",
+        )
+        .unwrap();
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        let counts = fs::read_to_string(&counts_path).unwrap();
+        assert!(
+            counts.contains("[no-todo-comments.suppressed]\n\".\" = 1\n"),
+            "{counts}"
+        );
+        assert!(
+            counts.contains("[unjustified-suppression]\n\".\" = 0\n"),
+            "{counts}"
+        );
+    });
+}
+
+#[test]
+fn test_check_budgets_forbidden_imports_per_region() {
+    with_temp_dir(|temp_dir| {
+        let config = r#"
+enabled_ratchets = ["domain-no-infra"]
+
+[ratchets]
+version = "2"
+languages = ["typescript"]
+include = ["**/*.ts"]
+"#;
+        fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+
+        let imports_dir = temp_dir.path().join("ratchets").join("imports");
+        fs::create_dir_all(&imports_dir).unwrap();
+        let rule = r#"
+[rule]
+id = "domain-no-infra"
+description = "Domain code must not import infrastructure"
+severity = "error"
+
+[match]
+languages = ["typescript"]
+include = ["src/domain/**"]
+forbid = ["infra/**"]
+
+[[test]]
+code = "import { db } from '../infra/db';\nimport { Order } from './order';\n"
+filename = "src/domain/invoice.ts"
+lines = [1]
+"#;
+        fs::write(imports_dir.join("domain-no-infra.toml"), rule).unwrap();
+
+        let domain_dir = temp_dir.path().join("src").join("domain");
+        fs::create_dir_all(&domain_dir).unwrap();
+        fs::create_dir_all(temp_dir.path().join("src").join("infra")).unwrap();
+        fs::write(
+            domain_dir.join("order.ts"),
+            "import { db } from '../infra/db';\nimport { log } from '../shared/log';\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("src").join("infra").join("db.ts"),
+            "import { Order } from '../domain/order';\nexport const db = {};\n",
+        )
+        .unwrap();
+
+        let run_check = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+
+        // Existing layering debt is over the default budget of zero...
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_EXCEEDED);
+
+        // ...and passes once budgeted for the domain region
+        let counts = "[domain-no-infra]\n\".\" = 0\n\"src/domain\" = 1\n";
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        let exit_code = cli::test_rules::run_test_rules(Some("domain-no-infra"));
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+    });
+}
+
+#[test]
+fn test_check_budgets_file_rules_per_region() {
+    with_temp_dir(|temp_dir| {
+        let config = r#"
+enabled_ratchets = ["modules-have-tests", "short-modules"]
+
+[ratchets]
+version = "2"
+languages = ["python"]
+include = ["**/*.py"]
+"#;
+        fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+
+        let files_dir = temp_dir.path().join("ratchets").join("files");
+        fs::create_dir_all(&files_dir).unwrap();
+        let sibling_rule = r#"
+[rule]
+id = "modules-have-tests"
+description = "Every module has a test file"
+severity = "error"
+
+[match]
+languages = ["python"]
+include = ["src/**"]
+exclude = ["**/test_*.py"]
+require_sibling = "test_{name}"
+"#;
+        fs::write(files_dir.join("modules-have-tests.toml"), sibling_rule).unwrap();
+        let length_rule = r#"
+[rule]
+id = "short-modules"
+description = "Modules have at most three lines"
+severity = "warning"
+
+[match]
+max_lines = 3
+forbid_name = "^utils\\.py$"
+
+[[test]]
+code = "a = 1\nb = 2\nc = 3\nd = 4\n"
+filename = "src/utils.py"
+expect = 2
+"#;
+        fs::write(files_dir.join("short-modules.toml"), length_rule).unwrap();
+
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("orders.py"), "a = 1\n").unwrap();
+        fs::write(src_dir.join("test_orders.py"), "a = 1\n").unwrap();
+        fs::write(src_dir.join("utils.py"), "a = 1\nb = 2\nc = 3\nd = 4\n").unwrap();
+
+        let run_check = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+
+        // utils.py has no test file, is too long and has a forbidden name
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_EXCEEDED);
+
+        let counts = "[modules-have-tests]\n\"src\" = 1\n\n[short-modules]\n\"src\" = 2\n";
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        // Adding the missing test file is picked up despite the result cache
+        fs::write(src_dir.join("test_utils.py"), "").unwrap();
+        let counts = "[short-modules]\n\"src\" = 2\n";
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        let exit_code = cli::test_rules::run_test_rules(Some("short-modules"));
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+    });
+}
+
+#[test]
+fn test_check_explicit_path_finds_siblings_outside_checked_files() {
+    with_temp_dir(|temp_dir| {
+        let config = r#"
+enabled_ratchets = ["modules-have-tests"]
+
+[ratchets]
+version = "2"
+languages = ["python"]
+include = ["**/*.py"]
+"#;
+        fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+
+        let files_dir = temp_dir.path().join("ratchets").join("files");
+        fs::create_dir_all(&files_dir).unwrap();
+        let sibling_rule = r#"
+[rule]
+id = "modules-have-tests"
+description = "Every module has a test file"
+severity = "error"
+
+[match]
+exclude = ["**/test_*.py"]
+require_sibling = "test_{name}"
+"#;
+        fs::write(files_dir.join("modules-have-tests.toml"), sibling_rule).unwrap();
+
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("foo.py"), "a = 1\n").unwrap();
+        fs::write(src_dir.join("test_foo.py"), "a = 1\n").unwrap();
+
+        let run_check = |path: &str| {
+            cli::check::run_check(
+                &[path.to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+
+        // The test file is found although only the module is checked
+        assert_eq!(run_check("."), cli::common::EXIT_SUCCESS);
+        assert_eq!(run_check("src/foo.py"), cli::common::EXIT_SUCCESS);
+
+        fs::remove_file(src_dir.join("test_foo.py")).unwrap();
+        assert_eq!(run_check("src/foo.py"), cli::common::EXIT_EXCEEDED);
+    });
+}
+
+#[test]
+fn test_check_budgets_rule_template_instances() {
+    with_temp_dir(|temp_dir| {
+        let config = r#"
+enabled_ratchets = ["no-pickle", "no-legacy-client"]
+
+[ratchets]
+version = "2"
+languages = ["python"]
+include = ["**/*.py"]
+
+[[rules.instances]]
+template = "python-no-module-import"
+id = "no-pickle"
+params = { module = "pickle" }
+
+[[rules.instances]]
+template = "no-call"
+id = "no-legacy-client"
+description = "Use the new client"
+params = { func = "legacy_client" }
+"#;
+        fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+
+        let templates_dir = temp_dir.path().join("ratchets").join("templates");
+        fs::create_dir_all(&templates_dir).unwrap();
+        let template = r#"
+[template]
+id = "no-call"
+kind = "regex"
+params = ["func"]
+
+[rule]
+description = "Do not call {{func}}"
+severity = "warning"
+
+[match]
+pattern = "\\b{{func}}\\("
+languages = ["python"]
+
+[[test]]
+code = "{{func}}()\n"
+expect = 1
+"#;
+        fs::write(templates_dir.join("no-call.toml"), template).unwrap();
+
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(
+            src_dir.join("app.py"),
+            "import pickle\nclient = legacy_client()\n",
+        )
+        .unwrap();
+
+        let run_check = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_EXCEEDED);
+
+        // Each instance has its own budget
+        let counts = "[no-pickle]\n\"src\" = 1\n\n[no-legacy-client]\n\"src\" = 1\n";
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        let exit_code = cli::test_rules::run_test_rules(None);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn test_check_budgets_external_rule_violations() {
+    with_temp_dir(|temp_dir| {
+        let config = r#"
+enabled_ratchets = ["no-prints"]
+
+[ratchets]
+version = "2"
+languages = ["python"]
+include = ["**/*.py"]
+"#;
+        fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+
+        let tools_dir = temp_dir.path().join("tools");
+        fs::create_dir_all(&tools_dir).unwrap();
+        let script = r#"for f in "$@"; do
+  grep -n 'print(' "$f" | while IFS=: read -r line rest; do
+    printf '{"type":"violation","file":"%s","line":%s,"message":"print call"}\n' "$f" "$line"
+  done
+done
+"#;
+        fs::write(tools_dir.join("no-prints.sh"), script).unwrap();
+
+        let external_dir = temp_dir.path().join("ratchets").join("external");
+        fs::create_dir_all(&external_dir).unwrap();
+        let rule = r#"
+[rule]
+id = "no-prints"
+description = "Use logging instead of print"
+severity = "warning"
+
+[command]
+run = ["sh", "tools/no-prints.sh"]
+
+[match]
+languages = ["python"]
+include = ["src/**"]
+"#;
+        fs::write(external_dir.join("no-prints.toml"), rule).unwrap();
+
+        let legacy_dir = temp_dir.path().join("src").join("legacy");
+        fs::create_dir_all(&legacy_dir).unwrap();
+        fs::write(legacy_dir.join("a.py"), "print(1)\nprint(2)\n").unwrap();
+        fs::write(
+            temp_dir.path().join("src").join("b.py"),
+            "print(3)  # ratchets-ignore[no-prints]: debug entry point\n",
+        )
+        .unwrap();
+
+        let run_check = || {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                &[],
+            )
+        };
+
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_EXCEEDED);
+
+        // Violations are budgeted per configured region; suppressed ones separately
+        let counts = "[no-prints]\n\"src/legacy\" = 3\n\n[no-prints.suppressed]\n\".\" = 1\n";
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_SUCCESS);
+
+        let exit_code = cli::tighten::run_tighten(Some("no-prints"), None, false, false);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        let counts = fs::read_to_string(temp_dir.path().join("ratchet-counts.toml")).unwrap();
+        assert!(counts.contains("\"src/legacy\" = 2"));
+
+        // A failing command is an error, not a pass
+        fs::write(tools_dir.join("no-prints.sh"), "exit 1\n").unwrap();
+        assert_eq!(run_check(), cli::common::EXIT_ERROR);
+    });
+}
+
+#[test]
+fn test_check_budgets_imported_report_violations() {
+    with_temp_dir(|temp_dir| {
+        let config = r#"
+enabled_ratchets = []
+
+[ratchets]
+version = "2"
+languages = ["python"]
+include = ["**/*.py"]
+"#;
+        fs::write(temp_dir.path().join("ratchets.toml"), config).unwrap();
+
+        let legacy_dir = temp_dir.path().join("src").join("legacy");
+        fs::create_dir_all(&legacy_dir).unwrap();
+        fs::write(temp_dir.path().join("src").join("app.py"), "x = 1\n").unwrap();
+        fs::write(legacy_dir.join("old.py"), "import os\n").unwrap();
+
+        let report = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("reports")
+            .join("ruff.json");
+        fs::copy(report, temp_dir.path().join("ruff.json")).unwrap();
+        let imports = vec!["ruff=ruff.json".parse().unwrap()];
+
+        let run_check = |imports: &[ReportImport]| {
+            cli::check::run_check(
+                &[".".to_string()],
+                cli::OutputFormat::Jsonl,
+                false,
+                None,
+                false,
+                false,
+                imports,
+            )
+        };
+
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
+        assert_eq!(run_check(&[]), cli::common::EXIT_SUCCESS);
+        assert_eq!(run_check(&imports), cli::common::EXIT_EXCEEDED);
+
+        // Each ruff code is its own rule, budgeted per configured region
+        let counts = r#"
+[ruff-E501]
+"." = 1
+"src/legacy" = 1
+
+[ruff-F401]
+"src/legacy" = 1
+
+[ruff-syntax-error]
+"src/legacy" = 1
+"#;
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run_check(&imports), cli::common::EXIT_SUCCESS);
+
+        let counts = counts.replace(
+            "\"src/legacy\" = 1\n\n[ruff-F401]",
+            "\"src/legacy\" = 0\n\n[ruff-F401]",
+        );
+        fs::write(temp_dir.path().join("ratchet-counts.toml"), counts).unwrap();
+        assert_eq!(run_check(&imports), cli::common::EXIT_EXCEEDED);
+
+        // A missing report is an error, not a pass
+        let missing = vec!["ruff=missing.json".parse().unwrap()];
+        assert_eq!(run_check(&missing), cli::common::EXIT_ERROR);
+    });
+}
+
+/// Add a `[fix]` section to the basic project's rule
+fn add_todo_fix(temp_dir: &Path) {
+    let rule_path = temp_dir
+        .join("builtin-ratchets")
+        .join("common")
+        .join("regex")
+        .join("no-todo-comments.toml");
+    let mut rule = fs::read_to_string(&rule_path).unwrap();
+    rule.push_str("\n[fix]\nreplacement = \"NOTE\"\n");
+    fs::write(&rule_path, rule).unwrap();
+}
+
+#[test]
+fn test_fix_dry_run_leaves_files_unchanged() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+        add_todo_fix(temp_dir.path());
+
+        let exit_code = cli::fix::run_fix(None, &[], true);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+
+        let content = fs::read_to_string(temp_dir.path().join("test.rs")).unwrap();
+        assert_eq!(content, "// TODO: test\nfn main() {}\n");
+    });
+}
+
+#[test]
+fn test_fix_applies_edits_then_tighten_locks_them_in() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+        add_todo_fix(temp_dir.path());
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("src").join("lib.rs"),
+            "// TODO one TODO two\n",
+        )
+        .unwrap();
+
+        // A first argument that is a path rather than a rule fixes only that path
+        let exit_code = cli::fix::run_fix(Some("src"), &[], false);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        let lib = fs::read_to_string(temp_dir.path().join("src").join("lib.rs")).unwrap();
+        assert_eq!(lib, "// NOTE one NOTE two\n");
+        let main = fs::read_to_string(temp_dir.path().join("test.rs")).unwrap();
+        assert_eq!(main, "// TODO: test\nfn main() {}\n");
+
+        let exit_code = cli::fix::run_fix(Some("no-todo-comments"), &[".".to_string()], false);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        let main = fs::read_to_string(temp_dir.path().join("test.rs")).unwrap();
+        assert_eq!(main, "// NOTE: test\nfn main() {}\n");
+
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        let counts = fs::read_to_string(temp_dir.path().join("ratchet-counts.toml")).unwrap();
+        assert!(counts.contains("\".\" = 0"), "{counts}");
+    });
+}
+
+#[test]
+fn test_fix_leaves_suppressed_lines_unchanged() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+        add_todo_fix(temp_dir.path());
+        // Split so that this file does not carry the directives itself
+        let content = concat!(
+            "// TODO: one\n",
+            "// TODO: two // ",
+            "ratchets-ignore[no-todo-comments]: tracked upstream\n",
+            "// ",
+            "ratchets-ignore-next-line[no-todo-comments]: tracked upstream\n",
+            "// TODO: three\n",
+        );
+        fs::write(temp_dir.path().join("test.rs"), content).unwrap();
+
+        let exit_code = cli::fix::run_fix(None, &[], false);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+        let fixed = fs::read_to_string(temp_dir.path().join("test.rs")).unwrap();
+        assert_eq!(fixed, content.replacen("TODO: one", "NOTE: one", 1));
+    });
+}
+
+#[test]
+fn test_fix_rejects_unknown_and_unfixable_rules() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+
+        let exit_code = cli::fix::run_fix(Some("no-todo-comments"), &[], false);
+        assert_eq!(exit_code, cli::common::EXIT_ERROR);
+
+        add_todo_fix(temp_dir.path());
+        let exit_code = cli::fix::run_fix(Some("no-such-rule"), &[], false);
+        assert_eq!(exit_code, cli::common::EXIT_ERROR);
+    });
+}
+
+#[test]
+fn test_test_rules_runs_builtin_and_custom_examples() {
+    with_temp_dir(|temp_dir| {
+        // Builtin rules carry passing examples and need no ratchets.toml
+        let exit_code = cli::test_rules::run_test_rules(None);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+
+        let custom_dir = temp_dir.path().join("ratchets").join("regex");
+        fs::create_dir_all(&custom_dir).unwrap();
+        let rule = r#"
+[rule]
+id = "no-print-debug"
+description = "No debug prints"
+severity = "warning"
+
+[match]
+pattern = "print_debug\\("
+languages = ["rust"]
+
+[[test]]
+code = "fn main() { print_debug(1); }"
+expect = 2
+"#;
+        fs::write(custom_dir.join("no-print-debug.toml"), rule).unwrap();
+
+        let exit_code = cli::test_rules::run_test_rules(Some("no-print-debug"));
+        assert_eq!(exit_code, cli::common::EXIT_EXCEEDED);
+
+        let exit_code = cli::test_rules::run_test_rules(Some("no-such-rule"));
+        assert_eq!(exit_code, cli::common::EXIT_ERROR);
+
+        fs::write(
+            custom_dir.join("no-print-debug.toml"),
+            rule.replace("expect = 2", "expect = 1"),
+        )
+        .unwrap();
+        let exit_code = cli::test_rules::run_test_rules(Some("no-print-debug"));
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+    });
+}
+
+#[test]
+fn test_explain_enabled_disabled_and_unknown_rules() {
+    with_temp_dir(|temp_dir| {
+        setup_basic_project(temp_dir.path());
+
+        let exit_code = cli::explain::run_explain("no-todo-comments", cli::OutputFormat::Human);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+
+        let exit_code = cli::explain::run_explain("no-todo-comments", cli::OutputFormat::Jsonl);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+
+        // Builtin rules outside the enabled set can be explained too
+        let exit_code = cli::explain::run_explain("no-unwrap", cli::OutputFormat::Human);
+        assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
+
+        let exit_code = cli::explain::run_explain("no-such-rule", cli::OutputFormat::Human);
+        assert_eq!(exit_code, cli::common::EXIT_ERROR);
+    });
+}
+
+#[test]
+fn test_merge_driver_minimum_wins() {
+    with_temp_dir(|temp_dir| {
+        // Create three versions with different counts
+        let base = r#"
+[no-todo-comments]
+"." = 20
+"#;
+        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+
+        let ours = r#"
+[no-todo-comments]
+"." = 15
+"#;
+        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
+
+        let theirs = r#"
+[no-todo-comments]
+"." = 18
+"#;
+        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
+
+        let exit_code =
+            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
+
+        assert_eq!(exit_code, 0); // EXIT_SUCCESS
+
+        // Verify merged result has minimum (15)
+        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
+        assert!(merged.contains("15"));
+        assert!(!merged.contains("18"));
+        assert!(!merged.contains("20"));
+    });
+}
+
+#[test]
+fn test_merge_driver_new_rule_in_ours() {
+    with_temp_dir(|temp_dir| {
+        let base = "";
+        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+
+        let ours = r#"
+[no-todo-comments]
+"." = 10
+"#;
+        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
+
+        let theirs = "";
+        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
+
+        let exit_code =
+            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
+
+        assert_eq!(exit_code, 0);
+
+        // Verify new rule is preserved
+        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
+        assert!(merged.contains("no-todo-comments"));
+        assert!(merged.contains("10"));
+    });
+}
+
+#[test]
+fn test_merge_driver_new_rule_in_theirs() {
+    with_temp_dir(|temp_dir| {
+        let base = "";
+        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+
+        let ours = "";
+        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
+
+        let theirs = r#"
+[no-unwrap]
+"." = 5
+"#;
+        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
+
+        let exit_code =
+            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
+
+        assert_eq!(exit_code, 0);
+
+        // Verify new rule from theirs is merged
+        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
+        assert!(merged.contains("no-unwrap"));
+        assert!(merged.contains("5"));
+    });
+}
+
+#[test]
+fn test_merge_driver_multiple_rules() {
+    with_temp_dir(|temp_dir| {
+        let base = r#"
+[no-unwrap]
+"." = 20
+[no-todo-comments]
+"." = 30
+"#;
+        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
+
+        let ours = r#"
+[no-unwrap]
+"." = 15
+[no-todo-comments]
+"." = 30
+"#;
+        fs::write(temp_dir.path().join("ours.toml"), ours).unwrap();
+
+        let theirs = r#"
+[no-unwrap]
+"." = 18
+[no-todo-comments]
+"." = 25
+"#;
+        fs::write(temp_dir.path().join("theirs.toml"), theirs).unwrap();
+
+        let exit_code =
+            cli::merge_driver::run_merge_driver("base.toml", "ours.toml", "theirs.toml");
+
+        assert_eq!(exit_code, 0);
+
+        // Verify minimum for each rule
+        let merged = fs::read_to_string(temp_dir.path().join("ours.toml")).unwrap();
+        assert!(merged.contains("no-unwrap"));
+        assert!(merged.contains("no-todo-comments"));
+        // no-unwrap: min(15, 18) = 15
+        // no-todo-comments: min(30, 25) = 25
+        // Check that both values are in the file
+        let parsed = ratchets::config::counts::CountsManager::parse(&merged).unwrap();
+        let no_unwrap = ratchets::types::RuleId::new("no-unwrap").unwrap();
+        let no_todo = ratchets::types::RuleId::new("no-todo-comments").unwrap();
+        assert_eq!(parsed.get_budget(&no_unwrap, Path::new(".")), 15);
+        assert_eq!(parsed.get_budget(&no_todo, Path::new(".")), 25);
+    });
+}
+
+#[test]
+fn test_merge_driver_multiple_regions() {
+    with_temp_dir(|temp_dir| {
+        let base = r#"
+[no-todo-comments]
+"." = 20
+"src" = 15
+"#;
+        fs::write(temp_dir.path().join("base.toml"), base).unwrap();
 
         let ours = r#"
 [no-todo-comments]
@@ -1032,8 +2306,15 @@ pattern = "TODO"
         // Create file with TODO
         fs::write(temp_dir.path().join("test.rs"), "// TODO: test\n").unwrap();
 
-        let exit_code =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit_code = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
 
         // Should fail with empty counts (budget defaults to 0)
         assert_eq!(exit_code, cli::common::EXIT_EXCEEDED);
@@ -1048,7 +2329,15 @@ fn test_bump_creates_counts_file_if_missing() {
         // Remove counts file
         fs::remove_file(temp_dir.path().join("ratchet-counts.toml")).unwrap();
 
-        let exit_code = cli::bump::run_bump(Some("no-todo-comments"), ".", Some(10), false);
+        let exit_code = cli::bump::run_bump(
+            Some("no-todo-comments"),
+            ".",
+            Some(10),
+            false,
+            false,
+            false,
+            false,
+        );
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -1107,7 +2396,7 @@ pattern = "TODO"
         // Create file with violations
         fs::write(temp_dir.path().join("test.rs"), "// TODO: test\n").unwrap();
 
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
 
         // Should succeed and tighten all rules
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
@@ -1171,7 +2460,7 @@ pattern = "FIXME"
         .unwrap();
 
         // Run bump --all
-        let exit_code = cli::bump::run_bump(None, ".", None, true);
+        let exit_code = cli::bump::run_bump(None, ".", None, true, false, false, false);
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -1258,7 +2547,7 @@ pattern = "FIXME"
         .unwrap();
 
         // Run bump --all
-        let exit_code = cli::bump::run_bump(None, ".", None, true);
+        let exit_code = cli::bump::run_bump(None, ".", None, true, false, false, false);
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -1321,7 +2610,7 @@ pattern = "TODO"
         fs::write(temp_dir.path().join("test.rs"), "fn main() {}\n").unwrap();
 
         // Run bump --all
-        let exit_code = cli::bump::run_bump(None, ".", None, true);
+        let exit_code = cli::bump::run_bump(None, ".", None, true, false, false, false);
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -1399,7 +2688,7 @@ pattern = "FIXME"
         .unwrap();
 
         // Run bump --all
-        let exit_code = cli::bump::run_bump(None, ".", None, true);
+        let exit_code = cli::bump::run_bump(None, ".", None, true, false, false, false);
 
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
@@ -1434,7 +2723,7 @@ include = ["**/*.rs"]
         fs::write(temp_dir.path().join("ratchet-counts.toml"), "").unwrap();
 
         // Run bump --all
-        let exit_code = cli::bump::run_bump(None, ".", None, true);
+        let exit_code = cli::bump::run_bump(None, ".", None, true, false, false, false);
 
         // Should fail with error since no rules are enabled
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -1446,7 +2735,7 @@ fn test_bump_all_missing_config() {
     with_temp_dir(|_temp_dir| {
         // Don't create config
 
-        let exit_code = cli::bump::run_bump(None, ".", None, true);
+        let exit_code = cli::bump::run_bump(None, ".", None, true, false, false, false);
 
         // Should fail
         assert_eq!(exit_code, cli::common::EXIT_ERROR);
@@ -1529,7 +2818,7 @@ pattern = "TODO"
         fs::write(temp_dir.path().join("c/file.rs"), "// TODO: in c\n").unwrap();
 
         // Run tighten
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         // Read and verify the counts file
@@ -1642,7 +2931,7 @@ pattern = "TODO"
         fs::write(temp_dir.path().join("root.rs"), "// TODO: root\n").unwrap();
 
         // Run tighten
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         // Read and verify the counts file
@@ -1758,7 +3047,7 @@ pattern = "TODO"
         .unwrap();
 
         // Run tighten
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         // Read and verify the counts file
@@ -1883,7 +3172,7 @@ pattern = "TODO"
         fs::write(temp_dir.path().join("tests/test.rs"), "fn test() {}\n").unwrap();
 
         // Run tighten
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         // Read and verify the counts file
@@ -1973,7 +3262,7 @@ fn test_tighten_locks_zero_violation_budget_to_zero() {
     with_temp_dir(|temp_dir| {
         setup_zero_violation_budget_project(temp_dir.path());
 
-        let exit_code = cli::tighten::run_tighten(None, None);
+        let exit_code = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         let counts_content =
@@ -1997,7 +3286,7 @@ fn test_tighten_locks_zero_violation_budget_with_rule_filter() {
     with_temp_dir(|temp_dir| {
         setup_zero_violation_budget_project(temp_dir.path());
 
-        let exit_code = cli::tighten::run_tighten(Some("no-todo-comments"), None);
+        let exit_code = cli::tighten::run_tighten(Some("no-todo-comments"), None, false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         let counts_content =
@@ -2015,7 +3304,7 @@ fn test_tighten_locks_zero_violation_budget_with_region_filter() {
     with_temp_dir(|temp_dir| {
         setup_zero_violation_budget_project(temp_dir.path());
 
-        let exit_code = cli::tighten::run_tighten(None, Some("src"));
+        let exit_code = cli::tighten::run_tighten(None, Some("src"), false, false);
         assert_eq!(exit_code, cli::common::EXIT_SUCCESS);
 
         let counts_content =
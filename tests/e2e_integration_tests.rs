@@ -249,8 +249,15 @@ include = ["**/*.rs"]
         fs::write("ratchets.toml", config).unwrap();
 
         // Step 3: Run check (should find violations but with default budget 0, will fail)
-        let check_exit =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let check_exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(check_exit, cli::common::EXIT_EXCEEDED);
 
         // Step 4: Set budgets high enough to pass
@@ -262,8 +269,15 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Step 5: Check should now pass
-        let check_exit =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let check_exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(check_exit, cli::common::EXIT_SUCCESS);
 
         // Step 6: Remove one TODO
@@ -272,20 +286,34 @@ include = ["**/*.rs"]
         fs::write("src/main.rs", cleaned).unwrap();
 
         // Step 7: Tighten should reduce budget
-        let tighten_exit = cli::tighten::run_tighten(None, None);
+        let tighten_exit = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(tighten_exit, cli::common::EXIT_SUCCESS);
 
         // Step 8: Check should still pass
-        let check_exit =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let check_exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(check_exit, cli::common::EXIT_SUCCESS);
 
         // Step 9: Add a new TODO (should exceed budget now)
         fs::write("src/new_file.rs", "// TODO: new violation\nfn new_fn() {}").unwrap();
 
         // Step 10: Check should fail with exceeded
-        let check_exit =
-            cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let check_exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(check_exit, cli::common::EXIT_EXCEEDED);
     });
 }
@@ -323,7 +351,15 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Check should pass (within all region budgets)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Now set src/legacy budget to 0
@@ -337,7 +373,15 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Check should fail (src/legacy exceeds budget)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
     });
 }
@@ -381,7 +425,15 @@ include = ["**/*.rs"]
         fs::write("ratchets.toml", config).unwrap();
 
         // Check should fail (no budgets set)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
 
         // Set budgets for both rules. This test sets its own `no-unwrap`
@@ -413,7 +465,15 @@ include = ["**/*.rs"]
         fs::write("ratchet-counts.toml", counts).unwrap();
 
         // Check should pass
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Add more violations
@@ -430,7 +490,15 @@ fn process() {
         .unwrap();
 
         // Check should fail (both rules exceeded)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
     });
 }
@@ -475,7 +543,15 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Check should pass (3 TODOs, budget 3)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Set specific budget for src/core
@@ -487,14 +563,30 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Check should pass (core has 1 TODO, budget 1)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Add another TODO in core
         fs::write(core.join("processor.rs"), "// TODO: 4\n").unwrap();
 
         // Check should fail (core has 2 TODOs, budget 1)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
     });
 }
@@ -553,14 +645,30 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Check should pass (only 1 TODO counted, gitignored files excluded)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Add another TODO in non-ignored location
         fs::write(src.join("lib.rs"), "// TODO: another\n").unwrap();
 
         // Check should fail
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
     });
 }
@@ -592,7 +700,15 @@ include = ["**/*.rs"]
 "#;
         write_counts_with_builtin_budgets(counts);
 
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // EXIT_EXCEEDED (1): Over budget
@@ -602,13 +718,29 @@ include = ["**/*.rs"]
 "#;
         write_counts_with_builtin_budgets(counts);
 
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
 
         // EXIT_ERROR (2): Missing config
         fs::remove_file("ratchets.toml").unwrap();
 
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_ERROR);
 
         // EXIT_PARSE_ERROR (3): Invalid TOML syntax
@@ -620,7 +752,15 @@ languages = ["rust"
 "#;
         fs::write("ratchets.toml", invalid_config).unwrap();
 
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_PARSE_ERROR);
     });
 }
@@ -725,7 +865,15 @@ include = ["**/*.rs"]
         fs::write("ratchet-counts.toml", counts).unwrap();
 
         // Check should pass (all within budgets)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // List rules
@@ -733,11 +881,19 @@ include = ["**/*.rs"]
         assert_eq!(list_exit, cli::common::EXIT_SUCCESS);
 
         // Tighten - should work since no region is exceeded
-        let tighten_exit = cli::tighten::run_tighten(None, None);
+        let tighten_exit = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(tighten_exit, cli::common::EXIT_SUCCESS);
 
         // Check should still pass
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
     });
 }
@@ -782,14 +938,22 @@ include = ["**/*.rs"]
 "#;
         write_counts_with_builtin_budgets(counts);
 
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Week 2: Clean up 2 TODOs
         fs::remove_file(src.join("module1.rs")).unwrap();
         fs::remove_file(src.join("module2.rs")).unwrap();
 
-        let tighten_exit = cli::tighten::run_tighten(Some("no-todo-comments"), None);
+        let tighten_exit = cli::tighten::run_tighten(Some("no-todo-comments"), None, false, false);
         assert_eq!(tighten_exit, cli::common::EXIT_SUCCESS);
 
         // Week 3: Clean up 3 more
@@ -797,18 +961,34 @@ include = ["**/*.rs"]
         fs::remove_file(src.join("module4.rs")).unwrap();
         fs::remove_file(src.join("module5.rs")).unwrap();
 
-        let tighten_exit = cli::tighten::run_tighten(Some("no-todo-comments"), None);
+        let tighten_exit = cli::tighten::run_tighten(Some("no-todo-comments"), None, false, false);
         assert_eq!(tighten_exit, cli::common::EXIT_SUCCESS);
 
         // Should still pass
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Week 4: Someone accidentally adds a new TODO
         fs::write(src.join("new_feature.rs"), "// TODO: implement\n").unwrap();
 
         // Check should fail (budget exceeded)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
     });
 }
@@ -853,12 +1033,22 @@ include = ["**/*.rs"]
             cli::OutputFormat::Human,
             false,
             None,
+            false,
+            false,
+            &[],
         );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Check only src
-        let exit =
-            cli::check::run_check(&["src".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &["src".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
     });
 }
@@ -893,7 +1083,15 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Check with JSONL format - should succeed
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Jsonl, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Jsonl,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // List with JSONL format
@@ -931,7 +1129,7 @@ include = ["**/*.rs"]
         write_counts_with_builtin_budgets(counts);
 
         // Tighten should fail (violations exceed budget)
-        let exit = cli::tighten::run_tighten(None, None);
+        let exit = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(exit, cli::common::EXIT_EXCEEDED);
     });
 }
@@ -955,7 +1153,15 @@ include = ["**/*.rs"]
         fs::write("ratchets.toml", config).unwrap();
 
         // Check empty project - should succeed with warning
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
     });
 }
@@ -985,11 +1191,19 @@ include = ["**/*.rs"]
         fs::write("ratchets.toml", config).unwrap();
 
         // Check should succeed (no violations)
-        let exit = cli::check::run_check(&[".".to_string()], cli::OutputFormat::Human, false, None);
+        let exit = cli::check::run_check(
+            &[".".to_string()],
+            cli::OutputFormat::Human,
+            false,
+            None,
+            false,
+            false,
+            &[],
+        );
         assert_eq!(exit, cli::common::EXIT_SUCCESS);
 
         // Tighten should succeed but report no changes
-        let tighten_exit = cli::tighten::run_tighten(None, None);
+        let tighten_exit = cli::tighten::run_tighten(None, None, false, false);
         assert_eq!(tighten_exit, cli::common::EXIT_SUCCESS);
     });
 }
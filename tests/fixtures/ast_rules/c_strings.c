@@ -0,0 +1,18 @@
+#include <stdio.h>
+#include <string.h>
+
+void copy_name(char *dest, size_t size, const char *src)
+{
+    strcpy(dest, src);
+    strcat(dest, "!");
+    strncpy(dest, src, size);
+}
+
+void format_id(char *dest, size_t size, int id) {
+    sprintf(dest, "id-%d", id);
+    snprintf(dest, size, "id-%d", id);
+}
+
+void read_line(char *line) {
+    gets(line);
+}
@@ -0,0 +1,17 @@
+#include <cstdio>
+#include <cstring>
+#include <string>
+
+void copy_name(char *dest, std::size_t size, const std::string &src)
+{
+    strcpy(dest, src.c_str());
+    std::sprintf(dest, "%s", src.c_str());
+    std::snprintf(dest, size, "%s", src.c_str());
+}
+
+int percent(double ratio, const void *data) {
+    int value = (int)(ratio * 100);
+    auto bytes = (const char *)data;
+    auto rounded = static_cast<int>(ratio);
+    return value + rounded + bytes[0];
+}
@@ -0,0 +1,27 @@
+package com.acme.orders;
+
+import java.io.IOException;
+
+public class OrderService {
+    private final Logger logger = Logger.getLogger("orders");
+
+    public void place(Order order) {
+        System.out.println("placing " + order);
+        try {
+            repository.save(order);
+        } catch (IOException e) {
+            logger.warning("retrying");
+        } catch (Exception e) {
+            System.err.println(e.getMessage());
+        }
+    }
+
+    public void cancel(Order order) {
+        try {
+            repository.delete(order);
+        } catch (IllegalStateException | Throwable t) {
+            logger.severe("cancel failed");
+        }
+        logger.info("cancelled");
+    }
+}
@@ -0,0 +1,9 @@
+// Test fixture for JSX support in JavaScript
+
+export function Banner({ message }) {
+    return (
+        <div className="banner" style={{ padding: 4 }}>  {/* Line 5 - inline style */}
+            <p>{message}</p>
+        </div>
+    );
+}
@@ -0,0 +1,25 @@
+package com.acme.orders
+
+import java.io.IOException
+
+class OrderService(private val repository: Repository) {
+    fun place(order: Order) {
+        println("placing $order")
+        try {
+            repository.save(order)
+        } catch (e: IOException) {
+            logger.warn("retrying")
+        } catch (e: Exception) {
+            print(e.message)
+        }
+    }
+
+    fun cancel(order: Order) {
+        try {
+            repository.delete(order)
+        } catch (t: kotlin.Throwable) {
+            logger.error("cancel failed")
+        }
+        logger.println("cancelled")
+    }
+}
@@ -0,0 +1,24 @@
+// Test fixture for TSX support
+// A React component mixing type annotations and JSX
+
+import React from "react";
+
+interface Props {
+    items: any[];  // Line 7 - any
+    title: string;
+}
+
+export function List({ items, title }: Props) {
+    return (
+        <section className="list">
+            <h2 style={{ color: "red" }}>{title}</h2>  {/* Line 14 - inline style */}
+            <ul>
+                {items.map((item: any) => (  // Line 16 - any
+                    <li key={item.id}>{item.name}</li>
+                ))}
+            </ul>
+        </section>
+    );
+}
+
+const label = <span>{(42 as number).toString()}</span>;
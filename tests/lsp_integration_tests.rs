@@ -0,0 +1,201 @@
+//! Integration tests for `ratchets lsp`
+//!
+//! These tests spawn the binary in a temporary project and drive it over
+//! stdin/stdout with `Content-Length` framed JSON-RPC messages.
+
+use ratchets::cli::lsp::{read_message, write_message};
+use serde_json::{Value, json};
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use tempfile::TempDir;
+
+/// Create a project with a budget of 1 for a `TODO` rule
+fn setup_project(root: &Path) {
+    let config = r#"
+enabled_ratchets = ["no-todo-comments"]
+
+[ratchets]
+version = "2"
+languages = ["rust"]
+include = ["**/*.rs"]
+"#;
+    fs::write(root.join("ratchets.toml"), config).unwrap();
+
+    let counts = r#"
+[no-todo-comments]
+"." = 1
+"#;
+    fs::write(root.join("ratchet-counts.toml"), counts).unwrap();
+
+    let rule_dir = root.join("ratchets").join("regex");
+    fs::create_dir_all(&rule_dir).unwrap();
+    let rule = r#"
+[rule]
+id = "no-todo-comments"
+description = "Disallow TODO comments"
+severity = "error"
+
+[match]
+pattern = "TODO"
+"#;
+    fs::write(rule_dir.join("no-todo-comments.toml"), rule).unwrap();
+
+    fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+    fs::write(root.join("other.rs"), "// TODO: existing\n").unwrap();
+}
+
+/// A running `ratchets lsp` process
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn spawn(root: &Path) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ratchets"))
+            .arg("lsp")
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut self.stdin, &message).unwrap();
+    }
+
+    /// Send a request and return its response, skipping notifications
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        write_message(&mut self.stdin, &message).unwrap();
+        loop {
+            let message = read_message(&mut self.stdout).unwrap().unwrap();
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+
+    /// Wait for the next published diagnostics
+    fn diagnostics(&mut self) -> Value {
+        loop {
+            let message = read_message(&mut self.stdout).unwrap().unwrap();
+            if message["method"] == "textDocument/publishDiagnostics" {
+                return message["params"].clone();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_and_hover() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+    let root = temp_dir.path().canonicalize().unwrap();
+    let uri = format!("file://{}", root.join("main.rs").display());
+
+    let mut client = Client::spawn(&root);
+    let response = client.request(
+        "initialize",
+        json!({ "rootUri": format!("file://{}", root.display()), "capabilities": {} }),
+    );
+    assert_eq!(response["result"]["capabilities"]["hoverProvider"], true);
+    client.notify("initialized", json!({}));
+
+    // Clean on open
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": {
+            "uri": uri, "languageId": "rust", "version": 1, "text": "fn main() {}\n",
+        }}),
+    );
+    let published = client.diagnostics();
+    assert_eq!(published["uri"], uri);
+    assert_eq!(published["diagnostics"], json!([]));
+
+    // An unsaved edit is reported with UTF-16 columns: "😀" is two code units
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": uri, "version": 2 },
+            "contentChanges": [{ "text": "fn main() {}\n/* 😀 TODO */\n" }],
+        }),
+    );
+    let published = client.diagnostics();
+    let diagnostics = published["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["code"], "no-todo-comments");
+    assert_eq!(diagnostic["severity"], 1);
+    assert_eq!(
+        diagnostic["range"],
+        json!({
+            "start": { "line": 1, "character": 6 },
+            "end": { "line": 1, "character": 10 },
+        })
+    );
+
+    // Hover counts the unsaved edit together with the other file on disk
+    let response = client.request(
+        "textDocument/hover",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 1, "character": 7 },
+        }),
+    );
+    let hover = response["result"]["contents"]["value"].as_str().unwrap();
+    assert!(hover.contains("Disallow TODO comments"), "{hover}");
+    assert!(
+        hover.contains("Region `.`: 2 / 1 allowed (over budget)"),
+        "{hover}"
+    );
+
+    // Nothing to show away from a violation
+    let response = client.request(
+        "textDocument/hover",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 0, "character": 0 },
+        }),
+    );
+    assert_eq!(response["result"], Value::Null);
+
+    // Closing clears the document's diagnostics
+    client.notify(
+        "textDocument/didClose",
+        json!({ "textDocument": { "uri": uri } }),
+    );
+    assert_eq!(client.diagnostics()["diagnostics"], json!([]));
+
+    let response = client.request("shutdown", Value::Null);
+    assert_eq!(response["result"], Value::Null);
+    client.notify("exit", Value::Null);
+    assert_eq!(client.child.wait().unwrap().code(), Some(0));
+}
+
+#[test]
+fn test_lsp_exit_without_shutdown() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+
+    let mut client = Client::spawn(temp_dir.path());
+    client.notify("exit", Value::Null);
+    assert_eq!(client.child.wait().unwrap().code(), Some(1));
+}
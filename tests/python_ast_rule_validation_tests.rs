@@ -472,8 +472,8 @@ fn mutable_attr_frozen_non_matches() {
 //     targets) — incorrectly skipped
 // And gives false positives on `def NAME(` appearing inside docstrings.
 //
-// Our query captures every `function_definition` and uses the
-// `nested_in_function_definition` post-filter to keep only those whose AST
+// Our query captures every `function_definition` and uses a
+// `[[match.where]]` ancestor condition to keep only those whose AST
 // ancestor chain hits a `function_definition` before any `class_definition`.
 // This handles direct nested functions, decorated-wrapper nested functions,
 // and functions nested inside `if`/`with`/`for`/`try` blocks of an enclosing
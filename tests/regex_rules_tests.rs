@@ -57,7 +57,9 @@ fn test_todo_rule_finds_todos() {
         file_path: &file_path,
         content: &content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     // Execute rule
@@ -96,7 +98,9 @@ fn test_fixme_rule_finds_fixmes() {
         file_path: &file_path,
         content: &content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     // Execute rule
@@ -123,7 +127,9 @@ fn test_position_accuracy() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -148,7 +154,9 @@ fn test_position_accuracy_first_line() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -171,7 +179,9 @@ fn test_multiline_content() {
         file_path: &file_path,
         content: &content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -193,7 +203,9 @@ fn test_case_insensitive_matching() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -222,7 +234,9 @@ fn test_snippet_extraction_accuracy() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -243,7 +257,9 @@ fn test_no_violations_returns_empty() {
         file_path: &file_path,
         content: &content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -262,7 +278,9 @@ fn test_multiple_violations_same_file() {
         file_path: &file_path,
         content: &content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -327,7 +345,9 @@ fn test_registry_execute_all_rules() {
         file_path: &file_path,
         content: &content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     // Execute all rules
@@ -386,7 +406,9 @@ fn test_end_line_and_column() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -410,7 +432,9 @@ fn test_violation_file_path() {
         file_path: &file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -432,7 +456,9 @@ fn test_multiple_rules_same_content() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     // Execute both rules
@@ -460,7 +486,9 @@ fn test_word_boundary_matching() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -480,7 +508,9 @@ fn test_empty_file() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);
@@ -499,7 +529,9 @@ fn test_only_whitespace() {
         file_path,
         content,
         ast: None,
+        line_offsets: None,
         region_resolver: None,
+        walked_files: None,
     };
 
     let violations = rule.execute(&ctx);